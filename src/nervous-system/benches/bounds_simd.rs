@@ -0,0 +1,23 @@
+//! Benchmark: checagem de limites de preço escalar vs vetorizada num lote de 10k
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use tensorwerk_nervous::validation::integrity::DataBounds;
+
+const BATCH: usize = 10_000;
+
+fn bench_price_bounds(c: &mut Criterion) {
+    let bounds = DataBounds::crypto();
+    // Lote todo válido: as duas versões percorrem os 10k preços
+    let prices: Vec<f64> = (0..BATCH).map(|i| 50_000.0 + (i % 1000) as f64 * 0.01).collect();
+
+    let mut group = c.benchmark_group("price_bounds");
+    group.throughput(Throughput::Elements(BATCH as u64));
+    group.bench_function("scalar", |b| {
+        b.iter(|| black_box(&prices).iter().position(|&price| bounds.validate_price(price, "price").is_err()))
+    });
+    group.bench_function("simd", |b| b.iter(|| bounds.validate_prices_simd(black_box(&prices))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_price_bounds);
+criterion_main!(benches);
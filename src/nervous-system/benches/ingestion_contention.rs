@@ -0,0 +1,66 @@
+//! Benchmark: ingestão multi-thread disputando os contadores de estatísticas.
+//!
+//! `atomic` é o ingestor como está; `mutex` soma a cada mensagem as
+//! atualizações do antigo `Mutex<IngestionStats>` (um lock com os três
+//! contadores), e a diferença entre os dois é o custo do lock disputado.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::time::Instant;
+use tensorwerk_nervous::ingestion::zero_copy::{MarketDataIngestor, MessageHeader};
+
+const MESSAGES_PER_THREAD: usize = 2_000;
+
+/// Estatísticas como eram antes dos contadores atômicos.
+#[derive(Default)]
+struct LockedStats {
+    messages_received: u64,
+    bytes_received: u64,
+    last_message_time: Option<Instant>,
+}
+
+/// Arena nova por iteração: cada mensagem ocupa 128 bytes alinhados.
+fn fresh_ingestor(threads: usize) -> Arc<MarketDataIngestor> {
+    Arc::new(MarketDataIngestor::new(threads * MESSAGES_PER_THREAD * 128, 1))
+}
+
+fn ingest(ingestor: &MarketDataIngestor, threads: usize, frame: &bytes::BytesMut, locked: Option<&Mutex<LockedStats>>) {
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(move || {
+                for _ in 0..MESSAGES_PER_THREAD {
+                    ingestor.process_raw_data(&mut frame.clone()).unwrap();
+                    if let Some(locked) = locked {
+                        let mut stats = locked.lock();
+                        stats.messages_received += 1;
+                        stats.bytes_received += frame.len() as u64;
+                        stats.last_message_time = Some(Instant::now());
+                    }
+                }
+            });
+        }
+    });
+}
+
+fn bench_contention(c: &mut Criterion) {
+    let frame = MessageHeader::builder().frame(&[0u8; 64]);
+    let mut group = c.benchmark_group("ingestion_contention");
+
+    for threads in [1usize, 2, 4, 8] {
+        group.bench_with_input(BenchmarkId::new("mutex", threads), &threads, |b, &threads| {
+            b.iter_with_setup(
+                || (fresh_ingestor(threads), Mutex::new(LockedStats::default())),
+                |(ingestor, locked)| ingest(&ingestor, threads, &frame, Some(&locked)),
+            )
+        });
+        group.bench_with_input(BenchmarkId::new("atomic", threads), &threads, |b, &threads| {
+            b.iter_with_setup(|| fresh_ingestor(threads), |ingestor| ingest(&ingestor, threads, &frame, None))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_contention);
+criterion_main!(benches);
@@ -0,0 +1,35 @@
+//! Benchmark: ingestão de payloads grandes com cópia comum vs stores não-temporais
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use tensorwerk_nervous::ingestion::zero_copy::{MarketDataIngestor, MessageHeader};
+
+const MESSAGES: usize = 256;
+
+fn bench_large_payloads(c: &mut Criterion) {
+    let mut group = c.benchmark_group("nontemporal_copy");
+
+    for payload_size in [1024usize, 16 * 1024, 256 * 1024] {
+        let frame = MessageHeader::builder().frame(&vec![0x5Au8; payload_size]);
+        let arena_size = MESSAGES * frame.len().next_multiple_of(64);
+        group.throughput(Throughput::Bytes((MESSAGES * frame.len()) as u64));
+
+        for (name, threshold) in [("copy_from_slice", usize::MAX), ("stream", 0)] {
+            group.bench_with_input(BenchmarkId::new(name, payload_size), &frame, |b, frame| {
+                b.iter_with_setup(
+                    // Canal de 1 posição: as mensagens são descartadas após a cópia
+                    || MarketDataIngestor::new(arena_size, 1).with_nontemporal_copy(threshold),
+                    |ingestor| {
+                        for _ in 0..MESSAGES {
+                            ingestor.process_raw_data(&mut frame.clone()).unwrap();
+                        }
+                    },
+                )
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_large_payloads);
+criterion_main!(benches);
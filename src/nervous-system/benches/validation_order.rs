@@ -0,0 +1,64 @@
+//! Benchmark: ChecksumFirst vs FastReject num feed dominado por mensagens inválidas
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use tensorwerk_nervous::ingestion::zero_copy::MessageHeader;
+use tensorwerk_nervous::validation::integrity::{
+    ChecksumValidator, CompositeValidator, DataBounds, SymbolValidator, ValidationOrder,
+};
+
+const PAYLOAD_SIZE: usize = 4096;
+const MESSAGES: usize = 1000;
+
+fn corrupt_heavy_feed() -> Vec<(MessageHeader, Vec<u8>)> {
+    let checksum = ChecksumValidator::new();
+    let bounds = DataBounds::crypto();
+
+    (0..MESSAGES)
+        .map(|i| {
+            let payload = vec![(i % 251) as u8; PAYLOAD_SIZE];
+            // 90% das mensagens com timestamp fora dos limites
+            let timestamp = if i % 10 == 0 { bounds.min_timestamp + i as u64 } else { 0 };
+            let header = MessageHeader {
+                magic: MessageHeader::MAGIC,
+                msg_type: 2,
+                version: 1,
+                priority: 0,
+                flags: 0,
+                timestamp,
+                payload_size: PAYLOAD_SIZE as u32,
+                checksum: checksum.calculate(&payload),
+            };
+            (header, payload)
+        })
+        .collect()
+}
+
+fn bench_orderings(c: &mut Criterion) {
+    let feed = corrupt_heavy_feed();
+    let mut group = c.benchmark_group("validation_order");
+
+    for (name, order) in [
+        ("checksum_first", ValidationOrder::ChecksumFirst),
+        ("fast_reject", ValidationOrder::FastReject),
+    ] {
+        let mut validator = CompositeValidator::new(DataBounds::crypto(), SymbolValidator::permissive())
+            .with_order(order);
+
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut rejected = 0usize;
+                for (header, payload) in &feed {
+                    if validator.validate_message(header, payload).is_err() {
+                        rejected += 1;
+                    }
+                }
+                black_box(rejected)
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_orderings);
+criterion_main!(benches);
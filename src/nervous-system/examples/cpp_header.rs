@@ -0,0 +1,20 @@
+//! Gera o wrapper C++ header-only da FFI.
+//!
+//! Uso: `cargo run --example cpp_header -- [diretório]` (padrão: stdout)
+
+use std::path::PathBuf;
+
+use tensorwerk_nervous::bridge::cpp::{ingestor_header, HEADER_NAME};
+
+fn main() -> std::io::Result<()> {
+    let header = ingestor_header();
+    match std::env::args_os().nth(1).map(PathBuf::from) {
+        Some(dir) => {
+            let path = dir.join(HEADER_NAME);
+            std::fs::write(&path, header)?;
+            eprintln!("Header gerado em {}", path.display());
+        }
+        None => print!("{}", header),
+    }
+    Ok(())
+}
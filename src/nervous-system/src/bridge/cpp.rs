@@ -0,0 +1,198 @@
+//! Gerador do wrapper C++ header-only (RAII) sobre a FFI de `ffi.rs`
+
+use crate::bridge::ffi::{IngestorStatsBinary, RustBuffer, FFI_STALE_BUFFER};
+use crate::ingestion::zero_copy::IngestionError;
+
+/// Nome sugerido para o header gerado.
+pub const HEADER_NAME: &str = "tensorwerk_ingestor.hpp";
+
+/// Header C++20 com as declarações `extern "C"` de `ffi.rs` e a classe
+/// `tensorwerk::Ingestor`: o destrutor chama `rust_ingestor_free`, `process`
+/// recebe um `std::span` e a iteração (`for (auto& msg : ingestor)`) drena o
+/// canal via `rust_ingestor_next`, devolvendo cada `Message` com
+/// `rust_buffer_free` ao sair de escopo. Layout de `RustBuffer` e códigos de
+/// erro vêm do próprio crate, então o header acompanha a FFI compilada.
+pub fn ingestor_header() -> String {
+    TEMPLATE
+        .replace("@RUST_BUFFER_SIZE@", &std::mem::size_of::<RustBuffer>().to_string())
+        .replace("@STATS_BINARY_SIZE@", &IngestorStatsBinary::SIZE.to_string())
+        .replace("@STATS_BINARY_VERSION@", &IngestorStatsBinary::VERSION.to_string())
+        .replace("@FFI_ARENA@", &IngestionError::FFI_ARENA.to_string())
+        .replace("@FFI_INCOMPLETE@", &IngestionError::FFI_INCOMPLETE.to_string())
+        .replace("@FFI_VALIDATION@", &IngestionError::FFI_VALIDATION.to_string())
+        .replace("@FFI_PAUSED@", &IngestionError::FFI_PAUSED.to_string())
+        .replace("@FFI_STALE_BUFFER@", &FFI_STALE_BUFFER.to_string())
+}
+
+const TEMPLATE: &str = r#"// Gerado por tensorwerk-nervous (bridge::cpp::ingestor_header). Não editar.
+#pragma once
+
+#include <cstddef>
+#include <cstdint>
+#include <iterator>
+#include <optional>
+#include <span>
+#include <stdexcept>
+#include <utility>
+
+extern "C" {
+struct RustIngestor;
+
+struct RustBuffer {
+    const uint8_t* ptr;
+    size_t len;
+    size_t _capacity;
+    const void* _arena_ptr;
+    uint64_t _generation;
+};
+
+struct IngestorStatsBinary {
+    uint16_t version;
+    uint16_t _reserved;
+    uint32_t size;
+    uint64_t messages_received;
+    uint64_t bytes_received;
+    uint64_t parse_errors;
+    uint64_t messages_dropped;
+    uint64_t failed_allocations;
+    uint64_t messages_filtered;
+    uint64_t rate_limited_drops;
+    uint64_t arena_used_bytes;
+    uint64_t arena_capacity_bytes;
+    uint64_t e2e_latency_p99;
+    double messages_per_second;
+};
+
+RustIngestor* rust_ingestor_new(size_t arena_capacity_mb, size_t channel_size);
+void rust_ingestor_free(RustIngestor* ingestor);
+int rust_ingestor_process(RustIngestor* ingestor, const uint8_t* raw_data, size_t len);
+int rust_ingestor_next(RustIngestor* ingestor, RustBuffer* out_buffer);
+void rust_buffer_free(RustBuffer buffer);
+int rust_buffer_is_valid(RustBuffer buffer);
+int rust_buffer_data(RustBuffer buffer, const uint8_t** out_ptr, size_t* out_len);
+int rust_ingestor_stats_binary(RustIngestor* ingestor, uint8_t* out, size_t out_len);
+}
+
+static_assert(sizeof(RustBuffer) == @RUST_BUFFER_SIZE@, "RustBuffer diverge da FFI compilada");
+static_assert(sizeof(IngestorStatsBinary) == @STATS_BINARY_SIZE@, "IngestorStatsBinary diverge da FFI compilada");
+
+namespace tensorwerk {
+
+// Códigos de retorno de Ingestor::process (além de 0 = sucesso, -1 = ponteiro nulo)
+inline constexpr int FFI_ARENA = @FFI_ARENA@;
+inline constexpr int FFI_INCOMPLETE = @FFI_INCOMPLETE@;
+inline constexpr int FFI_VALIDATION = @FFI_VALIDATION@;
+inline constexpr int FFI_PAUSED = @FFI_PAUSED@;
+// Retorno de rust_buffer_data para um buffer já liberado
+inline constexpr int FFI_STALE_BUFFER = @FFI_STALE_BUFFER@;
+inline constexpr uint16_t STATS_BINARY_VERSION = @STATS_BINARY_VERSION@;
+
+// Frame (header + payload) emprestado da arena; liberado no destrutor.
+class Message {
+public:
+    explicit Message(RustBuffer buffer) noexcept : buffer_(buffer) {}
+    Message(Message&& other) noexcept : buffer_(std::exchange(other.buffer_, RustBuffer{})) {}
+    Message& operator=(Message&& other) noexcept {
+        if (this != &other) {
+            reset();
+            buffer_ = std::exchange(other.buffer_, RustBuffer{});
+        }
+        return *this;
+    }
+    Message(const Message&) = delete;
+    Message& operator=(const Message&) = delete;
+    ~Message() { reset(); }
+
+    std::span<const uint8_t> bytes() const noexcept { return {buffer_.ptr, buffer_.len}; }
+    size_t size() const noexcept { return buffer_.len; }
+
+private:
+    void reset() noexcept {
+        if (buffer_._arena_ptr != nullptr) {
+            rust_buffer_free(std::exchange(buffer_, RustBuffer{}));
+        }
+    }
+
+    RustBuffer buffer_;
+};
+
+class Ingestor {
+public:
+    Ingestor(size_t arena_capacity_mb, size_t channel_size)
+        : handle_(rust_ingestor_new(arena_capacity_mb, channel_size)) {
+        if (handle_ == nullptr) {
+            throw std::runtime_error("rust_ingestor_new: arena não pôde ser alocada");
+        }
+    }
+    Ingestor(Ingestor&& other) noexcept : handle_(std::exchange(other.handle_, nullptr)) {}
+    Ingestor& operator=(Ingestor&& other) noexcept {
+        if (this != &other) {
+            reset();
+            handle_ = std::exchange(other.handle_, nullptr);
+        }
+        return *this;
+    }
+    Ingestor(const Ingestor&) = delete;
+    Ingestor& operator=(const Ingestor&) = delete;
+    ~Ingestor() { reset(); }
+
+    // Processa todos os frames completos; 0 em sucesso ou um dos códigos FFI_*.
+    int process(std::span<const uint8_t> data) { return rust_ingestor_process(handle_, data.data(), data.size()); }
+
+    // Próxima mensagem do canal, sem bloquear.
+    std::optional<Message> next() {
+        RustBuffer buffer{};
+        if (rust_ingestor_next(handle_, &buffer) == 1) {
+            return Message(buffer);
+        }
+        return std::nullopt;
+    }
+
+    // Iterador de entrada que drena o canal até esvaziá-lo.
+    class iterator {
+    public:
+        using iterator_category = std::input_iterator_tag;
+        using difference_type = std::ptrdiff_t;
+        using value_type = Message;
+
+        iterator() = default;
+        explicit iterator(Ingestor* ingestor) : ingestor_(ingestor), current_(ingestor->next()) {}
+
+        Message& operator*() const { return *current_; }
+        Message* operator->() const { return &*current_; }
+        iterator& operator++() {
+            current_ = ingestor_->next();
+            return *this;
+        }
+        void operator++(int) { ++*this; }
+        bool operator==(std::default_sentinel_t) const { return !current_.has_value(); }
+
+    private:
+        Ingestor* ingestor_ = nullptr;
+        mutable std::optional<Message> current_;
+    };
+
+    iterator begin() { return iterator(this); }
+    std::default_sentinel_t end() { return {}; }
+
+    // Estatísticas no layout binário fixo (polling barato, sem parse).
+    IngestorStatsBinary stats() const noexcept {
+        IngestorStatsBinary stats{};
+        rust_ingestor_stats_binary(handle_, reinterpret_cast<uint8_t*>(&stats), sizeof(stats));
+        return stats;
+    }
+
+    RustIngestor* handle() const noexcept { return handle_; }
+
+private:
+    void reset() noexcept {
+        if (handle_ != nullptr) {
+            rust_ingestor_free(std::exchange(handle_, nullptr));
+        }
+    }
+
+    RustIngestor* handle_;
+};
+
+}  // namespace tensorwerk
+"#;
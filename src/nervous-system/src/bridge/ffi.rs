@@ -1,59 +1,202 @@
 //! FFI para C++ e Python: exportação de funções Rust com memória compartilhada
 
-use std::ffi::{c_char, c_double, c_float, c_int, c_void};
-use std::mem::ManuallyDrop;
-use std::ptr::NonNull;
-use std::sync::Arc;
+use std::ffi::{c_char, c_int, c_void};
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(debug_assertions)]
+use std::{collections::HashMap, sync::LazyLock};
 
-use crate::ingestion::zero_copy::{MessageHeader, Trade, Quote};
+use bytes::BytesMut;
+#[cfg(debug_assertions)]
+use parking_lot::Mutex;
+
+use crate::ingestion::zero_copy::{IngestionError, IngestionStatsSnapshot, MarketDataIngestor, ZeroCopyBuffer};
 
 #[repr(C)]
 pub struct RustIngestor {
     _private: [u8; 0],
 }
 
+/// Mensagem emprestada por `rust_ingestor_next`: `ptr`/`len` apontam para o
+/// frame na arena, que fica reservado até `rust_buffer_free`.
 #[repr(C)]
 pub struct RustBuffer {
     ptr: *const u8,
     len: usize,
     _capacity: usize,
+    /// `Box<ZeroCopyBuffer>` dono da região (nulo em buffers vazios).
     _arena_ptr: *const c_void,
+    /// Identifica o empréstimo (0 em buffers vazios); em builds de debug,
+    /// `rust_buffer_free` o retira do registro de buffers vivos e cópias do
+    /// `RustBuffer` passam a ser reconhecidas como liberadas.
+    _generation: u64,
+}
+
+/// Buffer já liberado (ou nunca emprestado) passado a uma função que o valida.
+pub const FFI_STALE_BUFFER: c_int = -5;
+
+// Os códigos de `IngestionError::ffi_code` e os da própria FFI dividem o mesmo espaço
+const _: () = {
+    let codes = [
+        IngestionError::FFI_ARENA,
+        IngestionError::FFI_INCOMPLETE,
+        IngestionError::FFI_VALIDATION,
+        IngestionError::FFI_PAUSED,
+        FFI_STALE_BUFFER,
+    ];
+    let mut i = 0;
+    while i < codes.len() {
+        let mut j = i + 1;
+        while j < codes.len() {
+            assert!(codes[i] != codes[j], "códigos FFI repetidos");
+            j += 1;
+        }
+        i += 1;
+    }
+};
+
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+/// Geração → dono dos buffers emprestados e ainda não liberados. Só existe em
+/// debug: em release as checagens de validade não custam nada e não detectam
+/// uso após `rust_buffer_free`.
+#[cfg(debug_assertions)]
+static LIVE_BUFFERS: LazyLock<Mutex<HashMap<u64, usize>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[cfg(debug_assertions)]
+fn is_live(buffer: &RustBuffer) -> bool {
+    LIVE_BUFFERS.lock().get(&buffer._generation) == Some(&(buffer._arena_ptr as usize))
+}
+
+#[cfg(not(debug_assertions))]
+fn is_live(buffer: &RustBuffer) -> bool {
+    !buffer._arena_ptr.is_null()
+}
+
+/// O handle opaco é um `Box<MarketDataIngestor>` convertido em ponteiro.
+fn ingestor_ref<'a>(ingestor: *mut RustIngestor) -> &'a MarketDataIngestor {
+    unsafe { &*(ingestor as *const MarketDataIngestor) }
 }
 
+/// Retorna nulo se a arena não puder ser alocada (capacidade grande demais
+/// ou memória insuficiente); um pânico aqui atravessaria a fronteira FFI.
 #[no_mangle]
 pub extern "C" fn rust_ingestor_new(
     arena_capacity_mb: usize,
     channel_size: usize,
 ) -> *mut RustIngestor {
-    std::ptr::null_mut()
+    let Some(capacity) = arena_capacity_mb.checked_mul(1024 * 1024) else {
+        return std::ptr::null_mut();
+    };
+    match MarketDataIngestor::try_new(capacity, channel_size) {
+        Ok(ingestor) => Box::into_raw(Box::new(ingestor)) as *mut RustIngestor,
+        Err(_) => std::ptr::null_mut(),
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn rust_ingestor_free(ingestor: *mut RustIngestor) {
-    if !ingestor.is_null() {}
+    if !ingestor.is_null() {
+        unsafe { drop(Box::from_raw(ingestor as *mut MarketDataIngestor)); }
+    }
 }
 
+/// Processa todos os frames completos em `raw_data`. Retorna 0 em sucesso,
+/// -1 para ponteiros nulos ou, no primeiro frame com erro, o código de
+/// `IngestionError::ffi_code`: -2 arena esgotada, -3 dados incompletos,
+/// -4 header rejeitado, -6 ingestor pausado.
 #[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub extern "C" fn rust_ingestor_process(
     ingestor: *mut RustIngestor,
     raw_data: *const u8,
     len: usize,
 ) -> c_int {
     if ingestor.is_null() || raw_data.is_null() { return -1; }
+
+    let ingestor = ingestor_ref(ingestor);
+    let mut data = BytesMut::from(unsafe { std::slice::from_raw_parts(raw_data, len) });
+
+    while !data.is_empty() {
+        if let Err(e) = ingestor.process_raw_data(&mut data) {
+            return e.ffi_code();
+        }
+    }
     0
 }
 
+/// Retira a próxima mensagem do canal principal sem bloquear. Retorna 1 e
+/// preenche `out_buffer` (a devolver com `rust_buffer_free`) se havia uma
+/// mensagem, 0 com o canal vazio ou ponteiros nulos.
 #[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub extern "C" fn rust_ingestor_next(
     ingestor: *mut RustIngestor,
     out_buffer: *mut RustBuffer,
 ) -> c_int {
     if ingestor.is_null() || out_buffer.is_null() { return 0; }
-    0
+    let Ok(buffer) = ingestor_ref(ingestor).subscribe().try_recv() else { return 0 };
+
+    let slice = buffer.as_slice();
+    let (ptr, len) = (slice.as_ptr(), slice.len());
+    let owner = Box::into_raw(Box::new(buffer));
+    let generation = NEXT_GENERATION.fetch_add(1, Ordering::Relaxed);
+    #[cfg(debug_assertions)]
+    LIVE_BUFFERS.lock().insert(generation, owner as usize);
+    unsafe {
+        out_buffer.write(RustBuffer {
+            ptr,
+            len,
+            _capacity: len,
+            _arena_ptr: owner as *const c_void,
+            _generation: generation,
+        });
+    }
+    1
+}
+
+/// Libera a região de um buffer de `rust_ingestor_next`; `ptr` deixa de ser
+/// válido. Cada buffer deve ser liberado exatamente uma vez; em debug, uma
+/// segunda liberação é detectada e ignorada (com um aviso) em vez de
+/// corromper o heap.
+#[no_mangle]
+pub extern "C" fn rust_buffer_free(buffer: RustBuffer) {
+    if buffer._arena_ptr.is_null() {
+        return;
+    }
+    #[cfg(debug_assertions)]
+    if LIVE_BUFFERS.lock().remove(&buffer._generation).is_none() {
+        tracing::warn!("rust_buffer_free: buffer (geração {}) já liberado", buffer._generation);
+        return;
+    }
+    unsafe { drop(Box::from_raw(buffer._arena_ptr as *mut ZeroCopyBuffer)); }
+}
+
+/// 1 se `buffer` ainda está emprestado (não passou por `rust_buffer_free`),
+/// 0 caso contrário. Só detecta buffers liberados em builds de debug; em
+/// release, responde 1 para qualquer buffer não vazio.
+#[no_mangle]
+pub extern "C" fn rust_buffer_is_valid(buffer: RustBuffer) -> c_int {
+    is_live(&buffer) as c_int
 }
 
+/// Acesso validado aos bytes de `buffer`: preenche `out_ptr`/`out_len` e
+/// retorna 0, -1 para ponteiros nulos ou `FFI_STALE_BUFFER` se o buffer já
+/// foi liberado (detectado só em debug, como em `rust_buffer_is_valid`).
 #[no_mangle]
-pub extern "C" fn rust_buffer_free(buffer: RustBuffer) {}
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn rust_buffer_data(
+    buffer: RustBuffer,
+    out_ptr: *mut *const u8,
+    out_len: *mut usize,
+) -> c_int {
+    if out_ptr.is_null() || out_len.is_null() { return -1; }
+    if !is_live(&buffer) { return FFI_STALE_BUFFER; }
+    unsafe {
+        out_ptr.write(buffer.ptr);
+        out_len.write(buffer.len);
+    }
+    0
+}
 
 #[no_mangle]
 pub extern "C" fn rust_ingestor_get_py_buffer(
@@ -65,7 +208,7 @@ pub extern "C" fn rust_ingestor_get_py_buffer(
 }
 
 #[no_mangle]
-pub extern "C" fn rust_py_buffer_decref(py_buffer: *mut c_void) {}
+pub extern "C" fn rust_py_buffer_decref(_py_buffer: *mut c_void) {}
 
 #[no_mangle]
 pub extern "C" fn rust_copy_to_cuda(
@@ -97,29 +240,106 @@ pub struct IngestorStatsFFI {
     pub arena_used_mb: usize,
     pub arena_capacity_mb: usize,
     pub messages_per_second: f64,
+    // Campos novos sempre no final (layout append-only para estabilidade de ABI)
+    pub messages_dropped: u64,
+    pub failed_allocations: u64,
+    pub arena_used_bytes: usize,
+    pub arena_capacity_bytes: usize,
 }
 
 #[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub extern "C" fn rust_ingestor_stats(
     ingestor: *mut RustIngestor,
     out_stats: *mut IngestorStatsFFI,
 ) -> c_int {
     if ingestor.is_null() || out_stats.is_null() { return -1; }
+    let stats = ingestor_ref(ingestor).stats();
     unsafe {
-        (*out_stats).messages_received = 0;
-        (*out_stats).bytes_received = 0;
-        (*out_stats).parse_errors = 0;
-        (*out_stats).arena_used_mb = 0;
-        (*out_stats).arena_capacity_mb = 0;
-        (*out_stats).messages_per_second = 0.0;
+        (*out_stats).messages_received = stats.messages_received;
+        (*out_stats).bytes_received = stats.bytes_received;
+        (*out_stats).parse_errors = stats.parse_errors;
+        (*out_stats).arena_used_mb = stats.arena_used_mb;
+        (*out_stats).arena_capacity_mb = stats.arena_capacity_mb;
+        (*out_stats).messages_per_second = stats.messages_per_second;
+        (*out_stats).messages_dropped = stats.messages_dropped;
+        (*out_stats).failed_allocations = stats.failed_allocations;
+        (*out_stats).arena_used_bytes = stats.arena_used_bytes;
+        (*out_stats).arena_capacity_bytes = stats.arena_capacity_bytes;
     }
     0
 }
 
+/// Layout binário fixo das estatísticas para polling frequente: o lado C++
+/// copia os bytes para uma struct idêntica, sem parse. Sem padding implícito;
+/// campos novos só no final, com `version` incrementado, e `size` diz quantos
+/// bytes a versão escrita tem.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct IngestorStatsBinary {
+    pub version: u16,
+    pub _reserved: u16,
+    pub size: u32,
+    pub messages_received: u64,
+    pub bytes_received: u64,
+    pub parse_errors: u64,
+    pub messages_dropped: u64,
+    pub failed_allocations: u64,
+    pub messages_filtered: u64,
+    pub rate_limited_drops: u64,
+    pub arena_used_bytes: u64,
+    pub arena_capacity_bytes: u64,
+    pub e2e_latency_p99: u64,
+    pub messages_per_second: f64,
+}
+
+impl IngestorStatsBinary {
+    pub const VERSION: u16 = 1;
+    pub const SIZE: usize = std::mem::size_of::<Self>();
+
+    pub fn from_snapshot(stats: &IngestionStatsSnapshot) -> Self {
+        Self {
+            version: Self::VERSION,
+            _reserved: 0,
+            size: Self::SIZE as u32,
+            messages_received: stats.messages_received,
+            bytes_received: stats.bytes_received,
+            parse_errors: stats.parse_errors,
+            messages_dropped: stats.messages_dropped,
+            failed_allocations: stats.failed_allocations,
+            messages_filtered: stats.messages_filtered,
+            rate_limited_drops: stats.rate_limited_drops,
+            arena_used_bytes: stats.arena_used_bytes as u64,
+            arena_capacity_bytes: stats.arena_capacity_bytes as u64,
+            e2e_latency_p99: stats.e2e_latency_p99,
+            messages_per_second: stats.messages_per_second,
+        }
+    }
+}
+
+/// Escreve `IngestorStatsBinary` em `out`. Com `out_len` menor que a struct,
+/// escreve só o prefixo que cabe (um leitor de versão antiga recebe os campos
+/// que conhece). Retorna os bytes escritos, ou -1 para ponteiros nulos.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn rust_ingestor_stats_binary(
+    ingestor: *mut RustIngestor,
+    out: *mut u8,
+    out_len: usize,
+) -> c_int {
+    if ingestor.is_null() || out.is_null() { return -1; }
+    let stats = IngestorStatsBinary::from_snapshot(&ingestor_ref(ingestor).stats());
+    let written = out_len.min(IngestorStatsBinary::SIZE);
+    unsafe {
+        std::ptr::copy_nonoverlapping(&stats as *const IngestorStatsBinary as *const u8, out, written);
+    }
+    written as c_int
+}
+
 #[no_mangle]
 pub extern "C" fn rust_last_error() -> *const c_char {
     static mut LAST_ERROR: [u8; 256] = [0; 256];
-    unsafe { LAST_ERROR.as_ptr() as *const c_char }
+    std::ptr::addr_of!(LAST_ERROR) as *const c_char
 }
 
 #[cfg(test)]
@@ -131,4 +351,131 @@ mod tests {
         let result = rust_ingestor_process(std::ptr::null_mut(), std::ptr::null(), 0);
         assert_eq!(result, -1);
     }
+
+    #[test]
+    fn test_stats_report_drops_and_failed_allocations() {
+        use crate::ingestion::zero_copy::MessageHeader;
+
+        let ingestor = rust_ingestor_new(1, 1);
+        assert!(!ingestor.is_null());
+
+        let mut frames = Vec::new();
+        for _ in 0..3 {
+            frames.extend_from_slice(&MessageHeader::builder().frame(&[7u8; 32]));
+        }
+        assert_eq!(rust_ingestor_process(ingestor, frames.as_ptr(), frames.len()), 0);
+
+        let oversized = MessageHeader::builder().frame(&vec![0u8; 2 * 1024 * 1024]);
+        assert_eq!(rust_ingestor_process(ingestor, oversized.as_ptr(), oversized.len()), -2);
+
+        let mut stats = std::mem::MaybeUninit::<IngestorStatsFFI>::zeroed();
+        assert_eq!(rust_ingestor_stats(ingestor, stats.as_mut_ptr()), 0);
+        let stats = unsafe { stats.assume_init() };
+
+        assert_eq!(stats.messages_received, 3);
+        assert_eq!(stats.messages_dropped, 2);
+        assert_eq!(stats.failed_allocations, 1);
+        assert_eq!(stats.arena_capacity_mb, 1);
+        assert_eq!(stats.arena_capacity_bytes, 1024 * 1024);
+
+        rust_ingestor_free(ingestor);
+    }
+
+    #[test]
+    fn test_binary_stats_round_trip() {
+        use crate::ingestion::zero_copy::MessageHeader;
+
+        let ingestor = rust_ingestor_new(1, 4);
+        let frames: Vec<u8> = (0..2).flat_map(|_| MessageHeader::builder().frame(&[7u8; 32])).collect();
+        assert_eq!(rust_ingestor_process(ingestor, frames.as_ptr(), frames.len()), 0);
+
+        let mut out = [0u8; 256];
+        let written = rust_ingestor_stats_binary(ingestor, out.as_mut_ptr(), out.len());
+        assert_eq!(written as usize, IngestorStatsBinary::SIZE);
+        let stats: IngestorStatsBinary = unsafe { std::ptr::read_unaligned(out.as_ptr() as *const _) };
+        assert_eq!((stats.version, stats.size as usize), (IngestorStatsBinary::VERSION, IngestorStatsBinary::SIZE));
+        assert_eq!((stats.messages_received, stats.bytes_received), (2, frames.len() as u64));
+        assert_eq!(stats.arena_capacity_bytes, 1024 * 1024);
+
+        // Leitor antigo com buffer menor recebe só o prefixo
+        let mut prefix = [0xFFu8; 16];
+        assert_eq!(rust_ingestor_stats_binary(ingestor, prefix.as_mut_ptr(), prefix.len()), 16);
+        assert_eq!(prefix, out[..16]);
+        assert_eq!(rust_ingestor_stats_binary(std::ptr::null_mut(), out.as_mut_ptr(), out.len()), -1);
+
+        rust_ingestor_free(ingestor);
+    }
+
+    #[test]
+    fn test_process_returns_ingestion_error_codes() {
+        use crate::ingestion::zero_copy::{IngestionError, MessageHeader};
+
+        let ingestor = rust_ingestor_new(1, 4);
+        let frame = MessageHeader::builder().frame(&[7u8; 32]);
+
+        assert_eq!(rust_ingestor_process(ingestor, frame.as_ptr(), 30), IngestionError::FFI_INCOMPLETE);
+
+        let mut foreign = frame.clone();
+        foreign[..4].copy_from_slice(b"HTTP");
+        assert_eq!(rust_ingestor_process(ingestor, foreign.as_ptr(), foreign.len()), IngestionError::FFI_VALIDATION);
+
+        rust_ingestor_free(ingestor);
+    }
+
+    #[test]
+    fn test_next_lends_buffers_until_freed() {
+        use crate::ingestion::zero_copy::MessageHeader;
+
+        let ingestor = rust_ingestor_new(1, 4);
+        let frame = MessageHeader::builder().frame(&[7u8; 32]);
+        assert_eq!(rust_ingestor_process(ingestor, frame.as_ptr(), frame.len()), 0);
+
+        let mut buffer = std::mem::MaybeUninit::<RustBuffer>::uninit();
+        assert_eq!(rust_ingestor_next(ingestor, buffer.as_mut_ptr()), 1);
+        let buffer = unsafe { buffer.assume_init() };
+        assert_eq!(unsafe { std::slice::from_raw_parts(buffer.ptr, buffer.len) }, &frame[..]);
+        // Arena só rebobina (warmup a considera ociosa) depois do free
+        assert_eq!(ingestor_ref(ingestor).warmup(0), 0);
+
+        rust_buffer_free(buffer);
+        assert_eq!(ingestor_ref(ingestor).warmup(0), 1);
+        assert_eq!(rust_ingestor_next(ingestor, std::mem::MaybeUninit::uninit().as_mut_ptr()), 0);
+        rust_ingestor_free(ingestor);
+    }
+
+    /// Cópia bit a bit, como a que um chamador C mantém depois de liberar.
+    fn copy_of(buffer: &RustBuffer) -> RustBuffer {
+        unsafe { std::ptr::read(buffer) }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_freed_buffer_reported_stale() {
+        use crate::ingestion::zero_copy::MessageHeader;
+
+        let ingestor = rust_ingestor_new(1, 4);
+        let frame = MessageHeader::builder().frame(&[7u8; 32]);
+        assert_eq!(rust_ingestor_process(ingestor, frame.as_ptr(), frame.len()), 0);
+
+        let mut buffer = std::mem::MaybeUninit::<RustBuffer>::uninit();
+        assert_eq!(rust_ingestor_next(ingestor, buffer.as_mut_ptr()), 1);
+        let buffer = unsafe { buffer.assume_init() };
+        let (mut ptr, mut len) = (std::ptr::null(), 0);
+        assert_eq!(rust_buffer_is_valid(copy_of(&buffer)), 1);
+        assert_eq!(rust_buffer_data(copy_of(&buffer), &mut ptr, &mut len), 0);
+        assert_eq!((ptr, len), (buffer.ptr, frame.len()));
+
+        rust_buffer_free(copy_of(&buffer));
+        assert_eq!(rust_buffer_is_valid(copy_of(&buffer)), 0);
+        assert_eq!(rust_buffer_data(copy_of(&buffer), &mut ptr, &mut len), FFI_STALE_BUFFER);
+        // Segunda liberação é ignorada
+        rust_buffer_free(buffer);
+        rust_ingestor_free(ingestor);
+    }
+
+    #[test]
+    fn test_new_returns_null_instead_of_panicking() {
+        assert!(rust_ingestor_new(usize::MAX, 4).is_null());
+        assert!(rust_ingestor_new(usize::MAX / (1024 * 1024), 4).is_null());
+    }
 }
@@ -1 +1,4 @@
+pub mod cpp;
 pub mod ffi;
+#[cfg(all(unix, feature = "stats-socket"))]
+pub mod stats_socket;
@@ -0,0 +1,135 @@
+//! Servidor de estatísticas via Unix domain socket (feature `stats-socket`)
+//!
+//! Cada cliente conectado recebe um `IngestionStatsSnapshot` em JSON por linha,
+//! a cada intervalo configurado. Permite que um sidecar colete métricas fora do
+//! processo sem linkar a FFI.
+
+use std::io::Write;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+use crate::ingestion::zero_copy::MarketDataIngestor;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+/// Um cliente que não lê por este tempo é desconectado, em vez de travar a
+/// publicação para os demais (e o `Drop` do servidor).
+const WRITE_TIMEOUT: Duration = Duration::from_millis(100);
+
+pub struct StatsServer {
+    path: PathBuf,
+    running: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl StatsServer {
+    /// Cria o socket em `path` (removendo um socket antigo, se existir) e inicia
+    /// a thread de publicação.
+    pub fn spawn(
+        path: impl AsRef<Path>,
+        ingestor: Arc<MarketDataIngestor>,
+        interval: Duration,
+    ) -> Result<Self, std::io::Error> {
+        let path = path.as_ref().to_path_buf();
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+
+        let listener = UnixListener::bind(&path)?;
+        listener.set_nonblocking(true)?;
+
+        let running = Arc::new(AtomicBool::new(true));
+        let flag = running.clone();
+        let handle = std::thread::Builder::new()
+            .name("tensorwerk-stats".to_string())
+            .spawn(move || serve(listener, ingestor, interval, flag))?;
+
+        info!("Servidor de estatísticas em {}", path.display());
+
+        Ok(Self { path, running, handle: Some(handle) })
+    }
+
+    pub fn path(&self) -> &Path { &self.path }
+}
+
+impl Drop for StatsServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Release);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+fn serve(listener: UnixListener, ingestor: Arc<MarketDataIngestor>, interval: Duration, running: Arc<AtomicBool>) {
+    let mut clients: Vec<UnixStream> = Vec::new();
+    let mut next_publish = Instant::now();
+
+    while running.load(Ordering::Acquire) {
+        while let Ok((stream, _)) = listener.accept() {
+            if stream.set_nonblocking(false).is_ok() && stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_ok() {
+                // Novo cliente recebe um frame imediatamente
+                next_publish = Instant::now();
+                clients.push(stream);
+            }
+        }
+
+        if !clients.is_empty() && Instant::now() >= next_publish {
+            match serde_json::to_vec(&ingestor.stats()) {
+                Ok(mut frame) => {
+                    frame.push(b'\n');
+                    clients.retain_mut(|client| match client.write_all(&frame) {
+                        Ok(()) => true,
+                        Err(e) => {
+                            debug!("Cliente de estatísticas desconectado: {}", e);
+                            false
+                        }
+                    });
+                }
+                Err(e) => debug!("Falha ao serializar estatísticas: {}", e),
+            }
+            next_publish = Instant::now() + interval;
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingestion::zero_copy::MessageHeader;
+    use std::io::{BufRead, BufReader};
+
+    #[test]
+    fn test_stream_stats_frame() {
+        let path = std::env::temp_dir().join(format!("tensorwerk-stats-{}.sock", std::process::id()));
+        let ingestor = Arc::new(MarketDataIngestor::new(1024 * 1024, 16));
+        ingestor.process_raw_data(&mut MessageHeader::builder().frame(&[1u8; 16])).unwrap();
+
+        let server = StatsServer::spawn(&path, ingestor, Duration::from_millis(20)).unwrap();
+
+        // Um cliente que desconecta logo não deve derrubar o servidor
+        drop(UnixStream::connect(server.path()).unwrap());
+
+        let stream = UnixStream::connect(server.path()).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let mut reader = BufReader::new(stream);
+
+        for _ in 0..2 {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let frame: serde_json::Value = serde_json::from_str(&line).unwrap();
+            assert_eq!(frame["messages_received"], 1);
+            assert_eq!(frame["parse_errors"], 0);
+        }
+
+        drop(server);
+        assert!(!path.exists());
+    }
+}
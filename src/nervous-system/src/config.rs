@@ -0,0 +1,361 @@
+//! Configuração do pipeline lida de TOML, para reconfigurar sem recompilar
+//!
+//! ```toml
+//! [ingestion]
+//! arena_capacity = 104857600   # bytes
+//! channel_size = 10000
+//!
+//! [backpressure]               # arena esgotada: retentativas antes de descartar
+//! max_retries = 3
+//! backoff_us = 10
+//! max_total_us = 100
+//!
+//! [framing]
+//! magic = 0x4D524B54
+//! header_endian = "little"     # "big" para feeds de ordem mista (ver ByteOrder)
+//! payload_endian = "little"
+//!
+//! [validation]
+//! asset_class = "crypto"       # qual entrada de [bounds] vale
+//! symbol_whitelist = "symbols.txt"
+//! clock_skew_tolerance_ms = 1
+//!
+//! [bounds.crypto]              # ajustes sobre DataBounds::crypto()
+//! max_price = 500000.0
+//! ```
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::ingestion::byte_order::{ByteOrder, Endian};
+use crate::ingestion::zero_copy::{MarketDataIngestor, MessageHeader, RetryPolicy};
+use crate::validation::integrity::{CompositeValidator, DataBounds, SymbolValidator};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineConfig {
+    #[serde(default)]
+    pub ingestion: IngestionConfig,
+    #[serde(default)]
+    pub backpressure: BackpressureConfig,
+    #[serde(default)]
+    pub framing: FramingConfig,
+    #[serde(default)]
+    pub validation: ValidationConfig,
+    /// Ajustes de limites por classe de ativo ("crypto", "stocks").
+    #[serde(default)]
+    pub bounds: BTreeMap<AssetClass, BoundsConfig>,
+    /// Diretório do arquivo lido; caminhos relativos são resolvidos a partir dele.
+    #[serde(skip)]
+    base_dir: PathBuf,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IngestionConfig {
+    pub arena_capacity: usize,
+    pub channel_size: usize,
+}
+
+impl Default for IngestionConfig {
+    fn default() -> Self {
+        Self { arena_capacity: 100 * 1024 * 1024, channel_size: 10_000 }
+    }
+}
+
+/// Ver `RetryPolicy`. O padrão não retenta.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BackpressureConfig {
+    #[serde(default)]
+    pub max_retries: u32,
+    #[serde(default)]
+    pub backoff_us: u64,
+    #[serde(default)]
+    pub max_total_us: u64,
+}
+
+/// O protocolo só tem um enquadramento (header de 24 bytes + payload); `magic`
+/// existe para que um deploy apontado para outro protocolo falhe na carga.
+/// A ordem de bytes pode variar por seção do frame; o padrão é little-endian.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FramingConfig {
+    pub magic: u32,
+    #[serde(default)]
+    pub header_endian: Endian,
+    #[serde(default)]
+    pub payload_endian: Endian,
+}
+
+impl Default for FramingConfig {
+    fn default() -> Self {
+        Self { magic: MessageHeader::MAGIC, header_endian: Endian::Little, payload_endian: Endian::Little }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ValidationConfig {
+    #[serde(default)]
+    pub asset_class: AssetClass,
+    /// Arquivo com um símbolo por linha (linhas vazias e `#` ignoradas).
+    /// Sem ele, qualquer símbolo bem formado é aceito.
+    #[serde(default)]
+    pub symbol_whitelist: Option<PathBuf>,
+    #[serde(default = "default_clock_skew_ms")]
+    pub clock_skew_tolerance_ms: u64,
+}
+
+fn default_clock_skew_ms() -> u64 { 1 }
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self { asset_class: AssetClass::default(), symbol_whitelist: None, clock_skew_tolerance_ms: default_clock_skew_ms() }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetClass {
+    #[default]
+    Crypto,
+    Stocks,
+}
+
+impl AssetClass {
+    fn preset(self) -> DataBounds {
+        match self {
+            AssetClass::Crypto => DataBounds::crypto(),
+            AssetClass::Stocks => DataBounds::stocks(),
+        }
+    }
+}
+
+/// Campos omitidos mantêm o valor do preset da classe.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BoundsConfig {
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+    pub min_quantity: Option<f64>,
+    pub max_quantity: Option<f64>,
+    pub min_timestamp: Option<u64>,
+    pub max_timestamp: Option<u64>,
+    pub allow_negative_price: Option<bool>,
+    pub max_book_levels: Option<usize>,
+    pub max_level_quantity: Option<f64>,
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+impl PipelineConfig {
+    /// Lê e valida `path`.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        let mut config = Self::from_toml(&text)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        config.base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Ok(config)
+    }
+
+    /// Interpreta e valida um documento TOML; caminhos relativos são
+    /// resolvidos a partir do diretório atual.
+    pub fn from_toml(text: &str) -> io::Result<Self> {
+        let config: Self = toml::from_str(text).map_err(|e| invalid(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> io::Result<()> {
+        if self.ingestion.arena_capacity == 0 {
+            return Err(invalid("ingestion.arena_capacity: deve ser maior que zero".to_string()));
+        }
+        if self.ingestion.channel_size == 0 {
+            return Err(invalid("ingestion.channel_size: deve ser maior que zero".to_string()));
+        }
+        if self.backpressure.max_retries > 0 && self.backpressure.max_total_us < self.backpressure.backoff_us {
+            return Err(invalid(format!(
+                "backpressure.max_total_us: {} é menor que um único backoff ({} μs)",
+                self.backpressure.max_total_us, self.backpressure.backoff_us
+            )));
+        }
+        if self.framing.magic != MessageHeader::MAGIC {
+            return Err(invalid(format!(
+                "framing.magic: {:#010X} não suportado (o protocolo usa {:#010X})",
+                self.framing.magic,
+                MessageHeader::MAGIC
+            )));
+        }
+        for &class in self.bounds.keys() {
+            let name = format!("bounds.{:?}", class).to_lowercase();
+            let bounds = self.bounds_for(class);
+            let finite = [bounds.min_price, bounds.max_price, bounds.min_quantity, bounds.max_quantity, bounds.max_level_quantity];
+            if finite.iter().any(|v| !v.is_finite() || *v < 0.0) {
+                return Err(invalid(format!("{}: limites de preço e quantidade devem ser finitos e não negativos", name)));
+            }
+            if bounds.min_price > bounds.max_price {
+                return Err(invalid(format!("{}.max_price: {} é menor que min_price {}", name, bounds.max_price, bounds.min_price)));
+            }
+            if bounds.min_quantity > bounds.max_quantity {
+                return Err(invalid(format!(
+                    "{}.max_quantity: {} é menor que min_quantity {}",
+                    name, bounds.max_quantity, bounds.min_quantity
+                )));
+            }
+            if bounds.min_timestamp > bounds.max_timestamp {
+                return Err(invalid(format!(
+                    "{}.max_timestamp: {} é menor que min_timestamp {}",
+                    name, bounds.max_timestamp, bounds.min_timestamp
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Preset de `class` com os ajustes de `[bounds.<class>]`.
+    pub fn bounds_for(&self, class: AssetClass) -> DataBounds {
+        let mut bounds = class.preset();
+        let Some(overrides) = self.bounds.get(&class) else { return bounds };
+
+        bounds.min_price = overrides.min_price.unwrap_or(bounds.min_price);
+        bounds.max_price = overrides.max_price.unwrap_or(bounds.max_price);
+        bounds.min_quantity = overrides.min_quantity.unwrap_or(bounds.min_quantity);
+        bounds.max_quantity = overrides.max_quantity.unwrap_or(bounds.max_quantity);
+        bounds.min_timestamp = overrides.min_timestamp.unwrap_or(bounds.min_timestamp);
+        bounds.max_timestamp = overrides.max_timestamp.unwrap_or(bounds.max_timestamp);
+        bounds.allow_negative_price = overrides.allow_negative_price.unwrap_or(bounds.allow_negative_price);
+        bounds.max_book_levels = overrides.max_book_levels.unwrap_or(bounds.max_book_levels);
+        bounds.max_level_quantity = overrides.max_level_quantity.unwrap_or(bounds.max_level_quantity);
+        bounds
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_retries: self.backpressure.max_retries,
+            backoff: Duration::from_micros(self.backpressure.backoff_us),
+            max_total: Duration::from_micros(self.backpressure.max_total_us),
+        }
+    }
+
+    /// Validador com os limites da classe configurada, a whitelist de símbolos
+    /// (lida agora) e a tolerância de clock skew.
+    pub fn validator(&self) -> io::Result<CompositeValidator> {
+        let symbols = match &self.validation.symbol_whitelist {
+            Some(path) => {
+                let path = self.base_dir.join(path);
+                let text = std::fs::read_to_string(&path)
+                    .map_err(|e| io::Error::new(e.kind(), format!("validation.symbol_whitelist {}: {}", path.display(), e)))?;
+                let symbols: Vec<String> = text
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(str::to_string)
+                    .collect();
+                if let Some(symbol) = symbols.iter().find(|symbol| symbol.len() > 8) {
+                    return Err(invalid(format!("validation.symbol_whitelist: \"{}\" tem mais de 8 bytes", symbol)));
+                }
+                SymbolValidator::whitelist(symbols)
+            }
+            None => SymbolValidator::permissive(),
+        };
+
+        Ok(CompositeValidator::builder()
+            .bounds(self.bounds_for(self.validation.asset_class))
+            .symbol_validator(symbols)
+            .temporal_tolerance(Duration::from_millis(self.validation.clock_skew_tolerance_ms))
+            .build())
+    }
+
+    pub fn ingestor(&self) -> io::Result<MarketDataIngestor> {
+        Ok(MarketDataIngestor::try_new(self.ingestion.arena_capacity, self.ingestion.channel_size)?
+            .with_retry_policy(self.retry_policy())
+            .with_byte_order(ByteOrder { header_endian: self.framing.header_endian, payload_endian: self.framing.payload_endian }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingestion::zero_copy::Trade;
+    use crate::validation::integrity::ValidationError;
+
+    const SAMPLE: &str = r#"
+        [ingestion]
+        arena_capacity = 65536
+        channel_size = 2
+
+        [backpressure]
+        max_retries = 2
+        backoff_us = 0
+        max_total_us = 50
+
+        [validation]
+        asset_class = "crypto"
+        symbol_whitelist = "symbols.txt"
+        clock_skew_tolerance_ms = 5
+
+        [bounds.crypto]
+        max_price = 100000.0
+    "#;
+
+    #[test]
+    fn test_load_sample_config() {
+        let dir = std::env::temp_dir().join(format!("tensorwerk-config-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("pipeline.toml"), SAMPLE).unwrap();
+        std::fs::write(dir.join("symbols.txt"), "# majors\nBTCUSD\nETHUSD\n\n").unwrap();
+
+        let config = PipelineConfig::load(dir.join("pipeline.toml")).unwrap();
+        assert_eq!(config.retry_policy().max_retries, 2);
+
+        let ingestor = MarketDataIngestor::from_config(dir.join("pipeline.toml")).unwrap();
+        let _rx = ingestor.subscribe();
+        let stats = ingestor.stats();
+        assert_eq!(stats.arena_capacity_bytes, 65536);
+
+        // Canal de 2 posições: a terceira mensagem é descartada
+        let ts = 1_700_000_000_000_000_000;
+        for i in 0..3 {
+            let mut frame = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts + i, 1, i).to_frame();
+            ingestor.process_raw_data(&mut frame).unwrap();
+        }
+        assert_eq!(ingestor.stats().messages_dropped, 1);
+
+        let mut validator = config.validator().unwrap();
+        let mut validate = |symbol: &[u8; 8], price: i64, trade_id: u64| {
+            let frame = Trade::new(*symbol, price * 100_000_000, 100_000_000, ts + trade_id, 1, trade_id).to_frame();
+            let header = MessageHeader::from_bytes(&frame).unwrap();
+            validator.validate_message(&header, &frame[MessageHeader::SIZE..])
+        };
+        assert!(validate(b"BTCUSD\0\0", 50_000, 1).is_ok());
+        assert!(matches!(validate(b"BTCUSD\0\0", 150_000, 2), Err(ValidationError::OutOfBounds { .. })));
+        assert!(matches!(validate(b"XRPUSD\0\0", 1, 3), Err(ValidationError::InvalidSymbol(_))));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_values_are_reported() {
+        let error = |text: &str| PipelineConfig::from_toml(text).unwrap_err().to_string();
+
+        assert!(error("[ingestion]\narena_capacity = 0\nchannel_size = 4").contains("ingestion.arena_capacity"));
+        assert!(error("[bounds.stocks]\nmin_price = 10.0\nmax_price = 1.0").contains("bounds.stocks.max_price"));
+        assert!(error("[framing]\nmagic = 1").contains("framing.magic"));
+        assert!(error("[validation]\nasset_class = \"bonds\"").contains("bonds"));
+        assert!(error("[framing]\nmagic = 0x4D524B54\nheader_endian = \"middle\"").contains("middle"));
+        let mixed = PipelineConfig::from_toml("[framing]\nmagic = 0x4D524B54\nheader_endian = \"big\"").unwrap();
+        assert_eq!((mixed.framing.header_endian, mixed.framing.payload_endian), (Endian::Big, Endian::Little));
+        assert!(error("[ingestion]\narena_size = 4").contains("arena_size"));
+
+        assert!(PipelineConfig::load("/nonexistent/pipeline.toml").is_err());
+        assert!(PipelineConfig::from_toml("").is_ok());
+    }
+}
@@ -0,0 +1,117 @@
+//! Fan-out de mensagens para múltiplos assinantes (cada um recebe todas)
+
+use bytes::Bytes;
+use crossbeam_channel::{bounded, Receiver, SendTimeoutError, Sender, TrySendError};
+use parking_lot::RwLock;
+use std::time::Duration;
+
+/// O que fazer quando o canal de um assinante está cheio.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SlowSubscriberPolicy {
+    /// Descarta a mensagem para esse assinante; a ingestão nunca espera.
+    #[default]
+    Drop,
+    /// Espera até `timeout` por espaço e só então descarta. Limita quanto um
+    /// assinante lento pode atrasar a ingestão.
+    Block { timeout: Duration },
+}
+
+struct Subscriber {
+    tx: Sender<Bytes>,
+    policy: SlowSubscriberPolicy,
+}
+
+/// Entrega cada mensagem publicada a todos os assinantes, cada um com seu
+/// próprio canal limitado. O `Bytes` publicado é clonado por assinante (só o
+/// contador de referência; o buffer e o `Arc` da arena são compartilhados).
+/// Assinantes cujo receptor foi descartado são removidos na publicação seguinte.
+pub struct Broadcaster {
+    subscribers: RwLock<Vec<Subscriber>>,
+    channel_size: usize,
+}
+
+impl Broadcaster {
+    pub fn new(channel_size: usize) -> Self {
+        Self { subscribers: RwLock::new(Vec::new()), channel_size }
+    }
+
+    pub fn subscribe(&self) -> Receiver<Bytes> {
+        self.subscribe_with_policy(SlowSubscriberPolicy::default())
+    }
+
+    pub fn subscribe_with_policy(&self, policy: SlowSubscriberPolicy) -> Receiver<Bytes> {
+        let (tx, rx) = bounded(self.channel_size);
+        self.subscribers.write().push(Subscriber { tx, policy });
+        rx
+    }
+
+    pub fn subscriber_count(&self) -> usize { self.subscribers.read().len() }
+
+    pub fn is_empty(&self) -> bool { self.subscribers.read().is_empty() }
+
+    /// Publica `message` para todos os assinantes. Retorna quantas entregas
+    /// foram descartadas por canais cheios.
+    pub fn publish(&self, message: &Bytes) -> usize {
+        let subscribers = self.subscribers.read();
+        let mut dropped = 0;
+        let mut disconnected = Vec::new();
+
+        for subscriber in subscribers.iter() {
+            let delivered = match subscriber.policy {
+                SlowSubscriberPolicy::Drop => match subscriber.tx.try_send(message.clone()) {
+                    Ok(()) => true,
+                    Err(TrySendError::Full(_)) => false,
+                    Err(TrySendError::Disconnected(_)) => {
+                        disconnected.push(subscriber.tx.clone());
+                        continue;
+                    }
+                },
+                SlowSubscriberPolicy::Block { timeout } => match subscriber.tx.send_timeout(message.clone(), timeout) {
+                    Ok(()) => true,
+                    Err(SendTimeoutError::Timeout(_)) => false,
+                    Err(SendTimeoutError::Disconnected(_)) => {
+                        disconnected.push(subscriber.tx.clone());
+                        continue;
+                    }
+                },
+            };
+            if !delivered {
+                dropped += 1;
+            }
+        }
+        drop(subscribers);
+
+        if !disconnected.is_empty() {
+            self.subscribers
+                .write()
+                .retain(|subscriber| !disconnected.iter().any(|d| d.same_channel(&subscriber.tx)));
+        }
+
+        dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_subscriber_gets_every_message() {
+        let broadcaster = Broadcaster::new(1);
+        let fast = broadcaster.subscribe();
+        let slow = broadcaster.subscribe_with_policy(SlowSubscriberPolicy::Block { timeout: Duration::from_millis(1) });
+        drop(broadcaster.subscribe());
+
+        let message = Bytes::from_static(b"trade");
+        assert_eq!(broadcaster.publish(&message), 0);
+        assert_eq!(broadcaster.subscriber_count(), 2);
+        assert_eq!(fast.try_recv().unwrap(), message);
+
+        // `slow` não consumiu: é descartado após o timeout, `fast` recebe normalmente
+        let next = Bytes::from_static(b"quote");
+        assert_eq!(broadcaster.publish(&next), 1);
+        assert_eq!(fast.try_recv().unwrap(), next);
+        assert_eq!(slow.try_recv().unwrap(), message);
+        assert!(slow.try_recv().is_err());
+    }
+}
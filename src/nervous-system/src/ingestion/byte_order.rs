@@ -0,0 +1,224 @@
+//! Ordem de bytes por seção do frame (protocolos de ordem mista)
+
+use bytes::BytesMut;
+use std::mem::size_of;
+use serde::{Deserialize, Serialize};
+
+use crate::ingestion::zero_copy::{
+    read_struct, struct_bytes, BookLevel, BookSnapshot, BookSnapshotHeader, EndOfSession, MessageHeader, Quote, Trade,
+    TradeBatch, WireStruct,
+};
+use crate::validation::integrity::{Checksum, ChecksumValidator, ValidationError};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Endian {
+    #[default]
+    Little,
+    Big,
+}
+
+impl Endian {
+    #[inline]
+    pub fn is_native(self) -> bool { (self == Endian::Little) == cfg!(target_endian = "little") }
+}
+
+/// Ordem de bytes de cada seção do frame recebido. O layout canônico do
+/// protocolo é little-endian nas duas (o padrão); as combinações são:
+///
+/// - `Little`/`Little`: canônico, nenhuma conversão.
+/// - `Big`/`Little`: header big-endian (comum em gateways de rede) e payload
+///   little-endian.
+/// - `Little`/`Big` e `Big`/`Big`: payload big-endian. Só os tipos
+///   conhecidos (trade, quote, book snapshot, fim de sessão) têm o payload
+///   convertido; os demais passam como chegaram.
+///
+/// Frames fora do canônico são convertidos pelo ingestor ao serem ingeridos,
+/// então validação, replay e consumidores só veem little-endian. Nesse caso
+/// o CRC do payload (e, na v2, o do header) é conferido sobre os bytes
+/// originais já na ingestão e recalculado sobre os convertidos.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ByteOrder {
+    pub header_endian: Endian,
+    pub payload_endian: Endian,
+}
+
+impl ByteOrder {
+    pub fn is_canonical(&self) -> bool {
+        self.header_endian == Endian::Little && self.payload_endian == Endian::Little
+    }
+
+    /// Reescreve `frame` no layout canônico. `header` é o header já lido na
+    /// ordem de `header_endian`; `frame` precisa conter o frame inteiro.
+    pub(crate) fn canonicalize(
+        &self,
+        header: &MessageHeader,
+        frame: &[u8],
+        checksum: &ChecksumValidator,
+        payload_checksum: &dyn Checksum,
+    ) -> Result<BytesMut, ValidationError> {
+        let wire_payload = header.payload(frame).ok_or(ValidationError::CorruptedFormat)?;
+
+        if header.has_header_checksum() {
+            let stored = frame
+                .get(MessageHeader::SIZE..MessageHeader::SIZE + MessageHeader::HEADER_CHECKSUM_SIZE)
+                .ok_or(ValidationError::CorruptedFormat)?;
+            let stored = u32::from_le_bytes(stored.try_into().map_err(|_| ValidationError::CorruptedFormat)?);
+            let stored = if self.header_endian == Endian::Big { stored.swap_bytes() } else { stored };
+            let calculated = checksum.calculate(&frame[..MessageHeader::SIZE]);
+            if stored != calculated {
+                return Err(ValidationError::HeaderChecksumMismatch { expected: stored, calculated });
+            }
+        }
+        let calculated = payload_checksum.value(wire_payload);
+        if header.checksum != calculated {
+            return Err(ValidationError::ChecksumMismatch { expected: header.checksum, calculated });
+        }
+
+        let mut payload = Vec::with_capacity(wire_payload.len());
+        match self.payload_endian {
+            Endian::Little => payload.extend_from_slice(wire_payload),
+            Endian::Big => payload_to_little(header.msg_type, wire_payload, &mut payload)?,
+        }
+
+        let mut header = *header;
+        header.checksum = payload_checksum.value(&payload);
+        let mut out = BytesMut::with_capacity(header.frame_size());
+        out.extend_from_slice(&header.to_bytes());
+        if let Some(extension) = header.header_extension(checksum) {
+            out.extend_from_slice(&extension);
+        }
+        out.extend_from_slice(&payload);
+        Ok(out)
+    }
+}
+
+fn payload_to_little(msg_type: u8, payload: &[u8], out: &mut Vec<u8>) -> Result<(), ValidationError> {
+    match msg_type {
+        Trade::MSG_TYPE => swap_struct::<Trade>(payload, out)?,
+        Quote::MSG_TYPE => swap_struct::<Quote>(payload, out)?,
+        EndOfSession::MSG_TYPE => {
+            let session_id = payload.get(..EndOfSession::SIZE).ok_or(ValidationError::CorruptedFormat)?;
+            out.extend(session_id.iter().rev());
+        }
+        TradeBatch::MSG_TYPE => {
+            let count = payload.get(..TradeBatch::COUNT_SIZE).ok_or(ValidationError::CorruptedFormat)?;
+            out.extend(count.iter().rev());
+            for trade in payload[TradeBatch::COUNT_SIZE..].chunks_exact(Trade::SIZE) {
+                swap_struct::<Trade>(trade, out)?;
+            }
+        }
+        BookSnapshot::MSG_TYPE => {
+            let book = read_struct::<BookSnapshotHeader>(payload)?.swap_bytes();
+            out.extend_from_slice(struct_bytes(&book));
+            let levels = book.bid_count as usize + book.ask_count as usize;
+            for level in payload[size_of::<BookSnapshotHeader>()..].chunks_exact(size_of::<BookLevel>()).take(levels) {
+                swap_struct::<BookLevel>(level, out)?;
+            }
+        }
+        _ => {}
+    }
+    // Bytes além das structs conhecidas (ou de tipos desconhecidos) seguem intactos
+    out.extend_from_slice(&payload[out.len()..]);
+    Ok(())
+}
+
+fn swap_struct<T: WireStruct>(bytes: &[u8], out: &mut Vec<u8>) -> Result<(), ValidationError> {
+    let value = read_struct::<T>(bytes)?.swap_bytes();
+    out.extend_from_slice(struct_bytes(&value));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingestion::zero_copy::{IngestionError, MarketDataIngestor};
+    use crate::validation::integrity::CompositeValidator;
+
+    #[test]
+    fn test_big_endian_header_little_endian_payload() {
+        let ts = 1_700_000_000_000_000_000;
+        let trade = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts, Trade::SIDE_SELL, 7);
+        let canonical = trade.to_frame();
+        let header = MessageHeader::from_bytes(&canonical).unwrap();
+        let mut mixed = BytesMut::from(struct_bytes(&header.swap_bytes()));
+        mixed.extend_from_slice(&canonical[MessageHeader::SIZE..]);
+
+        let plain = MarketDataIngestor::new(64 * 1024, 16);
+        let err = plain.process_raw_data(&mut mixed.clone()).unwrap_err();
+        assert!(matches!(err, IngestionError::Validation(ValidationError::UnknownProtocol { .. })));
+
+        let order = ByteOrder { header_endian: Endian::Big, payload_endian: Endian::Little };
+        let ingestor = MarketDataIngestor::new(64 * 1024, 16).with_byte_order(order);
+        let _rx = ingestor.subscribe();
+        ingestor.process_raw_data(&mut mixed).unwrap();
+        assert!(mixed.is_empty());
+
+        let mut validator = CompositeValidator::builder().build();
+        let message = ingestor.drain_validated(&mut validator).next().unwrap().unwrap();
+        assert_eq!(message.buffer().as_slice(), &canonical[..]);
+        let parsed = message.trade().unwrap();
+        assert_eq!((parsed.price(), parsed.timestamp(), parsed.trade_id()), (trade.price(), ts, 7));
+
+        // Payload big-endian também: mesmo resultado canônico
+        let mut both = BytesMut::from(struct_bytes(&header.swap_bytes()));
+        both.extend_from_slice(struct_bytes(&trade.swap_bytes()));
+        let mut swapped_header = header.swap_bytes();
+        swapped_header.checksum = ChecksumValidator::crc32().calculate(&both[MessageHeader::SIZE..]).swap_bytes();
+        both[..MessageHeader::SIZE].copy_from_slice(struct_bytes(&swapped_header));
+        let ingestor = ingestor.with_byte_order(ByteOrder { header_endian: Endian::Big, payload_endian: Endian::Big });
+        ingestor.process_raw_data(&mut both).unwrap();
+        let message = ingestor.drain_validated(&mut CompositeValidator::builder().build()).next().unwrap().unwrap();
+        assert_eq!(message.buffer().as_slice(), &canonical[..]);
+    }
+
+    #[test]
+    fn test_swapped_frame_kept_when_arena_is_exhausted() {
+        use crate::ingestion::zero_copy::{ArenaError, RetryPolicy};
+        use std::time::Duration;
+
+        let canonical = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, 1_700_000_000_000_000_000, 1, 1).to_frame();
+        let header = MessageHeader::from_bytes(&canonical).unwrap();
+        let mut mixed = BytesMut::from(struct_bytes(&header.swap_bytes()));
+        mixed.extend_from_slice(&canonical[MessageHeader::SIZE..]);
+
+        // Arena com espaço para um frame só; a nova tentativa rebobina se puder
+        let policy = RetryPolicy { max_retries: 1, backoff: Duration::ZERO, max_total: Duration::from_secs(1) };
+        let ingestor = MarketDataIngestor::new(128, 4)
+            .with_byte_order(ByteOrder { header_endian: Endian::Big, payload_endian: Endian::Little })
+            .with_retry_policy(policy);
+        let rx = ingestor.subscribe();
+        ingestor.process_raw_data(&mut mixed.clone()).unwrap();
+
+        let err = ingestor.process_raw_data(&mut mixed).unwrap_err();
+        assert!(matches!(err, IngestionError::Arena(ArenaError::Exhausted { .. })));
+        assert_eq!(mixed.len(), canonical.len());
+
+        drop(rx.try_recv().unwrap());
+        ingestor.process_raw_data(&mut mixed).unwrap();
+        assert!(mixed.is_empty());
+        assert_eq!(rx.try_recv().unwrap().as_slice(), &canonical[..]);
+    }
+
+    #[test]
+    fn test_big_endian_trade_batch_is_canonicalized() {
+        let ts = 1_700_000_000_000_000_000;
+        let trades: Vec<Trade> = (0..3).map(|i| Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts + i, 1, i + 1)).collect();
+        let mut wire = (trades.len() as u32).to_be_bytes().to_vec();
+        for trade in &trades {
+            wire.extend_from_slice(struct_bytes(&trade.swap_bytes()));
+        }
+        let mut frame = MessageHeader::builder().msg_type(TradeBatch::MSG_TYPE).timestamp(ts).frame(&wire);
+
+        let order = ByteOrder { header_endian: Endian::Little, payload_endian: Endian::Big };
+        let ingestor = MarketDataIngestor::new(64 * 1024, 16).with_byte_order(order);
+        let _rx = ingestor.subscribe();
+        ingestor.process_raw_data(&mut frame).unwrap();
+
+        let mut validator = CompositeValidator::builder().with_sequence_validator().build();
+        let message = ingestor.drain_validated(&mut validator).next().unwrap().unwrap();
+        assert_eq!(message.buffer().as_slice(), &TradeBatch::frame(&trades)[..]);
+        let batch = TradeBatch::parse(message.payload()).unwrap();
+        assert_eq!(batch.trades().map(|trade| trade.trade_id()).collect::<Vec<_>>(), [1, 2, 3]);
+    }
+}
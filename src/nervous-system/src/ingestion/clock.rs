@@ -0,0 +1,58 @@
+//! Fonte de tempo injetável (relógio do sistema em produção, manual em testes)
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Tempo em nanossegundos desde a época Unix, na mesma escala dos timestamps do protocolo.
+pub trait Clock: Send + Sync {
+    fn now_nanos(&self) -> u64;
+}
+
+/// Converte para nanossegundos um timestamp de época Unix de produtor que
+/// pode vir em segundos, milissegundos ou microssegundos, deduzindo a unidade
+/// pela magnitude (válido para datas entre ~1973 e ~2286).
+pub fn normalize_to_nanos(timestamp: u64) -> u64 {
+    match timestamp {
+        0..100_000_000_000 => timestamp.saturating_mul(1_000_000_000),
+        100_000_000_000..100_000_000_000_000 => timestamp.saturating_mul(1_000_000),
+        100_000_000_000_000..100_000_000_000_000_000 => timestamp.saturating_mul(1_000),
+        _ => timestamp,
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    #[inline]
+    fn now_nanos(&self) -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or(0)
+    }
+}
+
+/// Relógio controlado manualmente; só avança via `set`/`advance`.
+#[derive(Debug, Default)]
+pub struct MockClock {
+    nanos: AtomicU64,
+}
+
+impl MockClock {
+    pub fn new(start_nanos: u64) -> Self {
+        Self { nanos: AtomicU64::new(start_nanos) }
+    }
+
+    pub fn set(&self, nanos: u64) {
+        self.nanos.store(nanos, Ordering::Release);
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.nanos.fetch_add(by.as_nanos() as u64, Ordering::AcqRel);
+    }
+}
+
+impl Clock for MockClock {
+    #[inline]
+    fn now_nanos(&self) -> u64 {
+        self.nanos.load(Ordering::Acquire)
+    }
+}
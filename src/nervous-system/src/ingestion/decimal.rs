@@ -0,0 +1,103 @@
+//! Conversão entre preços decimais em texto e o ponto fixo do protocolo (escala 1e8)
+
+use thiserror::Error;
+
+/// Escala dos preços e quantidades no fio: `50123.45` vira `5_012_345_000_000`.
+pub const PRICE_SCALE: i64 = 100_000_000;
+/// Casas decimais representáveis com `PRICE_SCALE`.
+pub const PRICE_DECIMALS: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("Preço vazio")]
+    Empty,
+    #[error("Preço mal formado: {0:?}")]
+    InvalidFormat(String),
+    /// Mais casas do que `PRICE_DECIMALS`: arredondar esconderia o erro de escala.
+    #[error("Preço com {digits} casas decimais (máximo {PRICE_DECIMALS})")]
+    TooManyDecimals { digits: usize },
+    #[error("Preço não cabe em i64 na escala 1e8: {0:?}")]
+    Overflow(String),
+}
+
+/// Converte `"50123.45"` (sinal opcional, até 8 casas decimais, sem expoente
+/// nem separador de milhar) para o ponto fixo do protocolo, sem passar por
+/// `f64`. Strings com mais de 8 casas são rejeitadas em vez de arredondadas.
+pub fn price_from_decimal_str(s: &str) -> Result<i64, ParseError> {
+    if s.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    let (negative, unsigned) = match s.as_bytes()[0] {
+        b'-' => (true, &s[1..]),
+        b'+' => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (unsigned, None),
+    };
+    let is_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    if !is_digits(integer) || fraction.is_some_and(|fraction| !is_digits(fraction)) {
+        return Err(ParseError::InvalidFormat(s.to_string()));
+    }
+    let fraction = fraction.unwrap_or("");
+    if fraction.len() > PRICE_DECIMALS {
+        return Err(ParseError::TooManyDecimals { digits: fraction.len() });
+    }
+
+    let overflow = || ParseError::Overflow(s.to_string());
+    let padding = std::iter::repeat_n(b'0', PRICE_DECIMALS - fraction.len());
+    let mut digits = integer.bytes().chain(fraction.bytes()).chain(padding);
+    let magnitude = digits.try_fold(0i128, |acc, digit| {
+        acc.checked_mul(10).and_then(|acc| acc.checked_add(i128::from(digit - b'0'))).ok_or_else(overflow)
+    })?;
+    let value = if negative { -magnitude } else { magnitude };
+    i64::try_from(value).map_err(|_| overflow())
+}
+
+/// Inverso de `price_from_decimal_str`, sem zeros à direita:
+/// `5_012_345_000_000` vira `"50123.45"` e `5_000_000_000_000`, `"50000"`.
+pub fn price_to_decimal_str(price: i64) -> String {
+    let sign = if price < 0 { "-" } else { "" };
+    let magnitude = price.unsigned_abs();
+    let scale = PRICE_SCALE as u64;
+    let (integer, fraction) = (magnitude / scale, magnitude % scale);
+    if fraction == 0 {
+        return format!("{}{}", sign, integer);
+    }
+    let fraction = format!("{:0width$}", fraction, width = PRICE_DECIMALS);
+    format!("{}{}.{}", sign, integer, fraction.trim_end_matches('0'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_price_round_trip() {
+        assert_eq!(price_from_decimal_str("50123.45"), Ok(5_012_345_000_000));
+        assert_eq!(price_from_decimal_str("-0.5"), Ok(-50_000_000));
+        assert_eq!(price_from_decimal_str("+7"), Ok(700_000_000));
+        assert_eq!(price_from_decimal_str("0.00000001"), Ok(1));
+        assert_eq!(price_from_decimal_str("92233720368.54775807"), Ok(i64::MAX));
+        assert_eq!(price_from_decimal_str("-92233720368.54775808"), Ok(i64::MIN));
+
+        for price in [5_012_345_000_000, -50_000_000, 1, 5_000_000_000_000, 0, i64::MAX, i64::MIN] {
+            assert_eq!(price_from_decimal_str(&price_to_decimal_str(price)), Ok(price));
+        }
+        assert_eq!(price_to_decimal_str(5_012_345_000_000), "50123.45");
+        assert_eq!(price_to_decimal_str(5_000_000_000_000), "50000");
+        assert_eq!(price_to_decimal_str(-1), "-0.00000001");
+    }
+
+    #[test]
+    fn test_decimal_price_rejections() {
+        assert_eq!(price_from_decimal_str("0.123456789"), Err(ParseError::TooManyDecimals { digits: 9 }));
+        assert!(matches!(price_from_decimal_str("92233720368.54775808"), Err(ParseError::Overflow(_))));
+        assert!(matches!(price_from_decimal_str("1".repeat(60).as_str()), Err(ParseError::Overflow(_))));
+        assert_eq!(price_from_decimal_str(""), Err(ParseError::Empty));
+        for malformed in ["-", ".5", "5.", "1e8", "1,000.00", " 5", "5..0", "0x10"] {
+            assert!(matches!(price_from_decimal_str(malformed), Err(ParseError::InvalidFormat(_))), "{}", malformed);
+        }
+    }
+}
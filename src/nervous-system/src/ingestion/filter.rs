@@ -0,0 +1,111 @@
+//! Pré-filtro de mensagens por símbolo e tipo, antes da arena e da validação
+
+use std::collections::HashSet;
+
+use crate::ingestion::zero_copy::{BookSnapshot, Quote, Trade, TradeBatch};
+
+/// Decide, logo após o parse do header, quais mensagens seguem para a arena.
+/// As demais são descartadas sem alocação nem validação (ver
+/// `MarketDataIngestor::with_filter`). Sem critérios, aceita tudo.
+///
+/// O filtro de símbolos compara os 8 bytes do payload como chegaram (sem a
+/// normalização do `SymbolValidator`) e só se aplica a tipos com símbolo
+/// (trade, quote, book snapshot, lote de trades); os demais passam ou não
+/// apenas pelo filtro de tipos. Um lote passa inteiro se algum de seus trades
+/// for de um símbolo aceito.
+#[derive(Debug, Clone, Default)]
+pub struct DiscardFilter {
+    symbols: Option<HashSet<[u8; 8]>>,
+    msg_types: Option<Box<[bool; 256]>>,
+}
+
+impl DiscardFilter {
+    pub fn new() -> Self { Self::default() }
+
+    /// Só aceita estes símbolos (completados com NUL até 8 bytes).
+    pub fn symbols<S: AsRef<str>>(mut self, symbols: impl IntoIterator<Item = S>) -> Self {
+        let symbols = symbols.into_iter().map(|symbol| {
+            let mut bytes = [0u8; 8];
+            for (slot, byte) in bytes.iter_mut().zip(symbol.as_ref().bytes()) {
+                *slot = byte;
+            }
+            bytes
+        });
+        self.symbols.get_or_insert_with(HashSet::new).extend(symbols);
+        self
+    }
+
+    /// Só aceita estes tipos de mensagem.
+    pub fn msg_types(mut self, msg_types: impl IntoIterator<Item = u8>) -> Self {
+        let accepted = self.msg_types.get_or_insert_with(|| Box::new([false; 256]));
+        for msg_type in msg_types {
+            accepted[msg_type as usize] = true;
+        }
+        self
+    }
+
+    /// Se a mensagem (`msg_type` e início do payload) passa pelo filtro.
+    #[inline]
+    pub fn accepts(&self, msg_type: u8, payload: &[u8]) -> bool {
+        if self.msg_types.as_ref().is_some_and(|accepted| !accepted[msg_type as usize]) {
+            return false;
+        }
+        let Some(symbols) = &self.symbols else { return true };
+        if msg_type == TradeBatch::MSG_TYPE {
+            let trades = payload.get(TradeBatch::COUNT_SIZE..).unwrap_or_default();
+            return trades.chunks_exact(Trade::SIZE).any(|trade| trade.first_chunk::<8>().is_some_and(|symbol| symbols.contains(symbol)));
+        }
+        if ![Trade::MSG_TYPE, Quote::MSG_TYPE, BookSnapshot::MSG_TYPE].contains(&msg_type) {
+            return true;
+        }
+        payload.first_chunk::<8>().is_some_and(|symbol| symbols.contains(symbol))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingestion::zero_copy::{MarketDataIngestor, MessageHeader, AVX512_ALIGNMENT};
+
+    #[test]
+    fn test_symbol_filter_drops_before_allocation() {
+        let ingestor = MarketDataIngestor::new(64 * 1024, 16).with_filter(DiscardFilter::new().symbols(["BTCUSD"]));
+        let rx = ingestor.subscribe();
+        let ts = 1_700_000_000_000_000_000;
+        let frames = [
+            Trade::new(*b"BTCUSD\0\0", 1, 1, ts, 1, 1).to_frame(),
+            Trade::new(*b"ETHUSD\0\0", 1, 1, ts, 1, 2).to_frame(),
+            Quote::new(*b"ETHUSD\0\0", 100, 1, 101, 1, ts).to_frame(),
+            Quote::new(*b"BTCUSD\0\0", 100, 1, 101, 1, ts).to_frame(),
+        ];
+        for frame in &frames {
+            ingestor.process_raw_data(&mut frame.clone()).unwrap();
+        }
+        // Sem símbolo: só o filtro de tipos decide
+        ingestor.process_raw_data(&mut MessageHeader::builder().msg_type(3).frame(&[0u8; 8])).unwrap();
+
+        let stats = ingestor.stats();
+        assert_eq!((rx.len(), stats.messages_received, stats.messages_filtered), (3, 3, 2));
+        let aligned = |frame: &[u8]| frame.len().next_multiple_of(AVX512_ALIGNMENT);
+        assert_eq!(stats.arena_used_bytes, aligned(&frames[0]) + aligned(&frames[3]) + AVX512_ALIGNMENT);
+
+        let trades_only = DiscardFilter::new().symbols(["BTCUSD"]).msg_types([Trade::MSG_TYPE]);
+        assert!(trades_only.accepts(Trade::MSG_TYPE, &frames[0][MessageHeader::SIZE..]));
+        assert!(!trades_only.accepts(Quote::MSG_TYPE, &frames[3][MessageHeader::SIZE..]));
+        assert!(!trades_only.accepts(3, &[]));
+        assert!(!trades_only.accepts(Trade::MSG_TYPE, b"BTC"));
+    }
+
+    #[test]
+    fn test_symbol_filter_applies_to_trade_batches() {
+        let filter = DiscardFilter::new().symbols(["BTCUSD"]);
+        let ts = 1_700_000_000_000_000_000;
+        let eth = [Trade::new(*b"ETHUSD\0\0", 1, 1, ts, 1, 1), Trade::new(*b"ETHUSD\0\0", 1, 1, ts, 1, 2)];
+        let mixed = [eth[0], Trade::new(*b"BTCUSD\0\0", 1, 1, ts, 1, 3)];
+        let payload = |trades: &[Trade]| TradeBatch::frame(trades)[MessageHeader::SIZE..].to_vec();
+
+        assert!(!filter.accepts(TradeBatch::MSG_TYPE, &payload(&eth)));
+        assert!(filter.accepts(TradeBatch::MSG_TYPE, &payload(&mixed)));
+        assert!(!filter.accepts(TradeBatch::MSG_TYPE, &[]));
+    }
+}
@@ -0,0 +1,111 @@
+//! Arena de capacidade fixa embutida (sem heap), para uso em `static` ou na pilha
+
+use std::cell::UnsafeCell;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::ingestion::zero_copy::{Arena, ArenaError, BumpAllocator, AVX512_ALIGNMENT};
+
+/// Mesma alocação bump de `ZeroCopyArena`, sobre um `[u8; N]` alinhado a 64
+/// bytes em vez de memória do heap: criar a arena nunca falha e `new` é
+/// `const`, então ela pode ser um `static`. Se `N` não for múltiplo de 64, o
+/// resto final nunca é usado.
+#[repr(C, align(64))]
+pub struct FixedArena<const N: usize> {
+    data: UnsafeCell<[u8; N]>,
+    offset: AtomicU64,
+    live_buffers: AtomicU64,
+}
+
+const _: () = assert!(std::mem::align_of::<FixedArena<0>>() == AVX512_ALIGNMENT);
+
+// A região só é escrita através de ponteiros entregues por `allocate`, cada um
+// exclusivo graças ao CAS do offset
+unsafe impl<const N: usize> Sync for FixedArena<N> {}
+
+impl<const N: usize> FixedArena<N> {
+    pub const fn new() -> Self {
+        Self { data: UnsafeCell::new([0; N]), offset: AtomicU64::new(0), live_buffers: AtomicU64::new(0) }
+    }
+
+    pub fn allocate(&self, size: usize) -> Result<NonNull<u8>, ArenaError> { self.bump(size) }
+
+    pub fn capacity(&self) -> usize { self.region_capacity() }
+    pub fn used(&self) -> usize { self.offset.load(Ordering::Relaxed) as usize }
+}
+
+impl<const N: usize> Default for FixedArena<N> {
+    fn default() -> Self { Self::new() }
+}
+
+// SAFETY: `data` é alinhado a 64 bytes pelo `repr` e vive com a arena;
+// `region_capacity` nunca passa de `N`.
+unsafe impl<const N: usize> BumpAllocator for FixedArena<N> {
+    #[inline]
+    fn region_base(&self) -> *mut u8 { self.data.get() as *mut u8 }
+    #[inline]
+    fn region_capacity(&self) -> usize { N - N % AVX512_ALIGNMENT }
+    #[inline]
+    fn region_offset(&self) -> &AtomicU64 { &self.offset }
+    #[inline]
+    fn region_live_buffers(&self) -> &AtomicU64 { &self.live_buffers }
+}
+
+// SAFETY: como em `ZeroCopyArena`: alocações via `bump`, `reset` via `reclaim`.
+unsafe impl<const N: usize> Arena for FixedArena<N> {
+    fn allocate(&self, size: usize) -> Result<NonNull<u8>, ArenaError> { self.bump(size) }
+    fn used(&self) -> usize { FixedArena::used(self) }
+    fn capacity(&self) -> usize { FixedArena::capacity(self) }
+    fn reset(&self) -> bool { self.reclaim() }
+    fn in_use(&self) -> usize { if self.live_buffers.load(Ordering::SeqCst) == 0 { 0 } else { self.used() } }
+
+    fn buffer_acquired(&self) {
+        self.live_buffers.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn buffer_released(&self, _ptr: NonNull<u8>, _tag: u32) {
+        self.live_buffers.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stack_arena_allocates_aligned_buffers() {
+        let arena = FixedArena::<4096>::new();
+        assert_eq!(arena.capacity(), 4096);
+
+        let sizes = [1, 64, 100, 500, 1000];
+        let mut buffers = Vec::new();
+        for size in sizes {
+            let ptr = arena.allocate(size).unwrap();
+            assert!(arena.verify_alignment(ptr, size));
+            unsafe { std::ptr::write_bytes(ptr.as_ptr(), size as u8, size) };
+            buffers.push((ptr, size));
+        }
+        assert_eq!(arena.used(), 64 + 64 + 128 + 512 + 1024);
+
+        // Regiões não se sobrepõem: cada uma mantém o próprio padrão
+        for &(ptr, size) in &buffers {
+            let bytes = unsafe { std::slice::from_raw_parts(ptr.as_ptr(), size) };
+            assert!(bytes.iter().all(|&b| b == size as u8));
+        }
+
+        assert!(matches!(arena.allocate(4096), Err(ArenaError::Exhausted { available: 2304, .. })));
+        assert!(arena.allocate(usize::MAX).is_err());
+
+        assert!(arena.reset());
+        assert_eq!(arena.used(), 0);
+        assert!(arena.allocate(4096).is_ok());
+    }
+
+    #[test]
+    fn test_static_arena() {
+        static ARENA: FixedArena<256> = FixedArena::new();
+        let ptr = ARENA.allocate(8).unwrap();
+        assert!(ARENA.verify_alignment(ptr, 8));
+        assert_eq!(ARENA.used(), 64);
+    }
+}
@@ -0,0 +1,243 @@
+//! Controle de fluxo do produtor com histerese (marcas alta/baixa de ocupação)
+
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::ingestion::zero_copy::{Arena, MarketDataIngestor, ZeroCopyArena};
+
+/// Marcas de ocupação (fração de `[0, 1]`, ver `MarketDataIngestor::pressure`).
+/// O produtor é contido ao atingir `high` e só é liberado abaixo de `low`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Watermarks {
+    pub high: f64,
+    pub low: f64,
+}
+
+impl Default for Watermarks {
+    fn default() -> Self {
+        Self { high: 0.9, low: 0.5 }
+    }
+}
+
+/// Obtido com `MarketDataIngestor::producer_token`. O produtor chama `acquire`
+/// antes de cada `process_raw_data`; com o canal ou a arena acima da marca
+/// alta, `acquire` espera (cedendo a thread) até a ocupação cair abaixo da
+/// marca baixa. O estado de contenção é do token, então cada produtor tem a
+/// própria histerese.
+pub struct ProducerToken<'a, A: Arena = ZeroCopyArena> {
+    ingestor: &'a MarketDataIngestor<A>,
+    watermarks: Watermarks,
+    throttled: bool,
+}
+
+impl<'a, A: Arena> ProducerToken<'a, A> {
+    pub fn new(ingestor: &'a MarketDataIngestor<A>) -> Self {
+        Self { ingestor, watermarks: Watermarks::default(), throttled: false }
+    }
+
+    pub fn with_watermarks(mut self, watermarks: Watermarks) -> Self { self.watermarks = watermarks; self }
+
+    pub fn is_throttled(&self) -> bool { self.throttled }
+
+    /// Não bloqueia: retorna `false` se o produtor deve esperar.
+    pub fn try_acquire(&mut self) -> bool {
+        let pressure = self.ingestor.pressure();
+        if self.throttled {
+            self.throttled = pressure >= self.watermarks.low;
+        } else {
+            self.throttled = pressure >= self.watermarks.high;
+        }
+        !self.throttled
+    }
+
+    /// Bloqueia até o produtor poder seguir.
+    pub fn acquire(&mut self) {
+        while !self.try_acquire() {
+            std::thread::yield_now();
+        }
+    }
+
+    /// Como `acquire`, desistindo após `timeout`. Retorna `true` se liberado.
+    pub fn acquire_timeout(&mut self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        while !self.try_acquire() {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            std::thread::yield_now();
+        }
+        true
+    }
+}
+
+/// O que o ingestor faz com mensagens acima do `RateCeiling`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RatePolicy {
+    /// Descarta a mensagem (contada em `rate_limited_drops`).
+    #[default]
+    Drop,
+    /// Segura o produtor dentro de `process_raw_data` até haver ficha.
+    Throttle,
+}
+
+/// Fichas em unidades de 1e-9 para o reabastecimento ser exato em ns.
+const TOKEN: i128 = 1_000_000_000;
+
+/// Teto global de mensagens por segundo do ingestor, um token bucket
+/// alimentado pelo relógio do ingestor. Vale para todas as mensagens de
+/// dados, de qualquer símbolo e fonte: é a válvula de segurança do sistema,
+/// distinta do `RateLimiter` por símbolo da validação. O balde começa cheio
+/// com `burst` fichas.
+pub struct RateCeiling {
+    rate: u64,
+    capacity: i128,
+    policy: RatePolicy,
+    bucket: Mutex<Bucket>,
+}
+
+struct Bucket {
+    /// Negativo em `Throttle`: fichas já prometidas a produtores em espera.
+    tokens: i128,
+    last_nanos: Option<u64>,
+}
+
+pub(crate) enum Admission {
+    Admit,
+    Drop,
+    Wait(Duration),
+}
+
+impl RateCeiling {
+    /// `messages_per_sec` e `burst` iguais a 0 são tratados como 1.
+    pub fn new(messages_per_sec: u64, burst: u64, policy: RatePolicy) -> Self {
+        let capacity = i128::from(burst.max(1)) * TOKEN;
+        Self {
+            rate: messages_per_sec.max(1),
+            capacity,
+            policy,
+            bucket: Mutex::new(Bucket { tokens: capacity, last_nanos: None }),
+        }
+    }
+
+    pub fn policy(&self) -> RatePolicy { self.policy }
+
+    pub(crate) fn admit(&self, now_nanos: u64) -> Admission {
+        let mut bucket = self.bucket.lock();
+        let elapsed = bucket.last_nanos.map_or(0, |last| now_nanos.saturating_sub(last));
+        bucket.last_nanos = Some(bucket.last_nanos.map_or(now_nanos, |last| last.max(now_nanos)));
+        bucket.tokens = (bucket.tokens + i128::from(elapsed) * i128::from(self.rate)).min(self.capacity);
+
+        if bucket.tokens >= TOKEN {
+            bucket.tokens -= TOKEN;
+            return Admission::Admit;
+        }
+        match self.policy {
+            RatePolicy::Drop => Admission::Drop,
+            RatePolicy::Throttle => {
+                bucket.tokens -= TOKEN;
+                let debt = bucket.tokens.unsigned_abs();
+                Admission::Wait(Duration::from_nanos(debt.div_ceil(u128::from(self.rate)) as u64))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingestion::clock::MockClock;
+    use crate::ingestion::zero_copy::{MessageHeader, Trade};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_producer_throttled_until_consumer_drains() {
+        let ingestor = MarketDataIngestor::new(1024 * 1024, 10);
+        let consumer = ingestor.subscribe();
+        let frame = MessageHeader::builder().frame(&[1u8; 32]);
+        let mut token = ingestor.producer_token().with_watermarks(Watermarks { high: 0.8, low: 0.2 });
+
+        for _ in 0..8 {
+            assert!(token.try_acquire());
+            ingestor.process_raw_data(&mut frame.clone()).unwrap();
+        }
+        assert!(!token.try_acquire());
+        assert!(!token.acquire_timeout(Duration::from_millis(5)));
+
+        // Histerese: abaixo da marca alta, mas ainda acima da baixa
+        for _ in 0..5 {
+            consumer.recv().unwrap();
+        }
+        assert!(!token.try_acquire());
+
+        let released = AtomicBool::new(false);
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                token.acquire();
+                released.store(true, Ordering::SeqCst);
+            });
+            std::thread::sleep(Duration::from_millis(20));
+            assert!(!released.load(Ordering::SeqCst));
+            while consumer.try_recv().is_ok() {}
+        });
+        assert!(released.load(Ordering::SeqCst));
+        assert!(!token.is_throttled());
+    }
+
+    #[test]
+    fn test_producer_released_after_arena_drains() {
+        // Canal folgado: só a arena passa da marca alta
+        let ingestor = MarketDataIngestor::new(64 * 1024, 1024);
+        let consumer = ingestor.subscribe();
+        let frame = MessageHeader::builder().frame(&[1u8; 4000]);
+        let mut token = ingestor.producer_token();
+
+        while token.try_acquire() {
+            ingestor.process_raw_data(&mut frame.clone()).unwrap();
+        }
+        assert!(consumer.len() < 20);
+        assert!(!token.acquire_timeout(Duration::from_millis(5)));
+
+        while consumer.try_recv().is_ok() {}
+        assert_eq!(ingestor.pressure(), 0.0);
+        assert!(token.acquire_timeout(Duration::from_millis(5)));
+    }
+
+    #[test]
+    fn test_global_rate_ceiling_policies() {
+        let now = 1_700_000_000_000_000_000;
+        let clock = Arc::new(MockClock::new(now));
+        let frame = |i: u64| Trade::new(*b"BTCUSD\0\0", 1, 1, now + i, 1, i).to_frame();
+
+        let dropping = MarketDataIngestor::new(1024 * 1024, 64)
+            .with_clock(clock.clone())
+            .with_rate_ceiling(RateCeiling::new(1_000, 5, RatePolicy::Drop));
+        let rx = dropping.subscribe();
+        for i in 0..10 {
+            let mut raw = frame(i);
+            dropping.process_raw_data(&mut raw).unwrap();
+            assert!(raw.is_empty());
+        }
+        assert_eq!((rx.len(), dropping.stats().rate_limited_drops), (5, 5));
+
+        // 1000 msg/s: 2 ms rendem duas fichas
+        clock.advance(Duration::from_millis(2));
+        for i in 10..15 {
+            dropping.process_raw_data(&mut frame(i)).unwrap();
+        }
+        assert_eq!((rx.len(), dropping.stats().rate_limited_drops), (7, 8));
+
+        // Relógio parado: cada mensagem além do burst espera 1/rate a mais que a anterior
+        let throttling = MarketDataIngestor::new(1024 * 1024, 64)
+            .with_clock(clock.clone())
+            .with_rate_ceiling(RateCeiling::new(10_000, 2, RatePolicy::Throttle));
+        let rx = throttling.subscribe();
+        let start = Instant::now();
+        for i in 0..5 {
+            throttling.process_raw_data(&mut frame(i)).unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_micros(600));
+        let stats = throttling.stats();
+        assert_eq!((rx.len(), stats.rate_throttled, stats.rate_limited_drops), (5, 3, 0));
+    }
+}
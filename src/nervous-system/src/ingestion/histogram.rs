@@ -0,0 +1,131 @@
+//! Histograma lock-free de valores `u64` (latências em nanossegundos)
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const EXACT: usize = 16;
+const SUB_BITS: u32 = 3;
+const SUB_BUCKETS: usize = 1 << SUB_BITS;
+const BUCKETS: usize = EXACT + (64 - 4) * SUB_BUCKETS;
+
+/// Buckets log-lineares: valores abaixo de 16 são exatos; acima, cada potência
+/// de dois é dividida em 8 faixas, então um percentil erra no máximo 12,5%
+/// para cima (e nunca passa do maior valor registrado). `record` é um
+/// `fetch_add` relaxado, seguro para várias threads.
+pub struct LatencyHistogram {
+    buckets: Box<[AtomicU64; BUCKETS]>,
+    count: AtomicU64,
+    max: AtomicU64,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self {
+            buckets: Box::new(std::array::from_fn(|_| AtomicU64::new(0))),
+            count: AtomicU64::new(0),
+            max: AtomicU64::new(0),
+        }
+    }
+
+    #[inline]
+    fn index(value: u64) -> usize {
+        if value < EXACT as u64 {
+            return value as usize;
+        }
+        let exponent = 63 - value.leading_zeros();
+        let sub = (value >> (exponent - SUB_BITS)) as usize & (SUB_BUCKETS - 1);
+        EXACT + (exponent as usize - 4) * SUB_BUCKETS + sub
+    }
+
+    /// Maior valor que cai no bucket `index`.
+    fn upper_bound(index: usize) -> u64 {
+        if index < EXACT {
+            return index as u64;
+        }
+        let exponent = ((index - EXACT) / SUB_BUCKETS + 4) as u32;
+        let sub = ((index - EXACT) % SUB_BUCKETS) as u64;
+        let width = 1u64 << (exponent - SUB_BITS);
+        ((SUB_BUCKETS as u64 + sub) << (exponent - SUB_BITS)).saturating_add(width - 1)
+    }
+
+    #[inline]
+    pub fn record(&self, value: u64) {
+        self.buckets[Self::index(value)].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.max.fetch_max(value, Ordering::Relaxed);
+    }
+
+    pub fn count(&self) -> u64 { self.count.load(Ordering::Relaxed) }
+    pub fn max(&self) -> u64 { self.max.load(Ordering::Relaxed) }
+
+    /// Valor abaixo do qual está a fração `quantile` (0.0..=1.0) das amostras;
+    /// 0 sem amostras.
+    pub fn percentile(&self, quantile: f64) -> u64 {
+        let count = self.count();
+        if count == 0 {
+            return 0;
+        }
+        let rank = ((quantile.clamp(0.0, 1.0) * count as f64).ceil() as u64).max(1);
+
+        let mut seen = 0;
+        for (index, bucket) in self.buckets.iter().enumerate() {
+            seen += bucket.load(Ordering::Relaxed);
+            if seen >= rank {
+                return Self::upper_bound(index).min(self.max());
+            }
+        }
+        self.max()
+    }
+
+    pub fn reset(&self) {
+        for bucket in self.buckets.iter() {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        self.count.store(0, Ordering::Relaxed);
+        self.max.store(0, Ordering::Relaxed);
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self { Self::new() }
+}
+
+impl std::fmt::Debug for LatencyHistogram {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LatencyHistogram")
+            .field("count", &self.count())
+            .field("p50", &self.percentile(0.5))
+            .field("p99", &self.percentile(0.99))
+            .field("max", &self.max())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_within_bucket_error() {
+        let histogram = LatencyHistogram::new();
+        assert_eq!(histogram.percentile(0.99), 0);
+
+        for value in 1..=10_000u64 {
+            histogram.record(value * 1_000);
+        }
+        assert_eq!(histogram.count(), 10_000);
+        assert_eq!(histogram.max(), 10_000_000);
+
+        for (quantile, exact) in [(0.5, 5_000_000u64), (0.9, 9_000_000), (0.99, 9_900_000)] {
+            let estimate = histogram.percentile(quantile);
+            assert!(estimate >= exact && estimate <= exact + exact / 8, "q={} estimativa={}", quantile, estimate);
+        }
+        assert_eq!(histogram.percentile(1.0), 10_000_000);
+
+        for value in [0, 1, 15, 16, 17, u64::MAX / 2, u64::MAX] {
+            assert!(LatencyHistogram::upper_bound(LatencyHistogram::index(value)) >= value);
+        }
+
+        histogram.reset();
+        assert_eq!((histogram.count(), histogram.percentile(0.5)), (0, 0));
+    }
+}
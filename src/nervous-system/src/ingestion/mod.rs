@@ -1 +1,19 @@
+pub mod broadcast;
+pub mod byte_order;
+pub mod clock;
+pub mod decimal;
+pub mod filter;
+pub mod fixed_arena;
+pub mod flow_control;
+pub mod histogram;
+pub mod mmap_source;
+pub mod nontemporal;
+#[cfg(feature = "otel")]
+pub mod otel;
+pub mod replay;
+pub mod sink;
+pub mod soa;
+pub mod synthetic;
+pub mod tape;
+pub mod throughput;
 pub mod zero_copy;
@@ -0,0 +1,123 @@
+//! Cópia com stores não-temporais para payloads grandes
+
+/// Copia `src` para `dst` (mesmo tamanho) com stores não-temporais (streaming),
+/// que escrevem direto na memória sem trazer as linhas para o cache. Vale para
+/// dados escritos uma vez e lidos em outro núcleo, como snapshots grandes
+/// copiados para a arena: a cópia comum expulsaria do cache dados quentes da
+/// ingestão. Usa AVX (`_mm256_stream_si256`) quando disponível, senão SSE2
+/// (`_mm_stream_si128`); fora de x86_64 equivale a `copy_from_slice`.
+///
+/// Termina com `sfence`, então os bytes estão visíveis a outras threads
+/// quando a função retorna (o canal do ingestor já sincroniza o resto).
+pub fn copy_nontemporal(dst: &mut [u8], src: &[u8]) {
+    assert_eq!(dst.len(), src.len(), "copy_nontemporal: tamanhos diferentes");
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::arch::is_x86_feature_detected!("avx") {
+            // SAFETY: AVX detectado em tempo de execução
+            unsafe { x86::copy_avx(dst, src) };
+        } else {
+            // SAFETY: SSE2 faz parte da base do x86_64
+            unsafe { x86::copy_sse2(dst, src) };
+        }
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    dst.copy_from_slice(src);
+}
+
+/// `copy_nontemporal` para cópias de pelo menos `threshold` bytes, cópia
+/// comum abaixo disso (onde o custo fixo do `sfence` não compensa).
+#[inline]
+pub fn copy_with_threshold(dst: &mut [u8], src: &[u8], threshold: usize) {
+    if src.len() >= threshold {
+        copy_nontemporal(dst, src);
+    } else {
+        dst.copy_from_slice(src);
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::{__m128i, __m256i, _mm256_loadu_si256, _mm256_stream_si256, _mm_loadu_si128, _mm_sfence, _mm_stream_si128};
+
+    /// Divide `dst` em cabeça, corpo alinhado a `LANE` bytes e cauda; só o
+    /// corpo vai por streaming.
+    #[inline(always)]
+    fn split<const LANE: usize>(dst: &[u8]) -> (usize, usize) {
+        let head = dst.as_ptr().align_offset(LANE).min(dst.len());
+        let body = (dst.len() - head) / LANE * LANE;
+        (head, body)
+    }
+
+    #[target_feature(enable = "avx")]
+    pub(super) unsafe fn copy_avx(dst: &mut [u8], src: &[u8]) {
+        const LANE: usize = 32;
+        let (head, body) = split::<LANE>(dst);
+        dst[..head].copy_from_slice(&src[..head]);
+        for offset in (head..head + body).step_by(LANE) {
+            // SAFETY: `offset..offset + LANE` está dentro dos dois slices e
+            // `dst + offset` é alinhado a 32 bytes (ver `split`)
+            unsafe {
+                let value = _mm256_loadu_si256(src.as_ptr().add(offset).cast::<__m256i>());
+                _mm256_stream_si256(dst.as_mut_ptr().add(offset).cast::<__m256i>(), value);
+            }
+        }
+        dst[head + body..].copy_from_slice(&src[head + body..]);
+        _mm_sfence();
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn copy_sse2(dst: &mut [u8], src: &[u8]) {
+        const LANE: usize = 16;
+        let (head, body) = split::<LANE>(dst);
+        dst[..head].copy_from_slice(&src[..head]);
+        for offset in (head..head + body).step_by(LANE) {
+            // SAFETY: como em `copy_avx`, com alinhamento de 16 bytes
+            unsafe {
+                let value = _mm_loadu_si128(src.as_ptr().add(offset).cast::<__m128i>());
+                _mm_stream_si128(dst.as_mut_ptr().add(offset).cast::<__m128i>(), value);
+            }
+        }
+        dst[head + body..].copy_from_slice(&src[head + body..]);
+        _mm_sfence();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingestion::zero_copy::{MarketDataIngestor, MessageHeader};
+
+    #[test]
+    fn test_streamed_copy_matches_normal_copy() {
+        let src: Vec<u8> = (0..4096u32).map(|i| (i * 31 % 251) as u8).collect();
+        // Destinos desalinhados e tamanhos que deixam cabeça e cauda parciais
+        for (offset, len) in [(0, 4096), (1, 4000), (13, 33), (31, 17), (7, 0), (0, 1), (5, 2048 + 3)] {
+            let mut expected = vec![0u8; 4200];
+            let mut streamed = vec![0u8; 4200];
+            expected[offset..offset + len].copy_from_slice(&src[..len]);
+            copy_nontemporal(&mut streamed[offset..offset + len], &src[..len]);
+            assert_eq!(streamed, expected, "offset={} len={}", offset, len);
+
+            #[cfg(target_arch = "x86_64")]
+            {
+                let mut sse2 = vec![0u8; 4200];
+                unsafe { x86::copy_sse2(&mut sse2[offset..offset + len], &src[..len]) };
+                assert_eq!(sse2, expected, "sse2 offset={} len={}", offset, len);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ingestor_streams_large_frames() {
+        let payload: Vec<u8> = (0..16 * 1024u32).map(|i| i as u8).collect();
+        let frame = MessageHeader::builder().frame(&payload);
+        let ingestor = MarketDataIngestor::new(64 * 1024, 16).with_nontemporal_copy(4096);
+        let rx = ingestor.subscribe();
+        ingestor.process_raw_data(&mut frame.clone()).unwrap();
+        ingestor.process_frame(&MessageHeader::from_bytes(&frame).unwrap(), &payload).unwrap();
+        for _ in 0..2 {
+            assert_eq!(rx.try_recv().unwrap().as_slice(), &frame[..]);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Spans OpenTelemetry do caminho de ingestão (feature `otel`)
+
+use opentelemetry::global::{self, BoxedSpan, BoxedTracer};
+use opentelemetry::trace::{Span, Status, Tracer};
+use opentelemetry::KeyValue;
+
+use crate::ingestion::zero_copy::{read_struct, BookSnapshot, IngestionError, MessageHeader, Quote, Trade};
+use crate::validation::integrity::{symbol_str, ValidationError};
+
+pub const TRACER_NAME: &str = "tensorwerk-nervous";
+
+/// Abre um span por mensagem processada (`tensorwerk.ingest`, cobrindo
+/// parse, cópia para a arena e enfileiramento) e por mensagem validada
+/// (`tensorwerk.validate`), com os atributos `msg_type`, `symbol` e `result`.
+/// Quem decide a amostragem é o `TracerProvider`: para spans não amostrados
+/// nenhum atributo é calculado.
+pub struct IngestionSpans {
+    tracer: BoxedTracer,
+}
+
+impl IngestionSpans {
+    /// Usa o `TracerProvider` global, configurado pela aplicação (ex.: com um
+    /// exportador OTLP). Sem provider configurado, os spans são no-op.
+    pub fn global() -> Self { Self::new(global::tracer(TRACER_NAME)) }
+
+    pub fn new(tracer: BoxedTracer) -> Self { Self { tracer } }
+
+    pub(crate) fn ingest(&self, raw_data: &[u8]) -> BoxedSpan {
+        let mut span = self.tracer.start("tensorwerk.ingest");
+        if span.is_recording() {
+            if let Ok(header) = read_struct::<MessageHeader>(raw_data) {
+                describe(&mut span, &header, raw_data.get(header.payload_offset()..).unwrap_or_default());
+            }
+        }
+        span
+    }
+
+    pub(crate) fn frame(&self, header: &MessageHeader, payload: &[u8]) -> BoxedSpan {
+        let mut span = self.tracer.start("tensorwerk.ingest");
+        if span.is_recording() {
+            describe(&mut span, header, payload);
+        }
+        span
+    }
+
+    pub(crate) fn validate(&self, header: &MessageHeader, payload: &[u8]) -> BoxedSpan {
+        let mut span = self.tracer.start("tensorwerk.validate");
+        if span.is_recording() {
+            describe(&mut span, header, payload);
+        }
+        span
+    }
+}
+
+/// Encerra um span de ingestão: `ok`, `incomplete`, `dropped` (arena), `rejected` ou `paused`.
+pub(crate) fn finish_ingest(mut span: BoxedSpan, result: &Result<(), IngestionError>) {
+    if span.is_recording() {
+        let outcome = match result {
+            Ok(()) => "ok",
+            Err(IngestionError::Incomplete { .. }) => "incomplete",
+            Err(IngestionError::Arena(_)) => "dropped",
+            Err(IngestionError::Validation(_)) => "rejected",
+            Err(IngestionError::Paused) => "paused",
+        };
+        end(&mut span, outcome, result.as_ref().err());
+    }
+    span.end();
+}
+
+/// Encerra um span de validação: `ok` ou `rejected`.
+pub(crate) fn finish_validate(mut span: BoxedSpan, result: &Result<(), ValidationError>) {
+    if span.is_recording() {
+        end(&mut span, if result.is_ok() { "ok" } else { "rejected" }, result.as_ref().err());
+    }
+    span.end();
+}
+
+fn end(span: &mut BoxedSpan, outcome: &'static str, error: Option<&impl std::fmt::Display>) {
+    span.set_attribute(KeyValue::new("result", outcome));
+    if let Some(e) = error {
+        span.set_status(Status::error(e.to_string()));
+    }
+}
+
+fn describe(span: &mut BoxedSpan, header: &MessageHeader, payload: &[u8]) {
+    let msg_type = header.msg_type;
+    span.set_attribute(KeyValue::new("msg_type", i64::from(msg_type)));
+
+    let has_symbol = [Trade::MSG_TYPE, Quote::MSG_TYPE, BookSnapshot::MSG_TYPE].contains(&msg_type);
+    let symbol = payload.first_chunk::<8>().filter(|_| has_symbol).and_then(|symbol| symbol_str(symbol).ok());
+    if let Some(symbol) = symbol {
+        span.set_attribute(KeyValue::new("symbol", symbol.to_owned()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingestion::zero_copy::MarketDataIngestor;
+    use crate::validation::integrity::CompositeValidator;
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry::Value;
+    use opentelemetry_sdk::testing::trace::InMemorySpanExporter;
+    use opentelemetry_sdk::trace::{Sampler, TracerProvider};
+
+    fn tracer(exporter: &InMemorySpanExporter, sampler: Sampler) -> BoxedTracer {
+        let provider = TracerProvider::builder().with_simple_exporter(exporter.clone()).with_sampler(sampler).build();
+        BoxedTracer::new(Box::new(provider.tracer("test")))
+    }
+
+    #[test]
+    fn test_span_per_processed_message() {
+        let exporter = InMemorySpanExporter::default();
+        let ingestor = MarketDataIngestor::new(64 * 1024, 16).with_tracer(tracer(&exporter, Sampler::AlwaysOn));
+        let ts = 1_700_000_000_000_000_000;
+        for i in 0..3 {
+            let mut frame = Trade::new(*b"BTCUSD\0\0", 1, 1, ts + i, 1, i).to_frame();
+            ingestor.process_raw_data(&mut frame).unwrap();
+        }
+        let mut validator = CompositeValidator::builder().build();
+        assert_eq!(ingestor.drain_validated(&mut validator).filter(Result::is_ok).count(), 3);
+
+        let spans = exporter.get_finished_spans().unwrap();
+        let named = |name: &str| spans.iter().filter(|s| s.name == name).count();
+        assert_eq!((named("tensorwerk.ingest"), named("tensorwerk.validate")), (3, 3));
+        for span in &spans {
+            let attribute = |key: &str| span.attributes.iter().find(|kv| kv.key.as_str() == key).map(|kv| kv.value.clone());
+            assert_eq!(attribute("msg_type"), Some(Value::I64(i64::from(Trade::MSG_TYPE))));
+            assert_eq!(attribute("symbol"), Some(Value::from("BTCUSD")));
+            assert_eq!(attribute("result"), Some(Value::from("ok")));
+        }
+
+        let unsampled = InMemorySpanExporter::default();
+        let ingestor = MarketDataIngestor::new(64 * 1024, 16).with_tracer(tracer(&unsampled, Sampler::AlwaysOff));
+        ingestor.process_raw_data(&mut Trade::new(*b"BTCUSD\0\0", 1, 1, ts, 1, 9).to_frame()).unwrap();
+        assert!(unsampled.get_finished_spans().unwrap().is_empty());
+    }
+}
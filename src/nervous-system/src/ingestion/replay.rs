@@ -0,0 +1,375 @@
+//! Replay de capturas de feed com injeção de falhas para testes de resiliência
+
+use bytes::BytesMut;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::ingestion::zero_copy::MessageHeader;
+use crate::validation::integrity::{ChecksumValidator, CompositeValidator};
+
+/// Percorre uma captura bruta (frames concatenados) devolvendo cada frame completo
+/// como slice, sem cópia. Para no primeiro header inválido ou no último frame
+/// completo, ignorando um frame final truncado.
+pub struct MessageIter<'a> {
+    data: &'a [u8],
+    offset: usize,
+    alignment: usize,
+}
+
+impl<'a> MessageIter<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self::with_alignment(data, 1)
+    }
+
+    /// Para regiões em que cada frame começa em múltiplo de `alignment` (ex.: o
+    /// conteúdo de uma arena), pulando o padding entre frames.
+    pub fn with_alignment(data: &'a [u8], alignment: usize) -> Self {
+        Self { data, offset: 0, alignment: alignment.max(1) }
+    }
+
+    /// Bytes consumidos até agora (início do próximo frame).
+    pub fn offset(&self) -> usize { self.offset }
+}
+
+impl<'a> Iterator for MessageIter<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.data[self.offset..];
+        let header = MessageHeader::from_bytes(rest)?;
+        if !header.is_valid() {
+            return None;
+        }
+
+        let total_size = header.frame_size();
+        let frame = rest.get(..total_size)?;
+        self.offset = (self.offset + total_size).next_multiple_of(self.alignment).min(self.data.len());
+        Some(frame)
+    }
+}
+
+/// Resultado de `verify_capture`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureIntegrity {
+    /// Todos os `frames` completos conferem. `torn_tail` bytes no fim formam
+    /// um frame incompleto (escrita interrompida), descartável sem perda dos
+    /// anteriores; 0 = nenhum.
+    Intact { frames: usize, torn_tail: usize },
+    /// Corrupção no meio da captura: o frame em `offset` tem header inválido
+    /// ou checksum (do header, em v2, ou do payload) divergente. Os `frames` anteriores (até
+    /// `offset`) são utilizáveis.
+    Corrupt { offset: usize, frames: usize },
+}
+
+/// Percorre uma captura (frames concatenados) conferindo o header, o checksum
+/// do header (v2) e o do payload de cada frame, e informa o primeiro frame
+/// corrompido.
+/// Ao contrário de `MessageIter`, que só para, distingue um fim truncado de
+/// uma corrupção no meio do arquivo. Um `payload_size` corrompido que aponte
+/// além do fim é indistinguível de um fim truncado e cai em `torn_tail`.
+pub fn verify_capture(data: &[u8], checksum: &ChecksumValidator) -> CaptureIntegrity {
+    let mut offset = 0;
+    let mut frames = 0;
+    while offset < data.len() {
+        let rest = &data[offset..];
+        let Some(header) = MessageHeader::from_bytes(rest) else {
+            return CaptureIntegrity::Intact { frames, torn_tail: rest.len() };
+        };
+        if !header.is_valid() {
+            return CaptureIntegrity::Corrupt { offset, frames };
+        }
+        let Some(payload) = header.payload(rest) else {
+            return CaptureIntegrity::Intact { frames, torn_tail: rest.len() };
+        };
+        let header_intact = header
+            .stored_header_checksum(rest)
+            .is_none_or(|stored| checksum.calculate(&rest[..MessageHeader::SIZE]) == stored);
+        if !header_intact || checksum.calculate(payload) != header.checksum {
+            return CaptureIntegrity::Corrupt { offset, frames };
+        }
+        offset += header.frame_size();
+        frames += 1;
+    }
+    CaptureIntegrity::Intact { frames, torn_tail: 0 }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplayStats {
+    pub frames: usize,
+    pub accepted: usize,
+    pub rejected: usize,
+    /// Frames cujo header ou tamanho não permitem nem chegar à validação.
+    pub malformed: usize,
+}
+
+/// Reproduz uma sequência de frames através de um `CompositeValidator`.
+pub struct ReplayDriver {
+    frames: Vec<BytesMut>,
+}
+
+impl ReplayDriver {
+    pub fn new(frames: Vec<BytesMut>) -> Self {
+        Self { frames }
+    }
+
+    pub fn from_capture(data: &[u8]) -> Self {
+        Self::new(MessageIter::new(data).map(BytesMut::from).collect())
+    }
+
+    pub fn with_faults(self, injector: &mut FaultInjector) -> Self {
+        Self::new(injector.inject(self.frames))
+    }
+
+    pub fn frames(&self) -> &[BytesMut] { &self.frames }
+
+    pub fn run(&self, validator: &mut CompositeValidator) -> ReplayStats {
+        let mut stats = ReplayStats::default();
+
+        for frame in &self.frames {
+            stats.frames += 1;
+
+            let Some(header) = MessageHeader::from_bytes(frame).filter(MessageHeader::is_valid) else {
+                stats.malformed += 1;
+                continue;
+            };
+            let Some(payload) = header.payload(frame) else {
+                stats.malformed += 1;
+                continue;
+            };
+
+            match validator.validate_header_checksum(&header, frame).and_then(|()| validator.validate_message(&header, payload)) {
+                Ok(()) => stats.accepted += 1,
+                Err(_) => stats.rejected += 1,
+            }
+        }
+
+        stats
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FaultKind {
+    /// Altera um byte do payload (o checksum deixa de bater).
+    ByteFlip,
+    /// Corta o payload, deixando `payload_size` maior que os bytes presentes.
+    Truncate,
+    /// Entrega o frame duas vezes.
+    Duplicate,
+    /// Troca o frame de posição com o anterior.
+    Reorder,
+}
+
+/// Frações de frames afetados por cada tipo de falha. Cada frame recebe no
+/// máximo uma falha, então a soma das taxas deve ser ≤ 1.
+#[derive(Debug, Clone, Copy)]
+pub struct FaultConfig {
+    pub byte_flip_rate: f64,
+    pub truncate_rate: f64,
+    pub duplicate_rate: f64,
+    pub reorder_rate: f64,
+    pub seed: u64,
+}
+
+impl Default for FaultConfig {
+    fn default() -> Self {
+        Self { byte_flip_rate: 0.0, truncate_rate: 0.0, duplicate_rate: 0.0, reorder_rate: 0.0, seed: 0 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InjectedFault {
+    /// Índice do frame na sequência original.
+    pub frame_index: usize,
+    pub kind: FaultKind,
+}
+
+/// Corrompe frames de forma reprodutível (RNG com seed) e registra cada falha injetada.
+pub struct FaultInjector {
+    config: FaultConfig,
+    rng: StdRng,
+    injected: Vec<InjectedFault>,
+}
+
+impl FaultInjector {
+    pub fn new(config: FaultConfig) -> Self {
+        Self { config, rng: StdRng::seed_from_u64(config.seed), injected: Vec::new() }
+    }
+
+    pub fn inject(&mut self, frames: Vec<BytesMut>) -> Vec<BytesMut> {
+        let mut out = Vec::with_capacity(frames.len());
+
+        for (frame_index, mut frame) in frames.into_iter().enumerate() {
+            let Some(kind) = self.pick_fault() else {
+                out.push(frame);
+                continue;
+            };
+
+            match kind {
+                FaultKind::ByteFlip => {
+                    let index = if frame.len() > MessageHeader::SIZE {
+                        self.rng.gen_range(MessageHeader::SIZE..frame.len())
+                    } else {
+                        MessageHeader::SIZE - 1
+                    };
+                    frame[index] ^= self.rng.gen_range(1..=u8::MAX);
+                    out.push(frame);
+                }
+                FaultKind::Truncate => {
+                    let payload_len = frame.len().saturating_sub(MessageHeader::SIZE);
+                    let keep = self.rng.gen_range(0..payload_len.max(1));
+                    frame.truncate(MessageHeader::SIZE + keep);
+                    out.push(frame);
+                }
+                FaultKind::Duplicate => {
+                    out.push(frame.clone());
+                    out.push(frame);
+                }
+                FaultKind::Reorder => {
+                    out.push(frame);
+                    let len = out.len();
+                    if len >= 2 {
+                        out.swap(len - 1, len - 2);
+                    }
+                }
+            }
+
+            self.injected.push(InjectedFault { frame_index, kind });
+        }
+
+        out
+    }
+
+    pub fn injected(&self) -> &[InjectedFault] { &self.injected }
+
+    pub fn count(&self, kind: FaultKind) -> usize {
+        self.injected.iter().filter(|f| f.kind == kind).count()
+    }
+
+    fn pick_fault(&mut self) -> Option<FaultKind> {
+        let roll: f64 = self.rng.gen();
+        let mut threshold = 0.0;
+
+        for (rate, kind) in [
+            (self.config.byte_flip_rate, FaultKind::ByteFlip),
+            (self.config.truncate_rate, FaultKind::Truncate),
+            (self.config.duplicate_rate, FaultKind::Duplicate),
+            (self.config.reorder_rate, FaultKind::Reorder),
+        ] {
+            threshold += rate;
+            if roll < threshold {
+                return Some(kind);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingestion::zero_copy::Trade;
+    use crate::validation::integrity::{DataBounds, SymbolValidator};
+
+    fn trade_frames(count: usize) -> Vec<BytesMut> {
+        let base_ts = 1_700_000_000_000_000_000;
+        (0..count)
+            .map(|i| {
+                Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, base_ts + i as u64 * 1_000, 1, i as u64)
+                    .to_frame()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_message_iter_stops_at_last_complete_frame() {
+        let mut capture = Vec::new();
+        for frame in trade_frames(3) {
+            capture.extend_from_slice(&frame);
+        }
+        capture.extend_from_slice(&trade_frames(1)[0][..30]);
+
+        let mut iter = MessageIter::new(&capture);
+        assert_eq!(iter.by_ref().count(), 3);
+        assert_eq!(iter.offset(), 3 * (MessageHeader::SIZE + Trade::SIZE));
+    }
+
+    #[test]
+    fn test_verify_capture_reports_first_corrupt_frame() {
+        let frames = trade_frames(5);
+        let frame_len = MessageHeader::SIZE + Trade::SIZE;
+        let mut capture: Vec<u8> = frames.iter().flat_map(|frame| frame.iter().copied()).collect();
+        let checksum = ChecksumValidator::new();
+        assert_eq!(verify_capture(&capture, &checksum), CaptureIntegrity::Intact { frames: 5, torn_tail: 0 });
+
+        // Fim truncado: os 5 frames continuam íntegros
+        let mut torn = capture.clone();
+        torn.extend_from_slice(&frames[0][..30]);
+        assert_eq!(verify_capture(&torn, &checksum), CaptureIntegrity::Intact { frames: 5, torn_tail: 30 });
+
+        // Byte trocado no payload do terceiro frame
+        capture[2 * frame_len + MessageHeader::SIZE + 10] ^= 0xFF;
+        assert_eq!(verify_capture(&capture, &checksum), CaptureIntegrity::Corrupt { offset: 2 * frame_len, frames: 2 });
+        assert_eq!(MessageIter::new(&capture).count(), 5);
+
+        // Magic destruído no quarto frame
+        capture[2 * frame_len + MessageHeader::SIZE + 10] ^= 0xFF;
+        capture[3 * frame_len] = 0;
+        assert_eq!(verify_capture(&capture, &checksum), CaptureIntegrity::Corrupt { offset: 3 * frame_len, frames: 3 });
+    }
+
+    #[test]
+    fn test_verify_capture_checks_v2_header_checksum() {
+        let ts = 1_700_000_000_000_000_000;
+        let frames: Vec<BytesMut> = (0..3)
+            .map(|i| {
+                let payload = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts + i, 1, i).to_bytes();
+                MessageHeader::builder()
+                    .msg_type(Trade::MSG_TYPE)
+                    .version(MessageHeader::VERSION_HEADER_CHECKSUM)
+                    .timestamp(ts + i)
+                    .frame(&payload)
+            })
+            .collect();
+        let frame_len = frames[0].len();
+        let mut capture: Vec<u8> = frames.iter().flat_map(|frame| frame.iter().copied()).collect();
+        let checksum = ChecksumValidator::new();
+        assert_eq!(verify_capture(&capture, &checksum), CaptureIntegrity::Intact { frames: 3, torn_tail: 0 });
+
+        // Timestamp do segundo frame corrompido: o payload continua íntegro
+        capture[frame_len + 8] ^= 0x01;
+        assert_eq!(verify_capture(&capture, &checksum), CaptureIntegrity::Corrupt { offset: frame_len, frames: 1 });
+    }
+
+    #[test]
+    fn test_fault_injection_matches_rejections() {
+        let config = FaultConfig {
+            byte_flip_rate: 0.1,
+            truncate_rate: 0.05,
+            duplicate_rate: 0.05,
+            reorder_rate: 0.05,
+            seed: 42,
+        };
+        let mut injector = FaultInjector::new(config);
+        let driver = ReplayDriver::new(trade_frames(500)).with_faults(&mut injector);
+
+        let flips = injector.count(FaultKind::ByteFlip);
+        let truncs = injector.count(FaultKind::Truncate);
+        let dups = injector.count(FaultKind::Duplicate);
+        assert!(flips > 0 && truncs > 0 && dups > 0 && injector.count(FaultKind::Reorder) > 0);
+
+        let mut validator = CompositeValidator::new(
+            DataBounds::crypto(),
+            SymbolValidator::whitelist(vec!["BTCUSD".to_string()]),
+        );
+        let stats = driver.run(&mut validator);
+
+        assert_eq!(stats.frames, 500 + dups);
+        assert_eq!(stats.rejected, flips);
+        assert_eq!(stats.malformed, truncs);
+        assert_eq!(stats.accepted, 500 - flips - truncs + dups);
+
+        let mut replayed = FaultInjector::new(config);
+        ReplayDriver::new(trade_frames(500)).with_faults(&mut replayed);
+        assert_eq!(replayed.injected(), injector.injected());
+    }
+}
@@ -0,0 +1,139 @@
+//! Destinos configuráveis para as mensagens aceitas pelo ingestor
+
+use bytes::Bytes;
+use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
+use thiserror::Error;
+
+use crate::ingestion::zero_copy::{Arena, ZeroCopyArena, ZeroCopyBuffer};
+
+#[derive(Debug, Error)]
+pub enum SinkError {
+    #[error("Destino cheio")]
+    Full,
+    #[error("Destino desconectado")]
+    Disconnected,
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Recebe cada mensagem aceita pelo `MarketDataIngestor` (frame completo:
+/// header + payload), na thread de ingestão. O buffer só é emprestado: para
+/// reter a mensagem, copie-a. Erros são contados em `sink_errors` e não
+/// interrompem a ingestão.
+pub trait MessageSink<A: Arena = ZeroCopyArena>: Send + Sync {
+    fn consume(&self, buffer: &ZeroCopyBuffer<A>) -> Result<(), SinkError>;
+}
+
+/// Se o sink substitui o canal principal do ingestor ou roda junto com ele.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SinkMode {
+    /// O canal de `subscribe` deixa de receber mensagens.
+    Replace,
+    #[default]
+    Alongside,
+}
+
+/// Descarta tudo; útil para medir a ingestão sem consumidor.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NullSink;
+
+impl<A: Arena> MessageSink<A> for NullSink {
+    fn consume(&self, _buffer: &ZeroCopyBuffer<A>) -> Result<(), SinkError> { Ok(()) }
+}
+
+/// Copia cada mensagem para um canal limitado próprio, desacoplado da arena
+/// do ingestor (o consumidor pode reter as mensagens sem segurar a arena).
+pub struct ChannelSink {
+    tx: Sender<Bytes>,
+}
+
+impl ChannelSink {
+    pub fn bounded(capacity: usize) -> (Self, Receiver<Bytes>) {
+        let (tx, rx) = bounded(capacity);
+        (Self { tx }, rx)
+    }
+}
+
+impl<A: Arena> MessageSink<A> for ChannelSink {
+    fn consume(&self, buffer: &ZeroCopyBuffer<A>) -> Result<(), SinkError> {
+        self.tx.try_send(Bytes::copy_from_slice(buffer.as_slice())).map_err(|e| match e {
+            TrySendError::Full(_) => SinkError::Full,
+            TrySendError::Disconnected(_) => SinkError::Disconnected,
+        })
+    }
+}
+
+impl<A: Arena, F> MessageSink<A> for F
+where
+    F: Fn(&[u8]) -> Result<(), SinkError> + Send + Sync,
+{
+    fn consume(&self, buffer: &ZeroCopyBuffer<A>) -> Result<(), SinkError> { self(buffer.as_slice()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingestion::zero_copy::{MarketDataIngestor, MessageHeader, Trade};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    struct CountingSink {
+        messages: Arc<AtomicU64>,
+        bytes: Arc<AtomicU64>,
+    }
+
+    impl MessageSink for CountingSink {
+        fn consume(&self, buffer: &ZeroCopyBuffer) -> Result<(), SinkError> {
+            self.messages.fetch_add(1, Ordering::Relaxed);
+            self.bytes.fetch_add(buffer.len() as u64, Ordering::Relaxed);
+            Ok(())
+        }
+    }
+
+    fn feed(ingestor: &MarketDataIngestor, count: u64) {
+        let ts = 1_700_000_000_000_000_000;
+        for i in 0..count {
+            let mut frame = Trade::new(*b"BTCUSD\0\0", 1, 1, ts + i, 1, i).to_frame();
+            ingestor.process_raw_data(&mut frame).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_counting_sink_replaces_or_joins_channel() {
+        let messages = Arc::new(AtomicU64::new(0));
+        let bytes = Arc::new(AtomicU64::new(0));
+        let sink = || CountingSink { messages: Arc::clone(&messages), bytes: Arc::clone(&bytes) };
+
+        let replaced = MarketDataIngestor::new(64 * 1024, 16).with_sink(sink(), SinkMode::Replace);
+        let rx = replaced.subscribe();
+        feed(&replaced, 3);
+        assert_eq!(messages.load(Ordering::Relaxed), 3);
+        assert_eq!(bytes.load(Ordering::Relaxed), 3 * (MessageHeader::SIZE + Trade::SIZE) as u64);
+        assert!(rx.is_empty());
+        assert_eq!(replaced.stats().messages_dropped, 0);
+
+        let alongside = MarketDataIngestor::new(64 * 1024, 16).with_sink(sink(), SinkMode::Alongside);
+        let rx = alongside.subscribe();
+        feed(&alongside, 2);
+        assert_eq!(messages.load(Ordering::Relaxed), 5);
+        assert_eq!(rx.len(), 2);
+
+        let (channel, copies) = ChannelSink::bounded(1);
+        let bounded = MarketDataIngestor::new(64 * 1024, 16).with_sink(channel, SinkMode::Replace);
+        feed(&bounded, 2);
+        assert_eq!(copies.len(), 1);
+        assert_eq!(bounded.stats().sink_errors, 1);
+
+        let closure = MarketDataIngestor::new(64 * 1024, 16)
+            .with_sink(|_: &[u8]| Err(SinkError::Other("indisponível".to_string())), SinkMode::Alongside);
+        feed(&closure, 1);
+        assert_eq!(closure.stats().sink_errors, 1);
+
+        let null = MarketDataIngestor::new(64 * 1024, 16).with_sink(NullSink, SinkMode::Replace);
+        let rx = null.subscribe();
+        feed(&null, 1);
+        assert_eq!((rx.len(), null.stats().messages_received, null.stats().sink_errors), (0, 1, 0));
+    }
+}
@@ -0,0 +1,149 @@
+//! Acumulador structure-of-arrays de trades para kernels SIMD
+
+use std::sync::Arc;
+
+use crate::ingestion::zero_copy::{Arena, ArenaError, ParsedMessage, Trade, ZeroCopyArena, ZeroCopyBuffer, AVX512_ALIGNMENT};
+
+/// Colunas de um `SoABatch` prontas para processamento vetorizado. Cada
+/// slice começa num endereço alinhado a 64 bytes (uma linha de cache / um
+/// registrador AVX-512).
+#[derive(Debug, Clone, Copy)]
+pub struct SoAColumns<'a> {
+    /// Preços × 1e8, como no protocolo.
+    pub prices: &'a [i64],
+    /// Quantidades × 1e8.
+    pub quantities: &'a [i64],
+    pub timestamps: &'a [u64],
+}
+
+impl SoAColumns<'_> {
+    pub fn len(&self) -> usize { self.prices.len() }
+    pub fn is_empty(&self) -> bool { self.prices.is_empty() }
+}
+
+/// Espalha os campos de trades (que chegam intercalados, um struct por
+/// mensagem) em colunas contíguas alocadas na arena. As colunas têm
+/// capacidade fixa: `push` recusa trades com o lote cheio, e `flush` devolve
+/// as colunas preenchidas e recomeça do zero, reaproveitando a memória.
+pub struct SoABatch<A: Arena = ZeroCopyArena> {
+    prices: ZeroCopyBuffer<A>,
+    quantities: ZeroCopyBuffer<A>,
+    timestamps: ZeroCopyBuffer<A>,
+    capacity: usize,
+    len: usize,
+}
+
+const COLUMN_WIDTH: usize = 8;
+
+impl<A: Arena> SoABatch<A> {
+    /// Aloca na arena as três colunas para até `capacity` trades.
+    pub fn new(arena: Arc<A>, capacity: usize) -> Result<Self, ArenaError> {
+        let bytes = capacity
+            .checked_mul(COLUMN_WIDTH)
+            .ok_or(ArenaError::TooLarge { requested: usize::MAX, capacity: arena.capacity() })?;
+        let column = || {
+            let buffer = ZeroCopyBuffer::new(bytes, Arc::clone(&arena))?;
+            debug_assert_eq!(buffer.as_slice().as_ptr() as usize % AVX512_ALIGNMENT, 0);
+            Ok::<_, ArenaError>(buffer)
+        };
+        Ok(Self { prices: column()?, quantities: column()?, timestamps: column()?, capacity, len: 0 })
+    }
+
+    pub fn len(&self) -> usize { self.len }
+    pub fn is_empty(&self) -> bool { self.len == 0 }
+    pub fn capacity(&self) -> usize { self.capacity }
+    pub fn is_full(&self) -> bool { self.len == self.capacity }
+
+    /// Acrescenta um trade; retorna `false` (sem alterar nada) se o lote está cheio.
+    pub fn push(&mut self, trade: &Trade) -> bool {
+        if self.is_full() {
+            return false;
+        }
+        let at = self.len * COLUMN_WIDTH..(self.len + 1) * COLUMN_WIDTH;
+        self.prices.as_mut_slice()[at.clone()].copy_from_slice(&trade.price().to_ne_bytes());
+        self.quantities.as_mut_slice()[at.clone()].copy_from_slice(&trade.quantity().to_ne_bytes());
+        self.timestamps.as_mut_slice()[at].copy_from_slice(&trade.timestamp().to_ne_bytes());
+        self.len += 1;
+        true
+    }
+
+    /// Como `push`, para uma mensagem drenada do ingestor; mensagens que não
+    /// são trades são ignoradas. Retorna `false` só se era um trade e o lote
+    /// está cheio.
+    pub fn push_message<B: Arena>(&mut self, message: &ParsedMessage<B>) -> bool {
+        match message.trade() {
+            Some(trade) => self.push(&trade),
+            None => true,
+        }
+    }
+
+    /// Colunas com os trades acumulados até aqui; o lote volta a ficar vazio.
+    pub fn flush(&mut self) -> SoAColumns<'_> {
+        let len = std::mem::take(&mut self.len);
+        SoAColumns {
+            prices: column(&self.prices, len),
+            quantities: column(&self.quantities, len),
+            timestamps: column(&self.timestamps, len),
+        }
+    }
+}
+
+/// Primeiros `len` valores de uma coluna de 8 bytes.
+fn column<T: Copy, A: Arena>(buffer: &ZeroCopyBuffer<A>, len: usize) -> &[T] {
+    const { assert!(std::mem::size_of::<T>() == COLUMN_WIDTH) };
+    let bytes = &buffer.as_slice()[..len * COLUMN_WIDTH];
+    debug_assert!(bytes.as_ptr().cast::<T>().is_aligned());
+    // SAFETY: o contrato de `Arena` (trait unsafe) garante que o buffer começa
+    // alinhado a `AVX512_ALIGNMENT` (≥ alinhamento de i64/u64), `bytes` cobre
+    // exatamente `len` valores e todo padrão de bits é um i64/u64 válido; o
+    // buffer vive enquanto o lote estiver emprestado.
+    unsafe { std::slice::from_raw_parts(bytes.as_ptr().cast::<T>(), len) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingestion::zero_copy::{MarketDataIngestor, Quote};
+    use crate::validation::integrity::CompositeValidator;
+
+    #[test]
+    fn test_accumulates_aligned_columns() {
+        let ingestor = MarketDataIngestor::new(64 * 1024, 16);
+        let _rx = ingestor.subscribe();
+        let ts = 1_700_000_000_000_000_000;
+        for i in 0..5u64 {
+            let price = (50_000 + i as i64) * 100_000_000;
+            let mut frame = Trade::new(*b"BTCUSD\0\0", price, (i as i64 + 1) * 1_000_000, ts + i, 1, i).to_frame();
+            ingestor.process_raw_data(&mut frame).unwrap();
+        }
+        let quote = Quote::new(*b"BTCUSD\0\0", 100, 1, 101, 1, ts + 5);
+        ingestor.process_raw_data(&mut quote.to_frame()).unwrap();
+
+        let arena = Arc::new(ZeroCopyArena::new(4096).unwrap());
+        let mut batch = SoABatch::new(Arc::clone(&arena), 4).unwrap();
+        let mut validator = CompositeValidator::builder().build();
+        let mut overflow = Vec::new();
+        for message in ingestor.drain_validated(&mut validator) {
+            let message = message.unwrap();
+            if !batch.push_message(&message) {
+                overflow.push(message.trade().unwrap());
+            }
+        }
+        assert!(batch.is_full());
+        assert_eq!(overflow.len(), 1);
+
+        let columns = batch.flush();
+        assert_eq!(columns.len(), 4);
+        for ptr in [columns.prices.as_ptr() as usize, columns.quantities.as_ptr() as usize, columns.timestamps.as_ptr() as usize] {
+            assert_eq!(ptr % AVX512_ALIGNMENT, 0);
+        }
+        assert_eq!(columns.prices, [5_000_000_000_000, 5_000_100_000_000, 5_000_200_000_000, 5_000_300_000_000]);
+        assert_eq!(columns.quantities, [1_000_000, 2_000_000, 3_000_000, 4_000_000]);
+        assert_eq!(columns.timestamps, [ts, ts + 1, ts + 2, ts + 3]);
+
+        assert!(batch.is_empty());
+        assert!(batch.push(&overflow[0]));
+        assert_eq!(batch.flush().timestamps, [ts + 4]);
+        assert_eq!(arena.used(), 3 * (4 * COLUMN_WIDTH).next_multiple_of(AVX512_ALIGNMENT));
+    }
+}
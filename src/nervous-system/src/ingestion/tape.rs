@@ -0,0 +1,127 @@
+//! Fita das mensagens recentes por símbolo, para visualização ao vivo e depuração
+
+use std::collections::{HashMap, VecDeque};
+
+use bytes::Bytes;
+use parking_lot::Mutex;
+
+use crate::ingestion::zero_copy::{message_symbol, read_struct, MessageHeader, Quote, Trade};
+
+/// Últimas `depth` mensagens de cada símbolo, copiadas para o heap na
+/// ingestão: a fita não segura buffers da arena do ingestor (que então
+/// rebobina normalmente) nem consome o canal principal. A memória fica
+/// limitada a `depth` frames por símbolo e `max_symbols` símbolos; símbolos
+/// novos além do limite não entram na fita.
+///
+/// Só mensagens com símbolo (trade, quote, book snapshot) são guardadas,
+/// pelos 8 bytes do payload como chegaram (sem normalização).
+pub struct RecentTape {
+    depth: usize,
+    max_symbols: usize,
+    frames: Mutex<HashMap<[u8; 8], VecDeque<TapeMessage>>>,
+}
+
+/// Mensagem guardada na fita: header decodificado e o frame inteiro no heap.
+/// Clonar é barato (o frame é compartilhado).
+#[derive(Debug, Clone)]
+pub struct TapeMessage {
+    pub header: MessageHeader,
+    frame: Bytes,
+}
+
+impl TapeMessage {
+    pub fn frame(&self) -> &[u8] { &self.frame }
+
+    pub fn payload(&self) -> &[u8] {
+        &self.frame[self.header.payload_offset()..self.header.frame_size()]
+    }
+
+    pub fn trade(&self) -> Option<Trade> {
+        if self.header.msg_type != Trade::MSG_TYPE {
+            return None;
+        }
+        read_struct(self.payload()).ok()
+    }
+
+    pub fn quote(&self) -> Option<Quote> {
+        if self.header.msg_type != Quote::MSG_TYPE {
+            return None;
+        }
+        read_struct(self.payload()).ok()
+    }
+}
+
+impl RecentTape {
+    pub fn new(depth: usize, max_symbols: usize) -> Self {
+        Self { depth, max_symbols, frames: Mutex::new(HashMap::new()) }
+    }
+
+    pub(crate) fn record(&self, frame: &[u8]) {
+        if self.depth == 0 {
+            return;
+        }
+        let Some(header) = MessageHeader::from_bytes(frame) else { return };
+        let Some(symbol) = header.payload(frame).and_then(|payload| message_symbol(header.msg_type, payload)) else {
+            return;
+        };
+
+        let mut frames = self.frames.lock();
+        if !frames.contains_key(&symbol) && frames.len() >= self.max_symbols {
+            return;
+        }
+        let recent = frames.entry(symbol).or_insert_with(|| VecDeque::with_capacity(self.depth));
+        if recent.len() == self.depth {
+            recent.pop_front();
+        }
+        recent.push_back(TapeMessage { header, frame: Bytes::copy_from_slice(&frame[..header.frame_size()]) });
+    }
+
+    /// Até `n` mensagens mais recentes de `symbol` (completado com NUL até 8
+    /// bytes), da mais antiga para a mais nova, compartilhando os frames da fita.
+    pub fn recent(&self, symbol: &str, n: usize) -> Vec<TapeMessage> {
+        let mut key = [0u8; 8];
+        for (slot, byte) in key.iter_mut().zip(symbol.bytes()) {
+            *slot = byte;
+        }
+        match self.frames.lock().get(&key) {
+            Some(recent) => recent.iter().skip(recent.len().saturating_sub(n)).cloned().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Símbolos presentes na fita, em ordem arbitrária.
+    pub fn symbols(&self) -> Vec<[u8; 8]> {
+        self.frames.lock().keys().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ingestion::zero_copy::{MarketDataIngestor, Quote, Trade};
+
+    #[test]
+    fn test_recent_returns_last_messages_in_order() {
+        let ingestor = MarketDataIngestor::new(64 * 1024, 64).with_recent_tape(4, 2);
+        let rx = ingestor.subscribe();
+        let ts = 1_700_000_000_000_000_000;
+        for i in 0..6 {
+            ingestor.process_raw_data(&mut Trade::new(*b"BTCUSD\0\0", 1, 1, ts + i, 1, i).to_frame()).unwrap();
+        }
+        ingestor.process_raw_data(&mut Quote::new(*b"ETHUSD\0\0", 1, 1, 2, 1, ts).to_frame()).unwrap();
+        ingestor.process_raw_data(&mut Quote::new(*b"SOLUSD\0\0", 1, 1, 2, 1, ts).to_frame()).unwrap();
+        // Canal principal intacto
+        assert_eq!(rx.len(), 8);
+
+        let tape = ingestor.recent_tape().unwrap();
+        let ids: Vec<u64> = tape.recent("BTCUSD", 3).iter().map(|m| m.trade().unwrap().trade_id()).collect();
+        assert_eq!(ids, [3, 4, 5]);
+        assert_eq!(tape.recent("BTCUSD", 10).len(), 4);
+        assert_eq!(tape.recent("ETHUSD", 10).len(), 1);
+        // Limite de símbolos: SOLUSD não coube
+        assert!(tape.recent("SOLUSD", 10).is_empty());
+
+        // A fita não segura a arena do ingestor
+        drop(rx.try_iter().collect::<Vec<_>>());
+        assert_eq!(ingestor.warmup(0), 1);
+    }
+}
@@ -0,0 +1,118 @@
+//! Vazão por janela de tempo (mensagens e bytes), para planejamento de capacidade
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+/// Mensagens e bytes ingeridos numa janela `[window_start, window_start + janela)`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ThroughputBucket {
+    /// Início da janela em ns desde a época Unix, múltiplo da duração da janela.
+    pub window_start: u64,
+    pub messages: u64,
+    pub bytes: u64,
+}
+
+/// Anel com as últimas `capacity` janelas de vazão, alinhadas a múltiplos de
+/// `window`. Janelas sem mensagens entram zeradas, para que rajadas e pausas
+/// apareçam (o contador acumulado esconde as duas). Registros com tempo de
+/// uma janela já fora do anel são ignorados.
+pub struct ThroughputRecorder {
+    window_nanos: u64,
+    capacity: usize,
+    buckets: Mutex<VecDeque<ThroughputBucket>>,
+}
+
+impl ThroughputRecorder {
+    pub fn new(window: Duration, capacity: usize) -> Self {
+        Self {
+            window_nanos: (window.as_nanos() as u64).max(1),
+            capacity,
+            buckets: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Conta uma mensagem de `bytes` bytes no instante `now_nanos` (o relógio
+    /// injetado do ingestor, ver `MarketDataIngestor::with_throughput_log`).
+    pub fn record(&self, now_nanos: u64, bytes: u64) {
+        if self.capacity == 0 {
+            return;
+        }
+        let window_start = now_nanos - now_nanos % self.window_nanos;
+        let mut buckets = self.buckets.lock();
+
+        let last_start = buckets.back().map(|last| last.window_start);
+        if last_start.is_none_or(|last| window_start > last) {
+            // Janelas vazias no meio, limitadas ao tamanho do anel
+            let skipped = last_start.map_or(0, |last| (window_start - last) / self.window_nanos - 1);
+            let first = window_start - skipped.min(self.capacity as u64 - 1) * self.window_nanos;
+            for start in (first..=window_start).step_by(self.window_nanos as usize) {
+                if buckets.len() == self.capacity {
+                    buckets.pop_front();
+                }
+                buckets.push_back(ThroughputBucket { window_start: start, ..Default::default() });
+            }
+        }
+
+        if let Some(bucket) = buckets.iter_mut().rev().find(|bucket| bucket.window_start == window_start) {
+            bucket.messages += 1;
+            bucket.bytes += bytes;
+        }
+    }
+
+    /// Janelas retidas, da mais antiga para a mais recente (a última pode
+    /// estar em andamento).
+    pub fn buckets(&self) -> Vec<ThroughputBucket> {
+        self.buckets.lock().iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::ingestion::clock::MockClock;
+    use crate::ingestion::zero_copy::{MarketDataIngestor, Trade};
+
+    #[test]
+    fn test_buckets_split_at_window_boundaries() {
+        const SECOND: u64 = 1_000_000_000;
+        let start = 1_700_000_000 * SECOND;
+        let clock = Arc::new(MockClock::new(start + SECOND - 1));
+        let ingestor = MarketDataIngestor::new(64 * 1024, 64).with_clock(clock.clone()).with_throughput_log(Duration::from_secs(1), 3);
+        let frame = Trade::new(*b"BTCUSD\0\0", 1, 1, start, 1, 1).to_frame();
+        let size = frame.len() as u64;
+
+        for _ in 0..3 {
+            ingestor.process_raw_data(&mut frame.clone()).unwrap();
+        }
+        // Um nanossegundo depois já é a janela seguinte
+        clock.advance(Duration::from_nanos(1));
+        for _ in 0..2 {
+            ingestor.process_raw_data(&mut frame.clone()).unwrap();
+        }
+
+        let log = ingestor.throughput_log().unwrap();
+        assert_eq!(
+            log.buckets(),
+            [
+                ThroughputBucket { window_start: start, messages: 3, bytes: 3 * size },
+                ThroughputBucket { window_start: start + SECOND, messages: 2, bytes: 2 * size },
+            ]
+        );
+
+        // Pausa de 2 s: a janela vazia aparece, e o anel guarda só 3
+        clock.advance(Duration::from_secs(2));
+        ingestor.process_raw_data(&mut frame.clone()).unwrap();
+        let starts: Vec<(u64, u64)> = log.buckets().iter().map(|b| ((b.window_start - start) / SECOND, b.messages)).collect();
+        assert_eq!(starts, [(1, 2), (2, 0), (3, 1)]);
+
+        // Pausa maior que o anel inteiro
+        clock.advance(Duration::from_secs(100));
+        ingestor.process_raw_data(&mut frame.clone()).unwrap();
+        let starts: Vec<(u64, u64)> = log.buckets().iter().map(|b| ((b.window_start - start) / SECOND, b.messages)).collect();
+        assert_eq!(starts, [(101, 0), (102, 0), (103, 1)]);
+    }
+}
@@ -1,40 +1,497 @@
 //! Ingestão zero-copy de dados de mercado com latência < 10μs
 
-use bytes::{Buf, BytesMut};
-use crossbeam_channel::{bounded, Sender};
-use parking_lot::Mutex;
+use bytes::{Buf, Bytes, BytesMut};
+use crossbeam_channel::{bounded, Receiver, Sender};
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
-use std::alloc::{alloc, dealloc, Layout};
+use std::alloc::{alloc_zeroed, dealloc, Layout};
+use std::collections::HashMap;
 use std::ptr::NonNull;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+use thiserror::Error;
 use tracing::{debug, info, warn};
 
+use crate::ingestion::broadcast::{Broadcaster, SlowSubscriberPolicy};
+use crate::ingestion::byte_order::{ByteOrder, Endian};
+use crate::ingestion::clock::{self, Clock, SystemClock};
+use crate::ingestion::filter::DiscardFilter;
+use crate::ingestion::flow_control::{Admission, ProducerToken, RateCeiling};
+use crate::ingestion::histogram::LatencyHistogram;
+use crate::ingestion::nontemporal;
+#[cfg(feature = "otel")]
+use crate::ingestion::otel::{self, IngestionSpans};
+use crate::ingestion::sink::{MessageSink, SinkMode};
+use crate::ingestion::tape::RecentTape;
+use crate::ingestion::throughput::ThroughputRecorder;
+use crate::validation::integrity::{CachedChecksum, Checksum, ChecksumValidator, CompositeValidator, ValidationError};
+
 pub const RECV_BUFFER_SIZE: usize = 16 * 1024 * 1024;
 pub const AVX512_ALIGNMENT: usize = 64;
 pub const MAX_PENDING_MESSAGES: usize = 10_000;
 
+/// Cópia somente-leitura de uma arena gravada por `ZeroCopyArena::dump`.
+///
+/// Formato: magic `u32` ("TWAD"), `capacity`, `offset` e `alignment` (`u64`),
+/// todos little-endian, seguidos dos `offset` bytes da região usada.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArenaDump {
+    pub capacity: usize,
+    pub offset: usize,
+    pub alignment: usize,
+    pub data: Vec<u8>,
+}
+
+impl ArenaDump {
+    pub const MAGIC: u32 = u32::from_le_bytes(*b"TWAD");
+    const HEADER_SIZE: usize = 4 + 3 * 8;
+
+    pub fn load_dump(reader: &mut dyn std::io::Read) -> Result<Self, std::io::Error> {
+        let invalid = |msg: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, msg.to_string());
+
+        let mut header = [0u8; Self::HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+        if u32::from_le_bytes(header[0..4].try_into().unwrap()) != Self::MAGIC {
+            return Err(invalid("Dump de arena inválido"));
+        }
+        let field = |i: usize| u64::from_le_bytes(header[4 + i * 8..12 + i * 8].try_into().unwrap()) as usize;
+        let (capacity, offset, alignment) = (field(0), field(1), field(2));
+        if offset > capacity || alignment == 0 {
+            return Err(invalid("Metadados do dump inconsistentes"));
+        }
+
+        let mut data = vec![0u8; offset];
+        reader.read_exact(&mut data)?;
+        Ok(Self { capacity, offset, alignment, data })
+    }
+
+    /// Frames na ordem de alocação, pulando o padding de alinhamento entre eles.
+    pub fn frames(&self) -> crate::ingestion::replay::MessageIter<'_> {
+        crate::ingestion::replay::MessageIter::with_alignment(&self.data, self.alignment)
+    }
+}
+
+/// Buffer vivo há mais tempo que o limite de `ZeroCopyArena::with_leak_detection`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct BufferInfo {
+    pub offset: usize,
+    pub len: usize,
+    /// Tag de `ZeroCopyBuffer::new_tagged` (0 = sem tag).
+    pub tag: u32,
+    pub age: Duration,
+}
+
+/// Buffers vivos por offset, com o instante (relógio injetado) da alocação.
+struct LeakTracker {
+    max_age_nanos: u64,
+    clock: Arc<dyn Clock>,
+    outstanding: parking_lot::Mutex<HashMap<usize, (usize, u32, u64)>>,
+}
+
+/// Callback invocado quando a arena esgota (ver `ZeroCopyArena::with_on_exhausted`).
+pub type ExhaustedCallback = Box<dyn Fn(&ArenaStats) + Send + Sync>;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ArenaStats {
+    pub capacity: usize,
+    pub used: usize,
+    pub failed_allocations: u64,
+    /// Alocações bem-sucedidas por classe de tamanho solicitado (ver
+    /// `size_class`), acumuladas desde a criação da arena.
+    #[cfg(feature = "arena-size-classes")]
+    pub size_classes: [u64; SIZE_CLASSES],
+}
+
+/// Classes de tamanho do histograma de alocações: a classe 0 vai até
+/// `AVX512_ALIGNMENT` bytes, cada classe seguinte dobra o limite e a última
+/// acumula tudo acima de 16 MiB.
+#[cfg(feature = "arena-size-classes")]
+pub const SIZE_CLASSES: usize = 20;
+
+/// Classe de tamanho de uma alocação de `size` bytes.
+#[cfg(feature = "arena-size-classes")]
+pub fn size_class(size: usize) -> usize {
+    if size <= AVX512_ALIGNMENT {
+        return 0;
+    }
+    let bits = size.checked_next_power_of_two().map_or(usize::BITS, usize::trailing_zeros);
+    let class = (bits - AVX512_ALIGNMENT.trailing_zeros()) as usize;
+    class.min(SIZE_CLASSES - 1)
+}
+
+/// Maior tamanho (inclusive) da classe `class`; `usize::MAX` para a última.
+#[cfg(feature = "arena-size-classes")]
+pub fn size_class_limit(class: usize) -> usize {
+    if class >= SIZE_CLASSES - 1 { usize::MAX } else { AVX512_ALIGNMENT << class }
+}
+
+const NEVER_FIRED: u64 = u64::MAX;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ArenaError {
+    #[error("Arena esgotada: solicitado={requested}, disponível={available}")]
+    Exhausted { requested: usize, available: usize },
+    /// A alocação (alinhada) excede a capacidade total: nem a arena vazia a
+    /// comporta, então retentar não adianta.
+    #[error("Alocação maior que a arena: solicitado={requested}, capacidade={capacity}")]
+    TooLarge { requested: usize, capacity: usize },
+    /// `allocate_at` pediu uma região que cruza outra reserva ou a parte já
+    /// usada pelo bump.
+    #[error("Região reservada sobreposta: offset={offset}, tamanho={size}")]
+    Overlap { offset: usize, size: usize },
+    /// Capacidade que não forma uma arena utilizável.
+    #[error("Layout de arena inválido: {reason}")]
+    InvalidLayout { reason: String },
+}
+
+impl From<ArenaError> for std::io::Error {
+    fn from(e: ArenaError) -> Self {
+        match e {
+            ArenaError::InvalidLayout { .. } | ArenaError::TooLarge { .. } => {
+                std::io::Error::new(std::io::ErrorKind::InvalidInput, e)
+            }
+            ArenaError::Exhausted { .. } | ArenaError::Overlap { .. } => std::io::Error::other(e),
+        }
+    }
+}
+
+/// Menor capacidade aceita por `ZeroCopyArena::new`: uma alocação alinhada.
+pub const MIN_ARENA_CAPACITY: usize = AVX512_ALIGNMENT;
+
+/// Endereço devolvido para alocações de tamanho zero: não aponta para a
+/// região (não consome espaço nem é rastreado), mas é não nulo e alinhado.
+#[inline]
+fn zero_sized_ptr() -> NonNull<u8> {
+    // SAFETY: AVX512_ALIGNMENT é diferente de zero
+    unsafe { NonNull::new_unchecked(std::ptr::without_provenance_mut(AVX512_ALIGNMENT)) }
+}
+
+/// Pede ao kernel que mapeie `[addr, addr + len)` (início alinhado à página)
+/// sem acessar a memória deste processo.
+#[cfg(unix)]
+fn prefault_pages(addr: usize, len: usize) {
+    // SAFETY: `madvise` só consulta o mapeamento; a faixa pertence à arena
+    // (a primeira página pode começar antes dela, no mesmo mapeamento)
+    unsafe {
+        #[cfg(target_os = "linux")]
+        if libc::madvise(addr as *mut libc::c_void, len, libc::MADV_POPULATE_READ) == 0 {
+            return;
+        }
+        libc::madvise(addr as *mut libc::c_void, len, libc::MADV_WILLNEED);
+    }
+}
+
+#[cfg(not(unix))]
+fn prefault_pages(_addr: usize, _len: usize) {}
+
+/// Erro de topo do caminho de ingestão (`process_raw_data`, `process_frame`).
+/// Cada variante tem um código FFI estável (ver `ffi_code`).
+#[derive(Error, Debug)]
+pub enum IngestionError {
+    /// Faltam bytes para o header ou para o payload declarado; o chamador deve
+    /// esperar mais dados antes de tentar de novo.
+    #[error("Dados incompletos: necessário={needed}, disponível={available}")]
+    Incomplete { needed: usize, available: usize },
+    #[error(transparent)]
+    Arena(#[from] ArenaError),
+    /// Header ou mensagem de controle rejeitados (`CorruptedFormat`, `UnknownProtocol`, ...).
+    #[error(transparent)]
+    Validation(#[from] ValidationError),
+    /// Ingestor pausado (`MarketDataIngestor::pause`): nada foi consumido de
+    /// `raw_data`, que pode ser reapresentado após `resume`.
+    #[error("Ingestão pausada")]
+    Paused,
+}
+
+impl IngestionError {
+    pub const FFI_ARENA: i32 = -2;
+    pub const FFI_INCOMPLETE: i32 = -3;
+    pub const FFI_VALIDATION: i32 = -4;
+    pub const FFI_PAUSED: i32 = -6;
+
+    /// Código negativo devolvido pela FFI. -1 é reservado para ponteiros nulos;
+    /// -2 mantém o significado histórico de falha de alocação.
+    pub fn ffi_code(&self) -> i32 {
+        match self {
+            Self::Arena(_) => Self::FFI_ARENA,
+            Self::Incomplete { .. } => Self::FFI_INCOMPLETE,
+            Self::Validation(_) => Self::FFI_VALIDATION,
+            Self::Paused => Self::FFI_PAUSED,
+        }
+    }
+}
+
+impl From<IngestionError> for std::io::Error {
+    fn from(e: IngestionError) -> Self {
+        let kind = match e {
+            IngestionError::Incomplete { .. } => std::io::ErrorKind::UnexpectedEof,
+            IngestionError::Validation(_) => std::io::ErrorKind::InvalidData,
+            IngestionError::Arena(_) => std::io::ErrorKind::OutOfMemory,
+            IngestionError::Paused => std::io::ErrorKind::WouldBlock,
+        };
+        std::io::Error::new(kind, e)
+    }
+}
+
+/// Resultado de `MarketDataIngestor::process_batch_tolerant`.
+#[derive(Debug, Default)]
+pub struct TolerantBatchReport {
+    /// Frames processados sem erro (inclusive os descartados pelo filtro ou
+    /// pelo teto de taxa, que não são erros).
+    pub ingested: usize,
+    /// Erros na ordem em que ocorreram, com o offset no lote do frame que falhou.
+    pub errors: Vec<(usize, IngestionError)>,
+    /// Bytes pulados procurando o próximo magic depois de headers corrompidos
+    /// ou frames que não cabem no lote.
+    pub skipped_bytes: usize,
+}
+
+/// Alocação bump lock-free sobre uma região contígua alinhada a
+/// `AVX512_ALIGNMENT`: cada alocação avança `offset` (CAS) pelo tamanho
+/// arredondado ao alinhamento. Compartilhada por `ZeroCopyArena` (heap) e
+/// `FixedArena` (array embutido).
+///
+/// # Safety
+///
+/// `bump` calcula ponteiros a partir dos valores do implementador, e quem os
+/// recebe (`ZeroCopyBuffer`, as colunas de `SoABatch`) lê e escreve por eles
+/// sem outras checagens. O implementador garante que:
+///
+/// - `region_base` é não nulo, alinhado a `AVX512_ALIGNMENT` e válido para
+///   leitura e escrita de `region_capacity` bytes enquanto `self` existir;
+/// - os bytes abaixo de `region_limit` só são acessados pelos ponteiros que
+///   `bump` entrega, e `region_limit` nunca passa de `region_capacity`;
+/// - `region_offset` e `region_live_buffers` devolvem sempre os mesmos
+///   atômicos, e o offset só muda pelos métodos padrão deste trait;
+/// - `region_alignment` é uma potência de dois que divide `AVX512_ALIGNMENT`.
+pub unsafe trait BumpAllocator {
+    /// Início da região; deve estar alinhado a `AVX512_ALIGNMENT`.
+    fn region_base(&self) -> *mut u8;
+    fn region_capacity(&self) -> usize;
+    fn region_offset(&self) -> &AtomicU64;
+    /// Buffers vivos sobre a região; com zero, `reclaim` pode rebobiná-la.
+    fn region_live_buffers(&self) -> &AtomicU64;
+
+    /// Até onde o bump pode avançar; abaixo de `region_capacity` quando o fim
+    /// da região tem reservas fixas (ver `ZeroCopyArena::allocate_at`).
+    #[inline]
+    fn region_limit(&self) -> usize { self.region_capacity() }
+
+    /// Alinhamento garantido a todo ponteiro entregue pela região.
+    #[inline]
+    fn region_alignment(&self) -> usize { AVX512_ALIGNMENT }
+
+    /// Se `ptr`, devolvido por uma alocação de `size` bytes, respeita
+    /// `region_alignment` e cabe inteiro em `[base, base + capacidade)`.
+    /// Alocações de tamanho zero só precisam estar alinhadas. `bump` confere
+    /// cada alocação com isto em builds de debug (sem custo em release).
+    fn verify_alignment(&self, ptr: NonNull<u8>, size: usize) -> bool {
+        let addr = ptr.as_ptr() as usize;
+        if !addr.is_multiple_of(self.region_alignment()) {
+            return false;
+        }
+        let base = self.region_base() as usize;
+        size == 0
+            || addr
+                .checked_sub(base)
+                .and_then(|offset| offset.checked_add(size))
+                .is_some_and(|end| end <= self.region_capacity())
+    }
+
+    #[inline]
+    fn bump(&self, size: usize) -> Result<NonNull<u8>, ArenaError> {
+        if size == 0 {
+            return Ok(zero_sized_ptr());
+        }
+        let capacity = self.region_capacity() as u64;
+        let offset = self.region_offset();
+        let aligned_size = size.checked_next_multiple_of(AVX512_ALIGNMENT).map_or(u64::MAX, |s| s as u64);
+        if aligned_size > capacity {
+            return Err(ArenaError::TooLarge { requested: size, capacity: capacity as usize });
+        }
+        let mut current_offset = offset.load(Ordering::Acquire);
+
+        let exhausted = |limit: u64, current_offset: u64| ArenaError::Exhausted {
+            requested: size,
+            available: limit.saturating_sub(current_offset) as usize,
+        };
+        loop {
+            let limit = self.region_limit() as u64;
+            if aligned_size > limit.saturating_sub(current_offset) {
+                return Err(exhausted(limit, current_offset));
+            }
+
+            match offset.compare_exchange_weak(
+                current_offset,
+                current_offset + aligned_size,
+                Ordering::SeqCst,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => break,
+                Err(actual) => current_offset = actual,
+            }
+        }
+
+        // Uma reserva concorrente pode ter baixado o limite entre a checagem e
+        // o CAS (ela relê o offset depois de baixá-lo, então um dos dois vê o
+        // outro). Desfaz o avanço se ainda for o último; senão o espaço fica
+        // perdido até o próximo `reclaim`, como o de qualquer alocação.
+        let limit = self.region_limit() as u64;
+        if current_offset + aligned_size > limit {
+            let _ = offset.compare_exchange(current_offset + aligned_size, current_offset, Ordering::SeqCst, Ordering::Relaxed);
+            return Err(exhausted(limit, current_offset));
+        }
+
+        let ptr = unsafe { NonNull::new_unchecked(self.region_base().add(current_offset as usize)) };
+        debug_assert!(
+            self.verify_alignment(ptr, size),
+            "alocação de {} bytes em {:p} viola alinhamento ou limites da região",
+            size,
+            ptr
+        );
+        Ok(ptr)
+    }
+
+    /// Rebobina a região para o início se nenhum buffer estiver vivo.
+    fn reclaim(&self) -> bool {
+        // O offset é lido antes da contagem: um buffer que aloque entre as duas
+        // leituras muda o offset e faz a troca abaixo falhar.
+        let offset = self.region_offset();
+        let current_offset = offset.load(Ordering::SeqCst);
+        if current_offset == 0 || self.region_live_buffers().load(Ordering::SeqCst) != 0 {
+            return false;
+        }
+        offset.compare_exchange(current_offset, 0, Ordering::SeqCst, Ordering::SeqCst).is_ok()
+    }
+}
+
+/// Estratégia de alocação por trás de `ZeroCopyBuffer` e `MarketDataIngestor`.
+/// `ZeroCopyArena` (heap) é a padrão; `FixedArena` dispensa o heap.
+///
+/// `ZeroCopyBuffer` chama `buffer_acquired` antes de alocar e `buffer_released`
+/// ao ser descartado, para que `reset` nunca rebobine sob um buffer vivo.
+///
+/// # Safety
+///
+/// `ZeroCopyBuffer` e `SoABatch` leem e escrevem pela memória entregue sem
+/// outras checagens (as colunas de `SoABatch` a reinterpretam como `&[i64]`).
+/// O implementador garante que:
+///
+/// - `allocate` e `allocate_tagged` com `size > 0` devolvem um ponteiro
+///   alinhado a `AVX512_ALIGNMENT`, válido para leitura e escrita de `size`
+///   bytes e disjunto de toda outra alocação ainda viva;
+/// - com `size == 0`, devolvem um ponteiro não nulo alinhado a
+///   `AVX512_ALIGNMENT`, que nunca é acessado;
+/// - essa memória segue válida até o `buffer_released` correspondente:
+///   `reset` só a reaproveita quando não há `buffer_acquired` sem o
+///   `buffer_released` correspondente.
+pub unsafe trait Arena: Send + Sync + 'static {
+    fn allocate(&self, size: usize) -> Result<NonNull<u8>, ArenaError>;
+    fn used(&self) -> usize;
+    fn capacity(&self) -> usize;
+    /// Rebobina a arena se nenhum buffer dela estiver vivo. Ponteiros obtidos
+    /// diretamente de `allocate` deixam de ser válidos. Retorna `true` se rebobinou.
+    fn reset(&self) -> bool;
+    fn buffer_acquired(&self);
+    fn buffer_released(&self, ptr: NonNull<u8>, tag: u32);
+
+    /// Bytes presos por buffers vivos: `used()`, ou 0 se nenhum buffer está
+    /// vivo (o espaço todo é recuperável por `reset`). O padrão é `used()`.
+    fn in_use(&self) -> usize { self.used() }
+
+    /// Chamado por `ZeroCopyBuffer` depois de uma alocação bem-sucedida, com
+    /// a região entregue ao buffer (para rastreamento de vazamentos).
+    fn buffer_allocated(&self, ptr: NonNull<u8>, len: usize, tag: u32) {
+        let _ = (ptr, len, tag);
+    }
+
+    /// Como `allocate`, marcando a região com `tag` quando a arena rastreia tags.
+    fn allocate_tagged(&self, size: usize, tag: u32) -> Result<NonNull<u8>, ArenaError> {
+        let _ = tag;
+        self.allocate(size)
+    }
+
+    fn failed_allocations(&self) -> u64 { 0 }
+}
+
 pub struct ZeroCopyArena {
     base_ptr: NonNull<u8>,
     capacity: usize,
     offset: AtomicU64,
     layout: Layout,
+    /// `ZeroCopyBuffer`s ainda vivos; com zero, `try_reclaim` pode rebobinar a arena.
+    live_buffers: AtomicU64,
+    /// Offset → tag das alocações marcadas (só em builds de debug).
+    #[cfg(debug_assertions)]
+    tags: parking_lot::Mutex<std::collections::BTreeMap<usize, u32>>,
+    failed_allocations: AtomicU64,
+    created_at: Instant,
+    on_exhausted: Option<ExhaustedCallback>,
+    exhausted_interval_nanos: u64,
+    last_exhausted_nanos: AtomicU64,
+    /// Loga 1 a cada N alocações bem-sucedidas; 0 = desligado.
+    allocation_log_every: u64,
+    allocation_count: AtomicU64,
+    #[cfg(feature = "arena-size-classes")]
+    size_classes: [AtomicU64; SIZE_CLASSES],
+    leaks: Option<LeakTracker>,
+    /// Regiões de `allocate_at`: offset → tamanho alinhado.
+    placements: Mutex<std::collections::BTreeMap<usize, usize>>,
+    /// Menor offset reservado (`capacity` sem reservas); teto do bump.
+    placement_floor: AtomicU64,
 }
 
 unsafe impl Send for ZeroCopyArena {}
 unsafe impl Sync for ZeroCopyArena {}
 
+/// Thread de baixa prioridade que mantém as páginas da arena residentes (ver
+/// `ZeroCopyArena::spawn_page_warmer`). Parada e aguardada no `drop`.
+pub struct PageWarmer {
+    running: Arc<AtomicBool>,
+    sweeps: Arc<AtomicU64>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl PageWarmer {
+    /// Varreduras completas da arena feitas até agora.
+    pub fn sweeps(&self) -> u64 { self.sweeps.load(Ordering::Relaxed) }
+}
+
+impl Drop for PageWarmer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Release);
+        if let Some(handle) = self.handle.take() {
+            handle.thread().unpark();
+            let _ = handle.join();
+        }
+    }
+}
+
 impl ZeroCopyArena {
+    /// Capacidades abaixo de `MIN_ARENA_CAPACITY` são rejeitadas com
+    /// `ArenaError::InvalidLayout` (como `io::Error` de `InvalidInput`); as
+    /// demais são arredondadas para cima até o alinhamento.
     pub fn new(capacity: usize) -> Result<Self, std::io::Error> {
-        let aligned_capacity = (capacity + AVX512_ALIGNMENT - 1) / AVX512_ALIGNMENT * AVX512_ALIGNMENT;
+        let invalid = |reason: String| std::io::Error::from(ArenaError::InvalidLayout { reason });
+        if capacity < MIN_ARENA_CAPACITY {
+            return Err(invalid(format!(
+                "capacidade de {} bytes não comporta uma alocação alinhada ({} bytes)",
+                capacity, MIN_ARENA_CAPACITY
+            )));
+        }
+        let aligned_capacity = capacity
+            .checked_next_multiple_of(AVX512_ALIGNMENT)
+            .ok_or_else(|| invalid(format!("capacidade de {} bytes excede usize ao alinhar", capacity)))?;
         let layout = Layout::from_size_align(aligned_capacity, AVX512_ALIGNMENT)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+            .map_err(|e| invalid(format!("capacidade de {} bytes: {}", aligned_capacity, e)))?;
 
+        // Zerada para que o padding entre alocações nunca seja memória não inicializada
+        // (o dump e a leitura de frames percorrem a região usada inteira)
         let base_ptr = unsafe {
-            let ptr = alloc(layout);
+            let ptr = alloc_zeroed(layout);
             if ptr.is_null() {
-                return Err(std::io::Error::new(std::io::ErrorKind::Other, "Falha na alocação"));
+                return Err(std::io::Error::other("Falha na alocação"));
             }
             NonNull::new_unchecked(ptr)
         };
@@ -46,23 +503,374 @@ impl ZeroCopyArena {
             capacity: aligned_capacity,
             offset: AtomicU64::new(0),
             layout,
+            live_buffers: AtomicU64::new(0),
+            #[cfg(debug_assertions)]
+            tags: parking_lot::Mutex::new(std::collections::BTreeMap::new()),
+            failed_allocations: AtomicU64::new(0),
+            created_at: Instant::now(),
+            on_exhausted: None,
+            exhausted_interval_nanos: 0,
+            last_exhausted_nanos: AtomicU64::new(NEVER_FIRED),
+            allocation_log_every: 0,
+            allocation_count: AtomicU64::new(0),
+            #[cfg(feature = "arena-size-classes")]
+            size_classes: std::array::from_fn(|_| AtomicU64::new(0)),
+            leaks: None,
+            placements: Mutex::new(std::collections::BTreeMap::new()),
+            placement_floor: AtomicU64::new(aligned_capacity as u64),
+        })
+    }
+
+    /// Loga em `debug` uma a cada `every` alocações bem-sucedidas, com tamanho
+    /// e offset, dando visibilidade do padrão de alocação sem o custo de logar
+    /// todas. O caminho não amostrado custa um `fetch_add` relaxado. 0 desliga
+    /// (padrão).
+    pub fn with_allocation_sampling(mut self, every: u64) -> Self {
+        self.allocation_log_every = every;
+        self
+    }
+
+    /// Registra o instante de alocação de cada `ZeroCopyBuffer` para que
+    /// `leaked_buffers` aponte os vivos há mais de `max_age` — consumidores
+    /// que esqueceram de descartar buffers e impedem a arena de rebobinar.
+    /// Custa um lock por alocação e liberação de buffer; desligado por padrão.
+    pub fn with_leak_detection(mut self, max_age: Duration, clock: Arc<dyn Clock>) -> Self {
+        self.leaks = Some(LeakTracker {
+            max_age_nanos: max_age.as_nanos() as u64,
+            clock,
+            outstanding: parking_lot::Mutex::new(HashMap::new()),
+        });
+        self
+    }
+
+    /// Buffers vivos há pelo menos o limite de `with_leak_detection`, em
+    /// ordem de offset. Vazio se a detecção estiver desligada.
+    pub fn leaked_buffers(&self) -> Vec<BufferInfo> {
+        let Some(leaks) = &self.leaks else {
+            return Vec::new();
+        };
+        let now = leaks.clock.now_nanos();
+        let mut leaked: Vec<_> = leaks
+            .outstanding
+            .lock()
+            .iter()
+            .map(|(&offset, &(len, tag, allocated_at))| BufferInfo {
+                offset,
+                len,
+                tag,
+                age: Duration::from_nanos(now.saturating_sub(allocated_at)),
+            })
+            .filter(|info| info.age.as_nanos() as u64 >= leaks.max_age_nanos)
+            .collect();
+        leaked.sort_unstable_by_key(|info| info.offset);
+        leaked
+    }
+
+    /// Loga em `warn` cada buffer de `leaked_buffers` e retorna quantos são.
+    pub fn check_leaks(&self) -> usize {
+        let leaked = self.leaked_buffers();
+        for info in &leaked {
+            warn!(
+                "Buffer possivelmente vazado: offset={} tamanho={} tag={} idade={:?}",
+                info.offset, info.len, info.tag, info.age
+            );
+        }
+        leaked.len()
+    }
+
+    /// Thread que roda `check_leaks` a cada `interval` enquanto a arena existir.
+    pub fn spawn_leak_watchdog(self: &Arc<Self>, interval: Duration) -> std::thread::JoinHandle<()> {
+        let arena = Arc::downgrade(self);
+        std::thread::spawn(move || loop {
+            std::thread::sleep(interval);
+            match arena.upgrade() {
+                Some(arena) => arena.check_leaks(),
+                None => return,
+            };
         })
     }
 
-    pub fn allocate(&self, size: usize) -> Result<NonNull<u8>, std::io::Error> {
-        let aligned_size = (size + AVX512_ALIGNMENT - 1) / AVX512_ALIGNMENT * AVX512_ALIGNMENT;
-        let current_offset = self.offset.fetch_add(aligned_size as u64, Ordering::Acquire) as usize;
+    /// Thread que, a cada `interval`, pede ao SO que traga de volta e mapeie
+    /// as páginas da arena inteira (`MADV_POPULATE_READ` no Linux, ou
+    /// `MADV_WILLNEED` onde ele não existe), para que páginas fora do working
+    /// set não voltem com page faults no caminho quente. O prefault é feito
+    /// pelo kernel, sem que a thread leia a memória (o que disputaria com as
+    /// escritas dos produtores): não toca o offset, contadores nem buffers
+    /// vivos, e roda com `nice` 19 cedendo a CPU a cada bloco de páginas. Fora
+    /// de unix, as passadas não fazem nada. Termina quando o `PageWarmer` é
+    /// descartado ou a arena deixa de existir.
+    pub fn spawn_page_warmer(self: &Arc<Self>, interval: Duration) -> std::io::Result<PageWarmer> {
+        const PAGE_SIZE: usize = 4096;
+        const PAGES_PER_YIELD: usize = 256;
+
+        let arena = Arc::downgrade(self);
+        let running = Arc::new(AtomicBool::new(true));
+        let sweeps = Arc::new(AtomicU64::new(0));
+        let (flag, count) = (Arc::clone(&running), Arc::clone(&sweeps));
+        let handle = std::thread::Builder::new().name("tensorwerk-page-warmer".to_string()).spawn(move || {
+            #[cfg(target_os = "linux")]
+            unsafe {
+                // Com `who` = 0 o Linux ajusta só a thread chamadora
+                libc::setpriority(libc::PRIO_PROCESS, 0, 19);
+            }
+            while flag.load(Ordering::Acquire) {
+                let Some(arena) = arena.upgrade() else { return };
+                let base = arena.base_ptr.as_ptr() as usize;
+                let (start, end) = (base / PAGE_SIZE * PAGE_SIZE, base + arena.capacity);
+                for chunk in (start..end).step_by(PAGE_SIZE * PAGES_PER_YIELD) {
+                    prefault_pages(chunk, (chunk + PAGE_SIZE * PAGES_PER_YIELD).min(end) - chunk);
+                    if !flag.load(Ordering::Acquire) {
+                        return;
+                    }
+                    std::thread::yield_now();
+                }
+                drop(arena);
+                count.fetch_add(1, Ordering::Relaxed);
+                std::thread::park_timeout(interval);
+            }
+        })?;
+        Ok(PageWarmer { running, sweeps, handle: Some(handle) })
+    }
+
+    /// Registra um callback chamado quando `allocate` falha por esgotamento
+    /// (ex.: para escalar capacidade ou alertar). O callback dispara no máximo
+    /// uma vez por `min_interval`, então uma arena cheia por longos períodos
+    /// não inunda o operador. Desligado por padrão.
+    pub fn with_on_exhausted<F>(mut self, min_interval: Duration, callback: F) -> Self
+    where
+        F: Fn(&ArenaStats) + Send + Sync + 'static,
+    {
+        self.on_exhausted = Some(Box::new(callback));
+        self.exhausted_interval_nanos = min_interval.as_nanos() as u64;
+        self
+    }
+
+    /// Como `allocate`, marcando a região com `tag` (ex.: id do produtor ou do
+    /// caminho de código). Em builds de debug o tag fica registrado na tabela
+    /// consultada por `tagged_regions`; em release não há custo extra.
+    pub fn allocate_tagged(&self, size: usize, tag: u32) -> Result<NonNull<u8>, ArenaError> {
+        let ptr = self.allocate(size)?;
+        #[cfg(debug_assertions)]
+        if let Some(offset) = self.offset_of(ptr) {
+            self.tags.lock().insert(offset, tag);
+        }
+        #[cfg(not(debug_assertions))]
+        let _ = tag;
+        Ok(ptr)
+    }
+
+    /// Reserva `[offset, offset + size)` numa posição fixa, para interoperar
+    /// por memória compartilhada com processos que precisam concordar com os
+    /// offsets dos slots. `offset` deve ser múltiplo de `AVX512_ALIGNMENT`.
+    /// Falha com `ArenaError::Overlap` se a região cruzar outra reserva ou a
+    /// parte já usada pelo bump, que por sua vez passa a parar na reserva de
+    /// menor offset: reserve no fim da arena e deixe o começo para o bump.
+    /// A reserva sobrevive a `try_reclaim` e só é desfeita por `release_at`.
+    pub fn allocate_at(&self, offset: usize, size: usize) -> Result<NonNull<u8>, ArenaError> {
+        if !offset.is_multiple_of(AVX512_ALIGNMENT) {
+            return Err(ArenaError::InvalidLayout {
+                reason: format!("offset {} não é múltiplo de {}", offset, AVX512_ALIGNMENT),
+            });
+        }
+        if size == 0 {
+            return Ok(zero_sized_ptr());
+        }
+        let aligned_size = size.checked_next_multiple_of(AVX512_ALIGNMENT).unwrap_or(usize::MAX);
+        let end = offset.checked_add(aligned_size).filter(|&end| end <= self.capacity);
+        let Some(end) = end else {
+            return Err(ArenaError::TooLarge { requested: size, capacity: self.capacity.saturating_sub(offset) });
+        };
+
+        let mut placements = self.placements.lock();
+        let overlap = ArenaError::Overlap { offset, size };
+        if placements.range(..end).next_back().is_some_and(|(&start, &len)| start + len > offset) {
+            return Err(overlap);
+        }
+        // Baixa o teto antes de conferir o offset do bump (par do recheck em `bump`)
+        self.placement_floor.fetch_min(offset as u64, Ordering::SeqCst);
+        if self.offset.load(Ordering::SeqCst) as usize > offset {
+            self.restore_placement_floor(&placements);
+            return Err(overlap);
+        }
+        placements.insert(offset, aligned_size);
+        // SAFETY: `offset + aligned_size <= capacity`
+        Ok(unsafe { NonNull::new_unchecked(self.base_ptr.as_ptr().add(offset)) })
+    }
+
+    /// Desfaz a reserva de `allocate_at` em `offset`; ponteiros para ela deixam
+    /// de ser válidos. Retorna `false` se não havia reserva ali.
+    pub fn release_at(&self, offset: usize) -> bool {
+        let mut placements = self.placements.lock();
+        let released = placements.remove(&offset).is_some();
+        if released {
+            self.restore_placement_floor(&placements);
+        }
+        released
+    }
+
+    fn restore_placement_floor(&self, placements: &std::collections::BTreeMap<usize, usize>) {
+        let floor = placements.keys().next().copied().unwrap_or(self.capacity);
+        self.placement_floor.store(floor as u64, Ordering::SeqCst);
+    }
+
+    /// Regiões marcadas cujos buffers ainda estão vivos, como (offset, tag), em
+    /// ordem de offset. Útil para achar a origem de buffers retidos (vazamentos).
+    #[cfg(debug_assertions)]
+    pub fn tagged_regions(&self) -> Vec<(usize, u32)> {
+        self.tags.lock().iter().map(|(&offset, &tag)| (offset, tag)).collect()
+    }
+
+    /// Offset de `ptr` na região; `None` para ponteiros de fora dela (alocações
+    /// de tamanho zero e o ponteiro pendente de alocações que falharam).
+    fn offset_of(&self, ptr: NonNull<u8>) -> Option<usize> {
+        (ptr.as_ptr() as usize)
+            .checked_sub(self.base_ptr.as_ptr() as usize)
+            .filter(|&offset| offset < self.capacity)
+    }
+
+    pub fn allocate(&self, size: usize) -> Result<NonNull<u8>, ArenaError> {
+        self.bump(size)
+            .inspect(|ptr| self.sample_allocation(*ptr, size))
+            .inspect_err(|e| {
+                self.failed_allocations.fetch_add(1, Ordering::Relaxed);
+                // Pedido maior que a arena não indica arena cheia
+                if matches!(e, ArenaError::Exhausted { .. }) {
+                    self.notify_exhausted();
+                }
+            })
+    }
+
+    #[inline]
+    fn sample_allocation(&self, ptr: NonNull<u8>, size: usize) {
+        #[cfg(feature = "arena-size-classes")]
+        self.size_classes[size_class(size)].fetch_add(1, Ordering::Relaxed);
+        if self.allocation_log_every == 0 {
+            return;
+        }
+        if self.allocation_count.fetch_add(1, Ordering::Relaxed).is_multiple_of(self.allocation_log_every) {
+            debug!("Alocação na arena: {} bytes no offset {:?} (usado: {} bytes)", size, self.offset_of(ptr), self.used());
+        }
+    }
+
+    /// Rebobina a arena para o início se nenhum `ZeroCopyBuffer` estiver vivo.
+    /// Ponteiros obtidos diretamente de `allocate` deixam de ser válidos após
+    /// uma recuperação bem-sucedida. Retorna `true` se a arena foi rebobinada.
+    pub fn try_reclaim(&self) -> bool {
+        let reclaimed = self.reclaim();
+        #[cfg(debug_assertions)]
+        if reclaimed {
+            self.tags.lock().clear();
+        }
+        reclaimed
+    }
+
+    pub fn live_buffers(&self) -> u64 { self.live_buffers.load(Ordering::Relaxed) }
+
+    /// Grava a região usada (`0..offset`) precedida de um cabeçalho de metadados
+    /// (ver `ArenaDump`), para análise post-mortem. Não sincroniza com escritores
+    /// concorrentes: buffers sendo preenchidos durante o dump podem sair parciais.
+    pub fn dump(&self, writer: &mut dyn std::io::Write) -> Result<(), std::io::Error> {
+        let used = self.used().min(self.capacity);
+        writer.write_all(&ArenaDump::MAGIC.to_le_bytes())?;
+        writer.write_all(&(self.capacity as u64).to_le_bytes())?;
+        writer.write_all(&(used as u64).to_le_bytes())?;
+        writer.write_all(&(AVX512_ALIGNMENT as u64).to_le_bytes())?;
+        let region = unsafe { std::slice::from_raw_parts(self.base_ptr.as_ptr(), used) };
+        writer.write_all(region)
+    }
 
-        if current_offset + aligned_size > self.capacity {
-            self.offset.fetch_sub(aligned_size as u64, Ordering::Release);
-            return Err(std::io::Error::new(std::io::ErrorKind::Other, "Arena esgotada"));
+    #[cold]
+    fn notify_exhausted(&self) {
+        let Some(callback) = &self.on_exhausted else { return };
+
+        let now = self.created_at.elapsed().as_nanos() as u64;
+        let last = self.last_exhausted_nanos.load(Ordering::Relaxed);
+        if last != NEVER_FIRED && now.saturating_sub(last) < self.exhausted_interval_nanos {
+            return;
         }
 
-        Ok(unsafe { NonNull::new_unchecked(self.base_ptr.as_ptr().add(current_offset)) })
+        if self
+            .last_exhausted_nanos
+            .compare_exchange(last, now, Ordering::AcqRel, Ordering::Relaxed)
+            .is_ok()
+        {
+            callback(&self.stats());
+        }
     }
 
     pub fn capacity(&self) -> usize { self.capacity }
     pub fn used(&self) -> usize { self.offset.load(Ordering::Relaxed) as usize }
+    pub fn failed_allocations(&self) -> u64 { self.failed_allocations.load(Ordering::Relaxed) }
+
+    pub fn stats(&self) -> ArenaStats {
+        ArenaStats {
+            capacity: self.capacity,
+            used: self.used(),
+            failed_allocations: self.failed_allocations(),
+            #[cfg(feature = "arena-size-classes")]
+            size_classes: std::array::from_fn(|class| self.size_classes[class].load(Ordering::Relaxed)),
+        }
+    }
+}
+
+// SAFETY: `base_ptr` vem de `alloc_zeroed` com `capacity` bytes alinhados a
+// `AVX512_ALIGNMENT` e só é liberado no `Drop`; `placement_floor` nunca
+// passa de `capacity`, e `allocate_at` só entrega faixas acima dele que não
+// cruzam a parte já usada pelo bump.
+unsafe impl BumpAllocator for ZeroCopyArena {
+    #[inline]
+    fn region_base(&self) -> *mut u8 { self.base_ptr.as_ptr() }
+    #[inline]
+    fn region_capacity(&self) -> usize { self.capacity }
+    #[inline]
+    fn region_limit(&self) -> usize { self.placement_floor.load(Ordering::SeqCst) as usize }
+    #[inline]
+    fn region_offset(&self) -> &AtomicU64 { &self.offset }
+    #[inline]
+    fn region_live_buffers(&self) -> &AtomicU64 { &self.live_buffers }
+}
+
+// SAFETY: `allocate` e `allocate_tagged` passam por `BumpAllocator::bump`, e
+// `reset` (`try_reclaim`) só rebobina sem buffers vivos.
+unsafe impl Arena for ZeroCopyArena {
+    fn allocate(&self, size: usize) -> Result<NonNull<u8>, ArenaError> { ZeroCopyArena::allocate(self, size) }
+    fn used(&self) -> usize { ZeroCopyArena::used(self) }
+    fn capacity(&self) -> usize { ZeroCopyArena::capacity(self) }
+    fn reset(&self) -> bool { self.try_reclaim() }
+    fn in_use(&self) -> usize { if self.live_buffers() == 0 { 0 } else { self.used() } }
+
+    fn buffer_acquired(&self) {
+        self.live_buffers.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn buffer_released(&self, ptr: NonNull<u8>, tag: u32) {
+        // Ponteiros de fora da região (alocação que falhou ou de tamanho zero)
+        // nunca foram registrados
+        if let Some(offset) = self.offset_of(ptr) {
+            #[cfg(debug_assertions)]
+            if tag != 0 {
+                self.tags.lock().remove(&offset);
+            }
+            if let Some(leaks) = &self.leaks {
+                leaks.outstanding.lock().remove(&offset);
+            }
+        }
+        #[cfg(not(debug_assertions))]
+        let _ = tag;
+        self.live_buffers.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    fn buffer_allocated(&self, ptr: NonNull<u8>, len: usize, tag: u32) {
+        if let (Some(leaks), Some(offset)) = (&self.leaks, self.offset_of(ptr)) {
+            leaks.outstanding.lock().insert(offset, (len, tag, leaks.clock.now_nanos()));
+        }
+    }
+
+    fn allocate_tagged(&self, size: usize, tag: u32) -> Result<NonNull<u8>, ArenaError> {
+        ZeroCopyArena::allocate_tagged(self, size, tag)
+    }
+
+    fn failed_allocations(&self) -> u64 { ZeroCopyArena::failed_allocations(self) }
 }
 
 impl Drop for ZeroCopyArena {
@@ -72,212 +880,2930 @@ impl Drop for ZeroCopyArena {
     }
 }
 
-pub struct ZeroCopyBuffer {
+pub struct ZeroCopyBuffer<A: Arena = ZeroCopyArena> {
+    ptr: NonNull<u8>,
+    len: usize,
+    tag: u32,
+    checksum: Option<CachedChecksum>,
+    /// Vista publicada por `share`, enquanto algum `Bytes` dela existir.
+    shared: Option<Weak<SharedRegion<A>>>,
+    /// Cópia no heap feita por `as_mut_slice` sobre um buffer compartilhado;
+    /// `ptr` aponta para ela.
+    detached: Option<Box<[u8]>>,
+    _arena: Arc<A>,
+}
+
+unsafe impl<A: Arena> Send for ZeroCopyBuffer<A> {}
+unsafe impl<A: Arena> Sync for ZeroCopyBuffer<A> {}
+
+/// Região de um `ZeroCopyBuffer` vista pelo broadcast (ver
+/// `ZeroCopyBuffer::share`). Conta como buffer vivo: a arena não rebobina
+/// enquanto ela existir.
+struct SharedRegion<A: Arena> {
     ptr: NonNull<u8>,
     len: usize,
-    _arena: Arc<ZeroCopyArena>,
+    arena: Arc<A>,
+}
+
+// SAFETY: a região só é lida enquanto compartilhada; o dono do buffer passa a
+// escrever numa cópia (`ZeroCopyBuffer::as_mut_slice`)
+unsafe impl<A: Arena> Send for SharedRegion<A> {}
+unsafe impl<A: Arena> Sync for SharedRegion<A> {}
+
+impl<A: Arena> Drop for SharedRegion<A> {
+    fn drop(&mut self) {
+        // Ponteiro fora da região: só devolve a contagem de buffers vivos
+        self.arena.buffer_released(NonNull::dangling(), 0);
+    }
 }
 
-unsafe impl Send for ZeroCopyBuffer {}
-unsafe impl Sync for ZeroCopyBuffer {}
+struct SharedView<A: Arena>(Arc<SharedRegion<A>>);
+
+impl<A: Arena> AsRef<[u8]> for SharedView<A> {
+    fn as_ref(&self) -> &[u8] { unsafe { std::slice::from_raw_parts(self.0.ptr.as_ptr(), self.0.len) } }
+}
+
+impl<A: Arena> ZeroCopyBuffer<A> {
+    pub fn new(len: usize, arena: Arc<A>) -> Result<Self, ArenaError> {
+        Self::new_tagged(len, arena, 0)
+    }
 
-impl ZeroCopyBuffer {
-    pub fn new(len: usize, arena: Arc<ZeroCopyArena>) -> Result<Self, std::io::Error> {
-        let ptr = arena.allocate(len)?;
-        Ok(Self { ptr, len, _arena: arena })
+    /// Aloca um buffer marcado com `tag` (0 = sem tag), para rastrear sua origem.
+    pub fn new_tagged(len: usize, arena: Arc<A>, tag: u32) -> Result<Self, ArenaError> {
+        // Contado antes de alocar para que `reset` nunca rebobine sob um buffer em criação
+        arena.buffer_acquired();
+        let allocated = if tag == 0 { arena.allocate(len) } else { arena.allocate_tagged(len, tag) };
+        match allocated {
+            Ok(ptr) => {
+                arena.buffer_allocated(ptr, len, tag);
+                Ok(Self { ptr, len, tag, checksum: None, shared: None, detached: None, _arena: arena })
+            }
+            Err(e) => {
+                arena.buffer_released(NonNull::dangling(), 0);
+                Err(e)
+            }
+        }
     }
 
     pub fn as_slice(&self) -> &[u8] {
         unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
     }
 
+    /// Acesso para escrita; descarta o CRC em cache, que deixaria de valer.
+    /// Se o buffer ainda é visto pelo broadcast, o conteúdo é antes copiado
+    /// para o heap e só a cópia é alterada.
     pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        self.checksum = None;
+        if self.shared.take().is_some_and(|region| region.strong_count() > 0) {
+            self.detach();
+        }
         unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
     }
 
+    /// Vista `Bytes` dos mesmos bytes, sem cópia nem nova alocação na arena.
+    pub(crate) fn share(&mut self) -> Bytes {
+        self._arena.buffer_acquired();
+        let region = Arc::new(SharedRegion { ptr: self.ptr, len: self.len, arena: Arc::clone(&self._arena) });
+        self.shared = Some(Arc::downgrade(&region));
+        Bytes::from_owner(SharedView(region))
+    }
+
+    #[cold]
+    fn detach(&mut self) {
+        let mut copy: Box<[u8]> = self.as_slice().into();
+        let region = std::mem::replace(&mut self.ptr, NonNull::from(&mut copy[..]).cast());
+        self.detached = Some(copy);
+        // A região fica com a vista compartilhada; o buffer segue contado como
+        // vivo até o `Drop`, que não a reconhece mais
+        self._arena.buffer_released(region, self.tag);
+        self._arena.buffer_acquired();
+    }
+
     pub fn len(&self) -> usize { self.len }
     pub fn is_empty(&self) -> bool { self.len == 0 }
-}
+    pub fn tag(&self) -> u32 { self.tag }
 
-#[derive(Copy, Clone, Debug)]
-#[repr(C, packed)]
-pub struct MessageHeader {
-    pub magic: u32,
-    pub msg_type: u8,
-    pub version: u8,
-    pub priority: u8,
-    pub flags: u8,
-    pub timestamp: u64,
-    pub payload_size: u32,
-    pub checksum: u32,
-}
+    /// CRC do payload calculado pelo ingestor ao montar o frame (em
+    /// `process_frame` e na conversão de ordem de bytes), se houver e o buffer
+    /// não tiver sido alterado desde então. `drain_validated` o usa no lugar
+    /// de recalcular.
+    pub fn cached_checksum(&self) -> Option<CachedChecksum> { self.checksum }
 
-impl MessageHeader {
-    pub const MAGIC: u32 = 0x4D524B54;
+    pub(crate) fn cache_checksum(&mut self, checksum: CachedChecksum) { self.checksum = Some(checksum); }
 
-    pub fn is_valid(&self) -> bool {
-        self.magic == Self::MAGIC && self.payload_size > 0 && self.payload_size <= 10_000_000
+    /// Converte o buffer em `Bytes` sem cópia.
+    ///
+    /// O `Bytes` resultante assume a posse do buffer e, com ele, do `Arc` da arena:
+    /// a arena permanece viva até que o último clone ou slice seja descartado.
+    pub fn into_bytes(self) -> Bytes {
+        Bytes::from_owner(self)
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-#[repr(C, packed)]
-pub struct Trade {
-    pub symbol: [u8; 8],
-    pub price: i64,
-    pub quantity: i64,
-    pub timestamp: u64,
-    pub side: u8,
-    pub trade_id: u64,
-    _padding: [u8; 7],
+impl<A: Arena> Drop for ZeroCopyBuffer<A> {
+    fn drop(&mut self) {
+        self._arena.buffer_released(self.ptr, self.tag);
+    }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-#[repr(C, packed)]
-pub struct Quote {
-    pub symbol: [u8; 8],
-    pub bid_price: i64,
-    pub bid_quantity: i64,
-    pub ask_price: i64,
-    pub ask_quantity: i64,
-    pub timestamp: u64,
-    _padding: [u8; 8],
+impl<A: Arena> AsRef<[u8]> for ZeroCopyBuffer<A> {
+    fn as_ref(&self) -> &[u8] { self.as_slice() }
 }
 
-pub struct MarketDataIngestor {
-    arena: Arc<ZeroCopyArena>,
-    tx: Sender<ZeroCopyBuffer>,
-    stats: Mutex<IngestionStats>,
+impl<A: Arena> From<ZeroCopyBuffer<A>> for Bytes {
+    fn from(buffer: ZeroCopyBuffer<A>) -> Self { buffer.into_bytes() }
 }
 
-#[derive(Debug, Default)]
-struct IngestionStats {
-    messages_received: u64,
-    bytes_received: u64,
-    parse_errors: u64,
-    last_message_time: Option<Instant>,
+/// Structs do protocolo que podem ser lidas direto de bytes da rede.
+///
+/// # Safety
+/// Só para tipos `#[repr(C, packed)]` compostos de inteiros e arrays de
+/// inteiros: qualquer sequência de `size_of::<Self>()` bytes é um valor válido.
+pub unsafe trait WireStruct: Copy {
+    /// Inverte a ordem de bytes de cada campo inteiro (arrays de bytes ficam).
+    fn swap_bytes(self) -> Self;
 }
 
-#[derive(Debug, Serialize)]
-pub struct IngestionStatsSnapshot {
-    pub messages_received: u64,
-    pub bytes_received: u64,
-    pub parse_errors: u64,
-    pub arena_used_mb: usize,
-    pub arena_capacity_mb: usize,
-    pub messages_per_second: f64,
+unsafe impl WireStruct for MessageHeader {
+    fn swap_bytes(self) -> Self {
+        Self {
+            magic: self.magic.swap_bytes(),
+            timestamp: self.timestamp.swap_bytes(),
+            payload_size: self.payload_size.swap_bytes(),
+            checksum: self.checksum.swap_bytes(),
+            ..self
+        }
+    }
 }
 
-impl MarketDataIngestor {
-    pub fn new(arena_capacity: usize, channel_size: usize) -> Self {
-        let arena = Arc::new(ZeroCopyArena::new(arena_capacity).unwrap());
-        let (tx, _) = bounded(channel_size);
-
-        info!("Ingestor criado: arena={} MB, canal={}", arena_capacity / (1024 * 1024), channel_size);
-
+unsafe impl WireStruct for Trade {
+    fn swap_bytes(self) -> Self {
         Self {
-            arena,
-            tx,
-            stats: Mutex::new(IngestionStats::default()),
+            price: self.price.swap_bytes(),
+            quantity: self.quantity.swap_bytes(),
+            timestamp: self.timestamp.swap_bytes(),
+            trade_id: self.trade_id.swap_bytes(),
+            ..self
         }
     }
+}
 
-    pub fn process_raw_data(&self, raw_data: &mut BytesMut) -> Result<(), std::io::Error> {
-        let start = Instant::now();
-
-        if raw_data.len() < std::mem::size_of::<MessageHeader>() {
-            return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "Dados muito pequenos"));
+unsafe impl WireStruct for Quote {
+    fn swap_bytes(self) -> Self {
+        Self {
+            bid_price: self.bid_price.swap_bytes(),
+            bid_quantity: self.bid_quantity.swap_bytes(),
+            ask_price: self.ask_price.swap_bytes(),
+            ask_quantity: self.ask_quantity.swap_bytes(),
+            timestamp: self.timestamp.swap_bytes(),
+            ..self
         }
+    }
+}
 
-        let header = unsafe { *(raw_data.as_ptr() as *const MessageHeader) };
-
-        if !header.is_valid() {
-            let mut stats = self.stats.lock();
-            stats.parse_errors += 1;
-            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Header inválido"));
+unsafe impl WireStruct for BookSnapshotHeader {
+    fn swap_bytes(self) -> Self {
+        Self {
+            timestamp: self.timestamp.swap_bytes(),
+            bid_count: self.bid_count.swap_bytes(),
+            ask_count: self.ask_count.swap_bytes(),
+            ..self
         }
+    }
+}
+
+unsafe impl WireStruct for BookLevel {
+    fn swap_bytes(self) -> Self {
+        Self { price: self.price.swap_bytes(), quantity: self.quantity.swap_bytes() }
+    }
+}
+
+/// Lê um `T` dos primeiros `size_of::<T>()` bytes de `bytes`, em qualquer
+/// alinhamento; bytes excedentes são ignorados. Único ponto de reinterpretação
+/// de bytes da camada de parsing.
+#[inline]
+pub fn read_struct<T: WireStruct>(bytes: &[u8]) -> Result<T, ValidationError> {
+    if bytes.len() < std::mem::size_of::<T>() {
+        return Err(ValidationError::CorruptedFormat);
+    }
+    // SAFETY: o tamanho foi verificado acima, `read_unaligned` dispensa
+    // alinhamento e `WireStruct` garante que todo padrão de bits é válido.
+    Ok(unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const T) })
+}
+
+/// Símbolo (8 bytes iniciais do payload) dos tipos que o carregam: trade,
+/// quote e book snapshot.
+pub(crate) fn message_symbol(msg_type: u8, payload: &[u8]) -> Option<[u8; 8]> {
+    if ![Trade::MSG_TYPE, Quote::MSG_TYPE, BookSnapshot::MSG_TYPE].contains(&msg_type) {
+        return None;
+    }
+    payload.first_chunk::<8>().copied()
+}
+
+/// Como `read_struct`, para bytes gravados na ordem `endian`.
+#[inline]
+pub fn read_struct_as<T: WireStruct>(bytes: &[u8], endian: Endian) -> Result<T, ValidationError> {
+    let value = read_struct::<T>(bytes)?;
+    Ok(if endian.is_native() { value } else { value.swap_bytes() })
+}
+
+/// Bytes de `value` na representação de memória (sem padding, por ser packed).
+pub(crate) fn struct_bytes<T: WireStruct>(value: &T) -> &[u8] {
+    // SAFETY: `WireStruct` é packed e só contém inteiros: todos os
+    // `size_of::<T>()` bytes estão inicializados.
+    unsafe { std::slice::from_raw_parts((value as *const T).cast::<u8>(), std::mem::size_of::<T>()) }
+}
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C, packed)]
+pub struct MessageHeader {
+    pub magic: u32,
+    pub msg_type: u8,
+    pub version: u8,
+    pub priority: u8,
+    pub flags: u8,
+    pub timestamp: u64,
+    pub payload_size: u32,
+    pub checksum: u32,
+}
+
+impl MessageHeader {
+    pub const MAGIC: u32 = 0x4D524B54;
+    pub const SIZE: usize = std::mem::size_of::<MessageHeader>();
+
+    pub const MAX_PAYLOAD_SIZE: u32 = 10_000_000;
+
+    /// A partir desta versão o header é seguido de um checksum próprio
+    /// (`u32` LE, calculado sobre os `SIZE` bytes do header) antes do payload;
+    /// `checksum` continua cobrindo só o payload. Versões anteriores não têm
+    /// o campo e o payload começa logo após o header.
+    pub const VERSION_HEADER_CHECKSUM: u8 = 2;
+    pub const HEADER_CHECKSUM_SIZE: usize = 4;
+
+    pub fn is_valid(&self) -> bool {
+        self.magic == Self::MAGIC && self.payload_size > 0 && self.payload_size <= Self::MAX_PAYLOAD_SIZE
+    }
+
+    /// Diagnóstico de um header que falhou em `is_valid`. Um magic que difere
+    /// do esperado em no máximo um byte é tratado como corrupção
+    /// (`CorruptedFormat`); um magic totalmente diferente indica outro
+    /// protocolo ou desalinhamento de stream (`UnknownProtocol`). Tamanho de
+    /// payload fora dos limites também é `CorruptedFormat`.
+    pub fn check_integrity(&self) -> Result<(), ValidationError> {
+        let magic = self.magic;
+        if magic != Self::MAGIC {
+            let differing_bytes = (magic ^ Self::MAGIC).to_le_bytes().iter().filter(|&&b| b != 0).count();
+            return Err(if differing_bytes <= 1 {
+                ValidationError::CorruptedFormat
+            } else {
+                ValidationError::UnknownProtocol { magic }
+            });
+        }
+        if self.payload_size == 0 || self.payload_size > Self::MAX_PAYLOAD_SIZE {
+            return Err(ValidationError::CorruptedFormat);
+        }
+        Ok(())
+    }
+
+    pub fn builder() -> MessageHeaderBuilder {
+        MessageHeaderBuilder::default()
+    }
+
+    #[inline]
+    pub fn has_header_checksum(&self) -> bool { self.version >= Self::VERSION_HEADER_CHECKSUM }
+
+    /// Início do payload no frame: depois do header e, em v2, do checksum do header.
+    #[inline]
+    pub fn payload_offset(&self) -> usize {
+        if self.has_header_checksum() { Self::SIZE + Self::HEADER_CHECKSUM_SIZE } else { Self::SIZE }
+    }
+
+    /// Tamanho do frame completo descrito por este header.
+    #[inline]
+    pub fn frame_size(&self) -> usize { self.payload_offset() + self.payload_size as usize }
+
+    /// Payload de `frame` (que começa por este header), se estiver completo.
+    pub fn payload<'a>(&self, frame: &'a [u8]) -> Option<&'a [u8]> {
+        frame.get(self.payload_offset()..self.frame_size())
+    }
+
+    /// Checksum do header gravado em `frame`; `None` antes da v2 ou se truncado.
+    pub fn stored_header_checksum(&self, frame: &[u8]) -> Option<u32> {
+        if !self.has_header_checksum() {
+            return None;
+        }
+        let bytes = frame.get(Self::SIZE..Self::SIZE + Self::HEADER_CHECKSUM_SIZE)?;
+        Some(u32::from_le_bytes(bytes.try_into().ok()?))
+    }
+
+    /// Bytes de fio entre o header e o payload (vazio antes da v2).
+    pub(crate) fn header_extension(&self, checksum: &ChecksumValidator) -> Option<[u8; Self::HEADER_CHECKSUM_SIZE]> {
+        self.has_header_checksum().then(|| checksum.calculate(&self.to_bytes()).to_le_bytes())
+    }
+
+    /// Serializa o header no layout de fio (little-endian, sem padding).
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut out = [0u8; Self::SIZE];
+        out[0..4].copy_from_slice(&{ self.magic }.to_le_bytes());
+        out[4] = self.msg_type;
+        out[5] = self.version;
+        out[6] = self.priority;
+        out[7] = self.flags;
+        out[8..16].copy_from_slice(&{ self.timestamp }.to_le_bytes());
+        out[16..20].copy_from_slice(&{ self.payload_size }.to_le_bytes());
+        out[20..24].copy_from_slice(&{ self.checksum }.to_le_bytes());
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes = bytes.get(..Self::SIZE)?;
+        Some(Self {
+            magic: u32::from_le_bytes(bytes[0..4].try_into().ok()?),
+            msg_type: bytes[4],
+            version: bytes[5],
+            priority: bytes[6],
+            flags: bytes[7],
+            timestamp: u64::from_le_bytes(bytes[8..16].try_into().ok()?),
+            payload_size: u32::from_le_bytes(bytes[16..20].try_into().ok()?),
+            checksum: u32::from_le_bytes(bytes[20..24].try_into().ok()?),
+        })
+    }
+}
+
+/// Monta headers corretamente enquadrados: `payload` preenche `payload_size` e
+/// calcula o checksum, e `frame` produz header + payload prontos para o ingestor
+/// (com o checksum do header entre os dois, se `version` >= 2).
+#[derive(Debug, Clone, Copy)]
+pub struct MessageHeaderBuilder {
+    header: MessageHeader,
+}
+
+impl Default for MessageHeaderBuilder {
+    fn default() -> Self {
+        Self {
+            header: MessageHeader {
+                magic: MessageHeader::MAGIC,
+                msg_type: 0,
+                version: 1,
+                priority: 0,
+                flags: 0,
+                timestamp: 0,
+                payload_size: 0,
+                checksum: 0,
+            },
+        }
+    }
+}
+
+impl MessageHeaderBuilder {
+    pub fn magic(mut self, magic: u32) -> Self { self.header.magic = magic; self }
+    pub fn msg_type(mut self, msg_type: u8) -> Self { self.header.msg_type = msg_type; self }
+    pub fn version(mut self, version: u8) -> Self { self.header.version = version; self }
+    pub fn priority(mut self, priority: u8) -> Self { self.header.priority = priority; self }
+    pub fn flags(mut self, flags: u8) -> Self { self.header.flags = flags; self }
+    pub fn timestamp(mut self, timestamp: u64) -> Self { self.header.timestamp = timestamp; self }
+    pub fn checksum(mut self, checksum: u32) -> Self { self.header.checksum = checksum; self }
+
+    pub fn payload(mut self, payload: &[u8]) -> Self {
+        self.header.payload_size = payload.len() as u32;
+        self.header.checksum = crate::validation::integrity::ChecksumValidator::crc32().calculate(payload);
+        self
+    }
+
+    pub fn build(self) -> MessageHeader { self.header }
+
+    /// Header (com tamanho e checksum do payload) seguido do payload.
+    pub fn frame(self, payload: &[u8]) -> BytesMut {
+        let header = self.payload(payload).build();
+        let mut frame = BytesMut::with_capacity(header.frame_size());
+        frame.extend_from_slice(&header.to_bytes());
+        if let Some(extension) = header.header_extension(crate::validation::integrity::ChecksumValidator::crc32()) {
+            frame.extend_from_slice(&extension);
+        }
+        frame.extend_from_slice(payload);
+        frame
+    }
+}
+
+/// O `_padding` de `Trade` e `Quote` vai para o fio e entra no checksum do
+/// payload, então deve ser zero: os construtores do lado Rust (`new` e a
+/// desserialização) sempre o zeram, e produtores em C precisam fazer o mesmo
+/// (ver `CompositeValidatorBuilder::strict_padding`).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[repr(C, packed)]
+pub struct Trade {
+    pub symbol: [u8; 8],
+    pub price: i64,
+    pub quantity: i64,
+    pub timestamp: u64,
+    pub side: u8,
+    pub trade_id: u64,
+    #[serde(skip)]
+    _padding: [u8; 7],
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[repr(C, packed)]
+pub struct Quote {
+    pub symbol: [u8; 8],
+    pub bid_price: i64,
+    pub bid_quantity: i64,
+    pub ask_price: i64,
+    pub ask_quantity: i64,
+    pub timestamp: u64,
+    #[serde(skip)]
+    _padding: [u8; 8],
+}
+
+/// Lê um campo de struct `packed` sem criar referência desalinhada.
+macro_rules! packed_getters {
+    ($($name:ident: $ty:ty),* $(,)?) => {
+        $(
+            #[inline]
+            pub fn $name(&self) -> $ty {
+                self.$name
+            }
+        )*
+    };
+}
+
+impl Trade {
+    pub const MSG_TYPE: u8 = 0;
+    pub const SIZE: usize = std::mem::size_of::<Trade>();
+    /// Valores de `side` com direção conhecida (os que o gerador sintético
+    /// produz); qualquer outro valor é lado desconhecido.
+    pub const SIDE_BUY: u8 = 0;
+    pub const SIDE_SELL: u8 = 1;
+
+    packed_getters! {
+        symbol: [u8; 8],
+        price: i64,
+        quantity: i64,
+        timestamp: u64,
+        side: u8,
+        trade_id: u64,
+    }
+
+    pub fn new(symbol: [u8; 8], price: i64, quantity: i64, timestamp: u64, side: u8, trade_id: u64) -> Self {
+        Self { symbol, price, quantity, timestamp, side, trade_id, _padding: [0; 7] }
+    }
+
+    /// Se os bytes de padding chegaram zerados (ver `Trade`).
+    pub fn has_zero_padding(&self) -> bool { let padding = self._padding; padding == [0; 7] }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut out = [0u8; Self::SIZE];
+        out[0..8].copy_from_slice(&self.symbol());
+        out[8..16].copy_from_slice(&self.price().to_le_bytes());
+        out[16..24].copy_from_slice(&self.quantity().to_le_bytes());
+        out[24..32].copy_from_slice(&self.timestamp().to_le_bytes());
+        out[32] = self.side();
+        out[33..41].copy_from_slice(&self.trade_id().to_le_bytes());
+        out[41..48].copy_from_slice(&self._padding);
+        out
+    }
+
+    /// Frame completo (header com checksum calculado + payload).
+    pub fn to_frame(&self) -> BytesMut {
+        MessageHeader::builder()
+            .msg_type(Self::MSG_TYPE)
+            .timestamp(self.timestamp())
+            .frame(&self.to_bytes())
+    }
+}
+
+impl Quote {
+    pub const MSG_TYPE: u8 = 1;
+    pub const SIZE: usize = std::mem::size_of::<Quote>();
+
+    packed_getters! {
+        symbol: [u8; 8],
+        bid_price: i64,
+        bid_quantity: i64,
+        ask_price: i64,
+        ask_quantity: i64,
+        timestamp: u64,
+    }
+
+    pub fn new(
+        symbol: [u8; 8],
+        bid_price: i64,
+        bid_quantity: i64,
+        ask_price: i64,
+        ask_quantity: i64,
+        timestamp: u64,
+    ) -> Self {
+        Self { symbol, bid_price, bid_quantity, ask_price, ask_quantity, timestamp, _padding: [0; 8] }
+    }
+
+    /// Se os bytes de padding chegaram zerados (ver `Trade`).
+    pub fn has_zero_padding(&self) -> bool { let padding = self._padding; padding == [0; 8] }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut out = [0u8; Self::SIZE];
+        out[0..8].copy_from_slice(&self.symbol());
+        out[8..16].copy_from_slice(&self.bid_price().to_le_bytes());
+        out[16..24].copy_from_slice(&self.bid_quantity().to_le_bytes());
+        out[24..32].copy_from_slice(&self.ask_price().to_le_bytes());
+        out[32..40].copy_from_slice(&self.ask_quantity().to_le_bytes());
+        out[40..48].copy_from_slice(&self.timestamp().to_le_bytes());
+        out[48..56].copy_from_slice(&self._padding);
+        out
+    }
+
+    pub fn to_frame(&self) -> BytesMut {
+        MessageHeader::builder()
+            .msg_type(Self::MSG_TYPE)
+            .timestamp(self.timestamp())
+            .frame(&self.to_bytes())
+    }
+}
+
+/// Mensagem de controle (`msg_type` 5) que marca o fim de uma sessão. O
+/// ingestor não a entrega ao canal: ela dispara os callbacks registrados em
+/// `MarketDataIngestor::on_session_end` (reset de arena, flush, compactação).
+/// Payload: `session_id` (u64 little-endian); o timestamp vem do header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EndOfSession {
+    pub session_id: u64,
+    pub timestamp: u64,
+}
+
+impl EndOfSession {
+    pub const MSG_TYPE: u8 = 5;
+    pub const SIZE: usize = 8;
+
+    pub fn parse(header: &MessageHeader, payload: &[u8]) -> Option<Self> {
+        if header.msg_type != Self::MSG_TYPE {
+            return None;
+        }
+        let session_id = u64::from_le_bytes(payload.get(..Self::SIZE)?.try_into().ok()?);
+        Some(Self { session_id, timestamp: header.timestamp })
+    }
+
+    pub fn to_frame(&self) -> BytesMut {
+        MessageHeader::builder()
+            .msg_type(Self::MSG_TYPE)
+            .timestamp(self.timestamp)
+            .frame(&self.session_id.to_le_bytes())
+    }
+}
+
+/// Callback de fim de sessão (ver `MarketDataIngestor::on_session_end`).
+pub type SessionEndCallback = Box<dyn Fn(&EndOfSession) + Send + Sync>;
+
+/// Cabeçalho do payload de um snapshot de book (`msg_type` 4), seguido de
+/// `bid_count` níveis de bid (preço decrescente) e `ask_count` níveis de ask
+/// (preço crescente), todos no formato `BookLevel`.
+#[derive(Debug, Clone, Copy)]
+#[repr(C, packed)]
+pub struct BookSnapshotHeader {
+    pub symbol: [u8; 8],
+    pub timestamp: u64,
+    pub bid_count: u32,
+    pub ask_count: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+#[repr(C, packed)]
+pub struct BookLevel {
+    pub price: i64,
+    pub quantity: i64,
+}
+
+/// Visão sobre um snapshot de book que percorre os níveis diretamente no payload,
+/// sem copiá-lo.
+#[derive(Debug, Clone, Copy)]
+pub struct BookSnapshot<'a> {
+    pub header: BookSnapshotHeader,
+    levels: &'a [u8],
+}
+
+impl<'a> BookSnapshot<'a> {
+    pub const MSG_TYPE: u8 = 4;
+
+    /// Retorna `None` se o payload não contém o cabeçalho ou todos os níveis declarados.
+    pub fn parse(payload: &'a [u8]) -> Option<Self> {
+        let header_size = std::mem::size_of::<BookSnapshotHeader>();
+        let header: BookSnapshotHeader = read_struct(payload).ok()?;
+        let level_count = header.bid_count as usize + header.ask_count as usize;
+        let levels_len = level_count.checked_mul(std::mem::size_of::<BookLevel>())?;
+
+        let levels = payload.get(header_size..header_size.checked_add(levels_len)?)?;
+        Some(Self { header, levels })
+    }
+
+    pub fn level_count(&self) -> usize {
+        self.levels.len() / std::mem::size_of::<BookLevel>()
+    }
+
+    /// Nível `index` do array completo (bids seguidos de asks).
+    pub fn level(&self, index: usize) -> Option<BookLevel> {
+        let size = std::mem::size_of::<BookLevel>();
+        let bytes = self.levels.get(index * size..(index + 1) * size)?;
+        read_struct(bytes).ok()
+    }
+
+    pub fn bids(&self) -> impl Iterator<Item = BookLevel> + '_ {
+        (0..self.header.bid_count as usize).filter_map(move |i| self.level(i))
+    }
+
+    pub fn asks(&self) -> impl Iterator<Item = BookLevel> + '_ {
+        let offset = self.header.bid_count as usize;
+        (0..self.header.ask_count as usize).filter_map(move |i| self.level(offset + i))
+    }
+}
+
+/// Lote de trades (`msg_type` 6) num único frame, para feeds de alta taxa
+/// sem o custo de um header por trade: `count` (u32 little-endian) seguido de
+/// `count` registros no formato `Trade`. Visão sobre o payload, sem cópia.
+#[derive(Debug, Clone, Copy)]
+pub struct TradeBatch<'a> {
+    trades: &'a [u8],
+}
+
+impl<'a> TradeBatch<'a> {
+    pub const MSG_TYPE: u8 = 6;
+    pub const COUNT_SIZE: usize = 4;
+
+    /// Retorna `None` se o payload não traz a contagem ou se o tamanho não é
+    /// exatamente o de `count` trades.
+    pub fn parse(payload: &'a [u8]) -> Option<Self> {
+        let (count, trades) = payload.split_first_chunk::<4>()?;
+        let expected = (u32::from_le_bytes(*count) as usize).checked_mul(Trade::SIZE)?;
+        (trades.len() == expected).then_some(Self { trades })
+    }
+
+    pub fn len(&self) -> usize { self.trades.len() / Trade::SIZE }
+    pub fn is_empty(&self) -> bool { self.trades.is_empty() }
+
+    pub fn trade(&self, index: usize) -> Option<Trade> {
+        read_struct(self.trades.get(index.checked_mul(Trade::SIZE)?..)?).ok()
+    }
+
+    pub fn trades(&self) -> impl Iterator<Item = Trade> + '_ {
+        self.trades.chunks_exact(Trade::SIZE).filter_map(|record| read_struct(record).ok())
+    }
+
+    /// Frame com os `trades` em lote; o timestamp do header é o do primeiro.
+    pub fn frame(trades: &[Trade]) -> BytesMut {
+        let mut payload = Vec::with_capacity(Self::COUNT_SIZE + trades.len() * Trade::SIZE);
+        payload.extend_from_slice(&(trades.len() as u32).to_le_bytes());
+        for trade in trades {
+            payload.extend_from_slice(&trade.to_bytes());
+        }
+        MessageHeader::builder()
+            .msg_type(Self::MSG_TYPE)
+            .timestamp(trades.first().map_or(0, Trade::timestamp))
+            .frame(&payload)
+    }
+}
+
+/// Mensagem retirada do canal: header decodificado mais o buffer da arena que a
+/// contém. O buffer mantém o `Arc` da arena vivo enquanto a mensagem existir.
+pub struct ParsedMessage<A: Arena = ZeroCopyArena> {
+    pub header: MessageHeader,
+    buffer: ZeroCopyBuffer<A>,
+}
+
+impl<A: Arena> ParsedMessage<A> {
+    pub fn from_buffer(buffer: ZeroCopyBuffer<A>) -> Option<Self> {
+        let header = MessageHeader::from_bytes(buffer.as_slice())?;
+        if buffer.len() < header.frame_size() {
+            return None;
+        }
+        Some(Self { header, buffer })
+    }
+
+    pub fn payload(&self) -> &[u8] {
+        &self.buffer.as_slice()[self.header.payload_offset()..self.header.frame_size()]
+    }
+
+    pub fn buffer(&self) -> &ZeroCopyBuffer<A> { &self.buffer }
+    pub fn into_buffer(self) -> ZeroCopyBuffer<A> { self.buffer }
+
+    pub fn trade(&self) -> Option<Trade> {
+        let payload = self.payload();
+        if self.header.msg_type != Trade::MSG_TYPE {
+            return None;
+        }
+        read_struct(payload).ok()
+    }
+
+    pub fn quote(&self) -> Option<Quote> {
+        let payload = self.payload();
+        if self.header.msg_type != Quote::MSG_TYPE {
+            return None;
+        }
+        read_struct(payload).ok()
+    }
+}
+
+/// Retentativas quando a arena esgota de forma transitória (ex.: consumidores
+/// ainda segurando buffers); `ArenaError::TooLarge` nunca é retentado. Cada tentativa tenta `try_reclaim` e, se não
+/// conseguir espaço, gira por `backoff` (ou cede a thread, se zero). O tempo
+/// total fica limitado por `max_total` para preservar a meta de latência.
+/// O padrão não retenta: a mensagem é descartada na primeira falha.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub backoff: Duration,
+    pub max_total: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { max_retries: 0, backoff: Duration::ZERO, max_total: Duration::ZERO }
+    }
+}
+
+/// Ocupa a arena inteira em blocos e escreve em cada página, forçando os page
+/// faults agora; depois libera os blocos e rebobina.
+fn prefault<A: Arena>(arena: &Arc<A>) {
+    const PAGE_SIZE: usize = 4096;
+    const CHUNK: usize = 1024 * 1024;
+
+    let mut held = Vec::new();
+    loop {
+        let remaining = arena.capacity().saturating_sub(arena.used());
+        let size = remaining.min(CHUNK) / AVX512_ALIGNMENT * AVX512_ALIGNMENT;
+        let Some(mut buffer) = (size > 0).then(|| ZeroCopyBuffer::new(size, Arc::clone(arena)).ok()).flatten() else {
+            break;
+        };
+        for page in buffer.as_mut_slice().chunks_mut(PAGE_SIZE) {
+            page[0] = 0;
+            *page.last_mut().expect("chunk não vazio") = 0;
+        }
+        held.push(buffer);
+    }
+    drop(held);
+    arena.reset();
+}
+
+/// Arena dedicada a uma fonte (ver `MarketDataIngestor::with_source_arenas`).
+struct SourceArena<A: Arena> {
+    arena: Arc<A>,
+    allocation_drops: AtomicU64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SourceArenaStats {
+    pub source: u8,
+    pub used_bytes: usize,
+    pub capacity_bytes: usize,
+    /// Mensagens desta fonte descartadas por falta de espaço na sua arena.
+    pub allocation_drops: u64,
+}
+
+pub struct MarketDataIngestor<A: Arena = ZeroCopyArena> {
+    arena: Arc<A>,
+    source_arenas: HashMap<u8, SourceArena<A>>,
+    tx: Sender<ZeroCopyBuffer<A>>,
+    rx: Receiver<ZeroCopyBuffer<A>>,
+    broadcaster: Broadcaster,
+    checksum: ChecksumValidator,
+    custom_checksum: Option<Arc<dyn Checksum>>,
+    session_end_handlers: RwLock<Vec<SessionEndCallback>>,
+    retry_policy: RetryPolicy,
+    clock: Arc<dyn Clock>,
+    sink: Option<Box<dyn MessageSink<A>>>,
+    sink_mode: SinkMode,
+    /// Alocações acima deste tempo (ns) geram um aviso.
+    #[cfg(feature = "alloc-timing")]
+    slow_allocation_ns: u64,
+    reassembly: Option<Reassembly>,
+    /// Cópias para a arena a partir deste tamanho usam stores não-temporais.
+    nontemporal_threshold: usize,
+    byte_order: ByteOrder,
+    rate_ceiling: Option<RateCeiling>,
+    filter: Option<DiscardFilter>,
+    tape: Option<RecentTape>,
+    throughput: Option<ThroughputRecorder>,
+    paused: AtomicBool,
+    #[cfg(feature = "otel")]
+    spans: IngestionSpans,
+    stats: IngestionStats,
+}
+
+/// Bytes de frames incompletos guardados entre chamadas a
+/// `process_raw_data`, por fonte (`None` = sem fonte).
+struct Reassembly {
+    max_frame: usize,
+    /// Um stash por fonte, com trava própria: uma fonte lenta (retentativas
+    /// de alocação) não bloqueia as demais.
+    pending: Mutex<HashMap<Option<u8>, Arc<Mutex<BytesMut>>>>,
+}
+
+/// Limiar padrão do aviso de alocação lenta: uma alocação normal é um único
+/// `fetch_add` e fica bem abaixo de 1 μs.
+#[cfg(feature = "alloc-timing")]
+pub const DEFAULT_SLOW_ALLOCATION_NS: u64 = 10_000;
+
+/// Contadores lock-free: o caminho de ingestão só faz `fetch_add` relaxados.
+#[derive(Debug, Default)]
+struct IngestionStats {
+    messages_received: AtomicU64,
+    bytes_received: AtomicU64,
+    parse_errors: AtomicU64,
+    /// Mensagens aceitas mas descartadas porque o canal (principal ou de um
+    /// assinante de broadcast) estava cheio.
+    messages_dropped: AtomicU64,
+    allocation_retries: AtomicU64,
+    /// Mensagens descartadas porque a arena continuou esgotada após as retentativas.
+    allocation_drops: AtomicU64,
+    /// Nanossegundos desde a época Unix; 0 = nenhuma mensagem ainda.
+    last_message_nanos: AtomicU64,
+    /// Ingestão menos `header.timestamp` (trânsito + processamento), em ns.
+    e2e_latency: LatencyHistogram,
+    /// Mensagens com timestamp do produtor no futuro do relógio local
+    /// (contadas aqui e registradas com latência 0).
+    clock_skew_events: AtomicU64,
+    /// Mensagens que o `MessageSink` configurado recusou.
+    sink_errors: AtomicU64,
+    /// Mensagens descartadas pelo `RateCeiling` (política `Drop`).
+    rate_limited_drops: AtomicU64,
+    /// Vezes que o `RateCeiling` segurou o produtor (política `Throttle`).
+    rate_throttled: AtomicU64,
+    /// Mensagens descartadas pelo `DiscardFilter`, antes da arena.
+    messages_filtered: AtomicU64,
+    /// Duração de cada chamada a `Arena::allocate` feita pelo ingestor, em ns.
+    #[cfg(feature = "alloc-timing")]
+    alloc_latency: LatencyHistogram,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct IngestionStatsSnapshot {
+    pub messages_received: u64,
+    pub bytes_received: u64,
+    pub parse_errors: u64,
+    /// Arredondados para cima: uma arena com qualquer byte em uso nunca aparece como 0 MB.
+    pub arena_used_mb: usize,
+    pub arena_capacity_mb: usize,
+    pub messages_per_second: f64,
+    pub messages_dropped: u64,
+    pub failed_allocations: u64,
+    pub allocation_retries: u64,
+    pub allocation_drops: u64,
+    pub arena_used_bytes: usize,
+    pub arena_capacity_bytes: usize,
+    /// Arenas dedicadas por fonte, em ordem de fonte (vazio sem `with_source_arenas`).
+    pub source_arenas: Vec<SourceArenaStats>,
+    /// p99 da latência fim a fim (relógio do ingestor − `header.timestamp`), em ns.
+    pub e2e_latency_p99: u64,
+    pub clock_skew_events: u64,
+    pub sink_errors: u64,
+    pub rate_limited_drops: u64,
+    pub rate_throttled: u64,
+    pub messages_filtered: u64,
+    /// Percentis da duração de `Arena::allocate`, em ns; 0 sem a feature `alloc-timing`.
+    pub alloc_latency_p50: u64,
+    pub alloc_latency_p99: u64,
+    pub alloc_latency_max: u64,
+}
+
+impl IngestionStatsSnapshot {
+    /// Visão única de vários ingestores (shards, fontes): contadores, bytes
+    /// de arena e `messages_per_second` são somados; `source_arenas` são
+    /// concatenadas. Percentis não se combinam exatamente sem os histogramas,
+    /// então p50/p99 e o máximo ficam com o maior valor entre os snapshots
+    /// (um limite superior).
+    pub fn merge(snapshots: &[IngestionStatsSnapshot]) -> IngestionStatsSnapshot {
+        let mut merged = snapshots.iter().fold(IngestionStatsSnapshot::default(), |mut acc, s| {
+            acc.messages_received += s.messages_received;
+            acc.bytes_received += s.bytes_received;
+            acc.parse_errors += s.parse_errors;
+            acc.messages_per_second += s.messages_per_second;
+            acc.messages_dropped += s.messages_dropped;
+            acc.failed_allocations += s.failed_allocations;
+            acc.allocation_retries += s.allocation_retries;
+            acc.allocation_drops += s.allocation_drops;
+            acc.arena_used_bytes += s.arena_used_bytes;
+            acc.arena_capacity_bytes += s.arena_capacity_bytes;
+            acc.source_arenas.extend_from_slice(&s.source_arenas);
+            acc.e2e_latency_p99 = acc.e2e_latency_p99.max(s.e2e_latency_p99);
+            acc.clock_skew_events += s.clock_skew_events;
+            acc.sink_errors += s.sink_errors;
+            acc.rate_limited_drops += s.rate_limited_drops;
+            acc.rate_throttled += s.rate_throttled;
+            acc.messages_filtered += s.messages_filtered;
+            acc.alloc_latency_p50 = acc.alloc_latency_p50.max(s.alloc_latency_p50);
+            acc.alloc_latency_p99 = acc.alloc_latency_p99.max(s.alloc_latency_p99);
+            acc.alloc_latency_max = acc.alloc_latency_max.max(s.alloc_latency_max);
+            acc
+        });
+        // Recalculados do total: somar MB já arredondados superestimaria
+        merged.arena_used_mb = merged.arena_used_bytes.div_ceil(1024 * 1024);
+        merged.arena_capacity_mb = merged.arena_capacity_bytes.div_ceil(1024 * 1024);
+        merged
+    }
+}
+
+impl MarketDataIngestor {
+    /// Como `try_new`, mas entra em pânico se a arena não puder ser alocada.
+    /// Só para binários e testes com capacidades fixas e pequenas; código
+    /// chamado via FFI deve usar `try_new`.
+    pub fn new(arena_capacity: usize, channel_size: usize) -> Self {
+        Self::try_new(arena_capacity, channel_size).expect("falha ao alocar a arena do ingestor")
+    }
+
+    pub fn try_new(arena_capacity: usize, channel_size: usize) -> Result<Self, std::io::Error> {
+        Ok(Self::with_arena(ZeroCopyArena::new(arena_capacity)?, channel_size))
+    }
+
+    /// Ingestor descrito por um `PipelineConfig` em TOML (arena, canal e
+    /// política de retentativa). Erros de leitura ou valores inválidos
+    /// voltam como `io::Error` citando o campo.
+    pub fn from_config(path: impl AsRef<std::path::Path>) -> Result<Self, std::io::Error> {
+        crate::config::PipelineConfig::load(path)?.ingestor()
+    }
+
+    /// Dá a cada fonte listada uma arena própria com a capacidade indicada
+    /// (bytes). Mensagens ingeridas com `process_raw_data_from` para essas
+    /// fontes só consomem a própria arena, então uma fonte que esgota a sua
+    /// descarta apenas as próprias mensagens. Fontes não listadas usam a arena
+    /// compartilhada.
+    pub fn with_source_arenas(mut self, capacities: HashMap<u8, usize>) -> Result<Self, std::io::Error> {
+        for (source, capacity) in capacities {
+            let arena = Arc::new(ZeroCopyArena::new(capacity)?);
+            self.source_arenas.insert(source, SourceArena { arena, allocation_drops: AtomicU64::new(0) });
+        }
+        Ok(self)
+    }
+}
+
+impl<A: Arena> MarketDataIngestor<A> {
+    /// Ingestor sobre uma arena já construída, de qualquer estratégia de alocação.
+    pub fn with_arena(arena: A, channel_size: usize) -> Self {
+        let arena = Arc::new(arena);
+        let (tx, rx) = bounded(channel_size);
+
+        info!("Ingestor criado: arena={} MB, canal={}", arena.capacity() / (1024 * 1024), channel_size);
+
+        Self {
+            arena,
+            tx,
+            rx,
+            source_arenas: HashMap::new(),
+            broadcaster: Broadcaster::new(channel_size),
+            checksum: ChecksumValidator::new(),
+            custom_checksum: None,
+            session_end_handlers: RwLock::new(Vec::new()),
+            retry_policy: RetryPolicy::default(),
+            clock: Arc::new(SystemClock),
+            sink: None,
+            sink_mode: SinkMode::default(),
+            #[cfg(feature = "alloc-timing")]
+            slow_allocation_ns: DEFAULT_SLOW_ALLOCATION_NS,
+            reassembly: None,
+            nontemporal_threshold: usize::MAX,
+            byte_order: ByteOrder::default(),
+            rate_ceiling: None,
+            filter: None,
+            tape: None,
+            throughput: None,
+            paused: AtomicBool::new(false),
+            #[cfg(feature = "otel")]
+            spans: IngestionSpans::global(),
+            stats: IngestionStats::default(),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Tempo (ns) a partir do qual uma única alocação na arena é registrada
+    /// como lenta. Padrão: `DEFAULT_SLOW_ALLOCATION_NS`.
+    #[cfg(feature = "alloc-timing")]
+    pub fn with_slow_allocation_threshold(mut self, nanos: u64) -> Self {
+        self.slow_allocation_ns = nanos;
+        self
+    }
+
+    /// Faz o ingestor guardar os bytes de um frame incompleto e emendá-los
+    /// aos da próxima chamada a `process_raw_data` (da mesma fonte), em vez
+    /// de exigir que o chamador reapresente os mesmos bytes. `Incomplete`
+    /// continua sendo devolvido, mas `raw_data` fica vazio. Um frame que
+    /// declara mais de `max_frame` bytes é descartado como `CorruptedFormat`.
+    /// Cada chamada ingere todos os frames completos do stash; um header
+    /// rejeitado dentro dele é devolvido como erro e a leitura ressincroniza
+    /// no próximo magic.
+    pub fn with_reassembly(mut self, max_frame: usize) -> Self {
+        self.reassembly = Some(Reassembly { max_frame, pending: Mutex::new(HashMap::new()) });
+        self
+    }
+
+    /// Descarta, logo após o parse do header, as mensagens que `filter` não
+    /// aceita: não ocupam a arena, não chegam ao canal nem à validação, não
+    /// consomem o teto de taxa e são contadas em `messages_filtered`.
+    /// `EndOfSession` nunca é filtrado.
+    pub fn with_filter(mut self, filter: DiscardFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Guarda cópias das últimas `depth` mensagens de cada símbolo (até
+    /// `max_symbols` símbolos), consultáveis por `recent_tape` sem drenar o
+    /// canal. Cada mensagem aceita passa a custar uma cópia no heap.
+    pub fn with_recent_tape(mut self, depth: usize, max_symbols: usize) -> Self {
+        self.tape = Some(RecentTape::new(depth, max_symbols));
+        self
+    }
+
+    /// Fita de mensagens recentes; `None` sem `with_recent_tape`.
+    pub fn recent_tape(&self) -> Option<&RecentTape> { self.tape.as_ref() }
+
+    /// Registra mensagens e bytes aceitos em janelas de `window` pelo relógio
+    /// do ingestor (ver `with_clock`), guardando as últimas `buckets` janelas.
+    pub fn with_throughput_log(mut self, window: Duration, buckets: usize) -> Self {
+        self.throughput = Some(ThroughputRecorder::new(window, buckets));
+        self
+    }
+
+    /// Vazão por janela; `None` sem `with_throughput_log`.
+    pub fn throughput_log(&self) -> Option<&ThroughputRecorder> { self.throughput.as_ref() }
+
+    /// Copia para a arena com stores não-temporais (ver
+    /// `nontemporal::copy_nontemporal`) os frames de pelo menos `threshold`
+    /// bytes — tipicamente book snapshots grandes, que o consumidor lê em
+    /// outro núcleo. Desligado por padrão.
+    pub fn with_nontemporal_copy(mut self, threshold: usize) -> Self {
+        self.nontemporal_threshold = threshold;
+        self
+    }
+
+    /// Ordem de bytes do header e do payload nos frames recebidos. Fora do
+    /// padrão (little-endian nas duas seções), cada frame é convertido para o
+    /// layout canônico ao ser ingerido; ver `ByteOrder`.
+    pub fn with_byte_order(mut self, byte_order: ByteOrder) -> Self {
+        self.byte_order = byte_order;
+        self
+    }
+
+    /// Confere e calcula o checksum do payload com `checksum` em vez do CRC,
+    /// ao converter frames (`with_byte_order`), montá-los (`process_frame`) e
+    /// antes de um fim de sessão. Par de `CompositeValidatorBuilder::custom_checksum`:
+    /// o checksum do header (v2) continua CRC, e os buffers não guardam
+    /// `cached_checksum`.
+    pub fn with_custom_checksum(mut self, checksum: impl Checksum + 'static) -> Self {
+        self.custom_checksum = Some(Arc::new(checksum));
+        self
+    }
+
+    fn payload_checksum(&self) -> &dyn Checksum {
+        match &self.custom_checksum {
+            Some(custom) => custom.as_ref(),
+            None => &self.checksum,
+        }
+    }
+
+    /// Teto global de mensagens de dados por segundo; mensagens de controle
+    /// (`EndOfSession`) não passam pelo teto. Ver `RateCeiling`.
+    pub fn with_rate_ceiling(mut self, ceiling: RateCeiling) -> Self {
+        self.rate_ceiling = Some(ceiling);
+        self
+    }
+
+    /// Tracer dos spans de ingestão e validação, no lugar do global.
+    #[cfg(feature = "otel")]
+    pub fn with_tracer(mut self, tracer: opentelemetry::global::BoxedTracer) -> Self {
+        self.spans = IngestionSpans::new(tracer);
+        self
+    }
+
+    /// Relógio do instante de ingestão (latência fim a fim e `last_message_nanos`).
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Entrega cada mensagem aceita a `sink`, no lugar do canal principal ou
+    /// junto com ele (ver `SinkMode`). O broadcast não é afetado.
+    pub fn with_sink(mut self, sink: impl MessageSink<A> + 'static, mode: SinkMode) -> Self {
+        self.sink = Some(Box::new(sink));
+        self.sink_mode = mode;
+        self
+    }
+
+    /// Ocupação do caminho de ingestão em `[0, 1]`: a maior entre a do canal
+    /// principal e a da arena compartilhada. Base do `ProducerToken`. Só lê:
+    /// a arena conta como vazia quando nenhum buffer dela está vivo, mesmo
+    /// antes de ser rebobinada (ver `Arena::in_use`).
+    pub fn pressure(&self) -> f64 {
+        let channel = match self.tx.capacity() {
+            Some(capacity) if capacity > 0 => self.tx.len() as f64 / capacity as f64,
+            _ => 0.0,
+        };
+        let arena = match self.arena.capacity() {
+            0 => 0.0,
+            capacity => self.arena.in_use() as f64 / capacity as f64,
+        };
+        channel.max(arena)
+    }
+
+    /// Token de controle de fluxo para um produtor; ver `ProducerToken`.
+    pub fn producer_token(&self) -> ProducerToken<'_, A> { ProducerToken::new(self) }
+
+    /// Aquece o caminho de ingestão antes do tráfego real: toca cada página
+    /// das arenas (evitando page faults na primeira mensagem) e roda
+    /// `iterations` vezes o parsing, checksum, alocação e cópia de um trade
+    /// sintético, descartando o resultado. Não altera estatísticas, não
+    /// publica nada no canal nem no broadcast e deixa as arenas rebobinadas.
+    ///
+    /// Para chamar com o ingestor ocioso: arenas com buffers vivos são
+    /// deixadas como estão. Retorna quantas arenas foram aquecidas.
+    pub fn warmup(&self, iterations: usize) -> usize {
+        let arenas = std::iter::once(&self.arena).chain(self.source_arenas.values().map(|source| &source.arena));
+        let idle: Vec<&Arc<A>> = arenas.filter(|arena| arena.used() == 0 || arena.reset()).collect();
+
+        let frame = Trade::new(*b"WARMUP\0\0", 1, 1, self.clock.now_nanos(), 0, 0).to_frame();
+        for arena in &idle {
+            prefault(arena);
+            for _ in 0..iterations {
+                let Ok(header) = read_struct::<MessageHeader>(&frame) else { continue };
+                let total_size = header.frame_size();
+                let valid = header.check_integrity().is_ok()
+                    && self.checksum.calculate(&frame[header.payload_offset()..total_size]) == { header.checksum };
+                if let (true, Ok(mut buffer)) = (valid, ZeroCopyBuffer::new(total_size, Arc::clone(arena))) {
+                    buffer.as_mut_slice().copy_from_slice(&frame[..total_size]);
+                    std::hint::black_box(buffer.as_slice());
+                }
+                arena.reset();
+            }
+        }
+        idle.len()
+    }
+
+    /// Receptor do canal principal para consumo MPMC: cada mensagem vai para
+    /// exatamente um dos consumidores (work-stealing), não para todos.
+    pub fn subscribe(&self) -> Receiver<ZeroCopyBuffer<A>> {
+        self.rx.clone()
+    }
+
+    /// Registra um callback chamado, na thread de ingestão, para cada
+    /// `EndOfSession` recebido. Essas mensagens não vão para o canal.
+    pub fn on_session_end<F>(&self, callback: F)
+    where
+        F: Fn(&EndOfSession) + Send + Sync + 'static,
+    {
+        self.session_end_handlers.write().push(Box::new(callback));
+    }
+
+    /// Canal próprio que recebe toda mensagem ingerida (broadcast), independente
+    /// do canal principal. Os assinantes recebem `Bytes` que compartilham o
+    /// buffer do canal principal, sem cópia (ver `ZeroCopyBuffer::as_mut_slice`); um assinante
+    /// lento perde mensagens (contadas em `messages_dropped`) em vez de travar
+    /// a ingestão. Ver `Broadcaster`.
+    pub fn broadcast_subscribe(&self) -> Receiver<Bytes> {
+        self.broadcaster.subscribe()
+    }
+
+    pub fn broadcast_subscribe_with_policy(&self, policy: SlowSubscriberPolicy) -> Receiver<Bytes> {
+        self.broadcaster.subscribe_with_policy(policy)
+    }
+
+    /// Para de aceitar mensagens sem desmontar o ingestor (manutenção,
+    /// reconfiguração no meio do fluxo): `process_raw_data`, `process_frame` e
+    /// afins passam a devolver `Paused` sem consumir os bytes nem contá-los
+    /// como descartes. Mensagens já no canal continuam disponíveis.
+    pub fn pause(&self) { self.paused.store(true, Ordering::SeqCst); }
+
+    /// Volta a aceitar mensagens após `pause`.
+    pub fn resume(&self) { self.paused.store(false, Ordering::SeqCst); }
+
+    pub fn is_paused(&self) -> bool { self.paused.load(Ordering::Relaxed) }
+
+    pub fn process_raw_data(&self, raw_data: &mut BytesMut) -> Result<(), IngestionError> {
+        self.reassemble(None, raw_data)
+    }
+
+    /// Como `process_raw_data`, alocando na arena dedicada de `source`, se houver.
+    pub fn process_raw_data_from(&self, source: u8, raw_data: &mut BytesMut) -> Result<(), IngestionError> {
+        self.reassemble(Some(source), raw_data)
+    }
+
+    /// Processa um lote completo sem parar no primeiro erro: um header
+    /// corrompido é registrado e a leitura ressincroniza no próximo magic;
+    /// frames bem formados que falham (arena esgotada, fim de sessão inválido)
+    /// são registrados e pulados. Um frame que não cabe no resto do lote
+    /// (`Incomplete`: truncado no fim, ou com `payload_size` corrompido) também
+    /// ressincroniza no próximo magic, se houver. `raw_data` termina vazio; a
+    /// remontagem entre chamadas não se aplica.
+    pub fn process_batch_tolerant(&self, raw_data: &mut BytesMut) -> TolerantBatchReport {
+        let batch_len = raw_data.len();
+        let mut report = TolerantBatchReport::default();
+        if self.is_paused() {
+            report.errors.push((0, IngestionError::Paused));
+            return report;
+        }
+
+        while !raw_data.is_empty() {
+            let offset = batch_len - raw_data.len();
+            let remaining = raw_data.len();
+            match self.ingest(None, raw_data) {
+                Ok(()) => report.ingested += 1,
+                Err(e) => {
+                    report.errors.push((offset, e));
+                    if raw_data.len() == remaining {
+                        report.skipped_bytes += self.resync(raw_data);
+                    }
+                }
+            }
+        }
+        report
+    }
+
+    fn reassemble(&self, source: Option<u8>, raw_data: &mut BytesMut) -> Result<(), IngestionError> {
+        if self.is_paused() {
+            return Err(IngestionError::Paused);
+        }
+        let source_arena = source.and_then(|source| self.source_arenas.get(&source));
+        let Some(reassembly) = &self.reassembly else {
+            return self.ingest(source_arena, raw_data);
+        };
+
+        let stash = Arc::clone(reassembly.pending.lock().entry(source).or_default());
+        let mut stashed = stash.lock();
+        if stashed.is_empty() {
+            let result = self.ingest(source_arena, raw_data);
+            let Err(IngestionError::Incomplete { needed, .. }) = result else {
+                return result;
+            };
+            if needed > reassembly.max_frame {
+                raw_data.clear();
+                return Err(self.oversized_frame(needed, reassembly.max_frame));
+            }
+            stashed.extend_from_slice(raw_data);
+            raw_data.clear();
+            return Err(IngestionError::Incomplete { needed, available: stashed.len() });
+        }
+
+        // Drena todos os frames completos do stash; um header rejeitado
+        // ressincroniza no próximo magic em vez de travar a fonte.
+        stashed.extend_from_slice(raw_data);
+        raw_data.clear();
+        let mut first_error = None;
+        while !stashed.is_empty() {
+            let remaining = stashed.len();
+            match self.ingest(source_arena, &mut stashed) {
+                Ok(()) => {}
+                Err(IngestionError::Incomplete { needed, .. }) if needed > reassembly.max_frame => {
+                    stashed.clear();
+                    first_error.get_or_insert(self.oversized_frame(needed, reassembly.max_frame));
+                }
+                Err(IngestionError::Incomplete { needed, .. }) => {
+                    return match first_error {
+                        Some(e) => Err(e),
+                        None => Err(IngestionError::Incomplete { needed, available: stashed.len() }),
+                    };
+                }
+                Err(e @ IngestionError::Validation(_)) => {
+                    if stashed.len() == remaining {
+                        self.resync(&mut stashed);
+                    }
+                    first_error.get_or_insert(e);
+                }
+                // Arena esgotada: o frame fica no stash para a próxima chamada
+                Err(e) => return Err(first_error.unwrap_or(e)),
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    #[cold]
+    fn oversized_frame(&self, needed: usize, max_frame: usize) -> IngestionError {
+        self.stats.parse_errors.fetch_add(1, Ordering::Relaxed);
+        warn!("Frame de {} bytes excede o limite de remontagem ({} bytes)", needed, max_frame);
+        ValidationError::CorruptedFormat.into()
+    }
+
+    /// Pula até o próximo magic após o início de `data` (ou até o fim),
+    /// devolvendo quantos bytes foram descartados.
+    fn resync(&self, data: &mut BytesMut) -> usize {
+        let magic = match self.byte_order.header_endian {
+            Endian::Little => MessageHeader::MAGIC.to_le_bytes(),
+            Endian::Big => MessageHeader::MAGIC.to_be_bytes(),
+        };
+        let skip = data
+            .get(1..)
+            .and_then(|rest| rest.windows(magic.len()).position(|window| window == magic))
+            .map_or(data.len(), |position| position + 1);
+        data.advance(skip);
+        skip
+    }
+
+    #[cfg(feature = "otel")]
+    fn ingest(&self, source_arena: Option<&SourceArena<A>>, raw_data: &mut BytesMut) -> Result<(), IngestionError> {
+        let span = self.spans.ingest(raw_data);
+        let result = self.ingest_frame(source_arena, raw_data);
+        otel::finish_ingest(span, &result);
+        result
+    }
+
+    #[cfg(not(feature = "otel"))]
+    fn ingest(&self, source_arena: Option<&SourceArena<A>>, raw_data: &mut BytesMut) -> Result<(), IngestionError> {
+        self.ingest_frame(source_arena, raw_data)
+    }
+
+    fn ingest_frame(&self, source_arena: Option<&SourceArena<A>>, raw_data: &mut BytesMut) -> Result<(), IngestionError> {
+        if self.byte_order.is_canonical() {
+            return self.ingest_canonical(source_arena, raw_data, false);
+        }
+
+        let Ok(header) = read_struct_as::<MessageHeader>(raw_data, self.byte_order.header_endian) else {
+            return Err(IngestionError::Incomplete { needed: MessageHeader::SIZE, available: raw_data.len() });
+        };
+        let total_size = match header.check_integrity() {
+            Ok(()) => header.frame_size(),
+            Err(e) => {
+                self.stats.parse_errors.fetch_add(1, Ordering::Relaxed);
+                return Err(e.into());
+            }
+        };
+        if raw_data.len() < total_size {
+            return Err(IngestionError::Incomplete { needed: total_size, available: raw_data.len() });
+        }
+
+        let mut frame = match self.byte_order.canonicalize(&header, &raw_data[..total_size], &self.checksum, self.payload_checksum()) {
+            Ok(frame) => frame,
+            Err(e) => {
+                raw_data.advance(total_size);
+                self.stats.parse_errors.fetch_add(1, Ordering::Relaxed);
+                return Err(e.into());
+            }
+        };
+        // `canonicalize` recalculou o checksum do payload convertido
+        let result = self.ingest_canonical(source_arena, &mut frame, true);
+        // Arena esgotada: como no caminho canônico, o frame fica em `raw_data`
+        if !matches!(result, Err(IngestionError::Arena(_))) {
+            raw_data.advance(total_size);
+        }
+        result
+    }
+
+    fn ingest_canonical(
+        &self,
+        source_arena: Option<&SourceArena<A>>,
+        raw_data: &mut BytesMut,
+        checksum_computed: bool,
+    ) -> Result<(), IngestionError> {
+        let start = Instant::now();
+
+        let Ok(header) = read_struct::<MessageHeader>(raw_data) else {
+            return Err(IngestionError::Incomplete { needed: MessageHeader::SIZE, available: raw_data.len() });
+        };
+
+        if let Err(e) = header.check_integrity() {
+            self.stats.parse_errors.fetch_add(1, Ordering::Relaxed);
+            return Err(e.into());
+        }
+
+        let total_size = header.frame_size();
+
+        if raw_data.len() < total_size {
+            return Err(IngestionError::Incomplete { needed: total_size, available: raw_data.len() });
+        }
+
+        if header.msg_type == EndOfSession::MSG_TYPE {
+            // O fim de sessão dispara resets: checksums conferidos antes, não só
+            // na validação do consumidor (`canonicalize` já os conferiu)
+            let frame = &raw_data[..total_size];
+            let verified = if checksum_computed { Ok(()) } else { self.verify_checksums(&header, frame) };
+            let result = match verified {
+                Ok(()) => self.end_session(&header, &frame[header.payload_offset()..]),
+                Err(e) => {
+                    self.stats.parse_errors.fetch_add(1, Ordering::Relaxed);
+                    Err(e.into())
+                }
+            };
+            raw_data.advance(total_size);
+            return result;
+        }
+
+        if !self.passes_filter(&header, &raw_data[header.payload_offset()..total_size]) || !self.admit() {
+            raw_data.advance(total_size);
+            return Ok(());
+        }
+
+        let mut buffer = match source_arena {
+            Some(source) => self.allocate_with_retry_in(&source.arena, total_size).map_err(|e| {
+                source.allocation_drops.fetch_add(1, Ordering::Relaxed);
+                self.stats.allocation_drops.fetch_add(1, Ordering::Relaxed);
+                IngestionError::from(e)
+            })?,
+            None => self.allocate_frame(total_size)?,
+        };
+        nontemporal::copy_with_threshold(buffer.as_mut_slice(), &raw_data[..total_size], self.nontemporal_threshold);
+        raw_data.advance(total_size);
+        if checksum_computed && self.custom_checksum.is_none() {
+            buffer.cache_checksum(CachedChecksum { algorithm: self.checksum.algorithm(), value: header.checksum });
+        }
+
+        self.enqueue(buffer, header.timestamp, start);
+        Ok(())
+    }
+
+    /// Monta e enfileira um frame a partir de um header e um payload separados,
+    /// escrevendo ambos direto no buffer da arena (sem concatenação prévia).
+    /// `payload_size` e `checksum` do header (e, em v2, o checksum do header)
+    /// são recalculados a partir de `payload`.
+    pub fn process_frame(&self, header: &MessageHeader, payload: &[u8]) -> Result<(), IngestionError> {
+        if self.is_paused() {
+            return Err(IngestionError::Paused);
+        }
+        #[cfg(feature = "otel")]
+        let span = self.spans.frame(header, payload);
+        let result = self.assemble_frame(header, payload);
+        #[cfg(feature = "otel")]
+        otel::finish_ingest(span, &result);
+        result
+    }
+
+    fn assemble_frame(&self, header: &MessageHeader, payload: &[u8]) -> Result<(), IngestionError> {
+        let start = Instant::now();
+
+        let mut header = *header;
+        header.payload_size = payload.len() as u32;
+        if let Err(e) = header.check_integrity() {
+            self.stats.parse_errors.fetch_add(1, Ordering::Relaxed);
+            return Err(e.into());
+        }
+        header.checksum = self.payload_checksum().value(payload);
+
+        if header.msg_type == EndOfSession::MSG_TYPE {
+            return self.end_session(&header, payload);
+        }
+        if !self.passes_filter(&header, payload) || !self.admit() {
+            return Ok(());
+        }
+
+        let mut buffer = self.allocate_frame(header.frame_size())?;
+        let (header_bytes, rest) = buffer.as_mut_slice().split_at_mut(MessageHeader::SIZE);
+        header_bytes.copy_from_slice(&header.to_bytes());
+        let (extension_bytes, payload_bytes) = rest.split_at_mut(header.payload_offset() - MessageHeader::SIZE);
+        if let Some(extension) = header.header_extension(&self.checksum) {
+            extension_bytes.copy_from_slice(&extension);
+        }
+        nontemporal::copy_with_threshold(payload_bytes, payload, self.nontemporal_threshold);
+        if self.custom_checksum.is_none() {
+            buffer.cache_checksum(CachedChecksum { algorithm: self.checksum.algorithm(), value: header.checksum });
+        }
+
+        self.enqueue(buffer, header.timestamp, start);
+        Ok(())
+    }
+
+    /// Checksum do header (v2) e do payload de um frame completo e canônico.
+    fn verify_checksums(&self, header: &MessageHeader, frame: &[u8]) -> Result<(), ValidationError> {
+        if let Some(expected) = header.stored_header_checksum(frame) {
+            let calculated = self.checksum.calculate(&frame[..MessageHeader::SIZE]);
+            if calculated != expected {
+                return Err(ValidationError::HeaderChecksumMismatch { expected, calculated });
+            }
+        }
+        let calculated = self.payload_checksum().value(&frame[header.payload_offset()..]);
+        if calculated != header.checksum {
+            return Err(ValidationError::ChecksumMismatch { expected: header.checksum, calculated });
+        }
+        Ok(())
+    }
+
+    fn end_session(&self, header: &MessageHeader, payload: &[u8]) -> Result<(), IngestionError> {
+        let Some(event) = EndOfSession::parse(header, payload) else {
+            self.stats.parse_errors.fetch_add(1, Ordering::Relaxed);
+            return Err(ValidationError::CorruptedFormat.into());
+        };
+
+        info!("Fim de sessão {} (ts={})", event.session_id, event.timestamp);
+        for handler in self.session_end_handlers.read().iter() {
+            handler(&event);
+        }
+        Ok(())
+    }
+
+    /// Consulta o `DiscardFilter`, contando as mensagens descartadas por ele.
+    fn passes_filter(&self, header: &MessageHeader, payload: &[u8]) -> bool {
+        let Some(filter) = &self.filter else { return true };
+        let accepted = filter.accepts(header.msg_type, payload);
+        if !accepted {
+            self.stats.messages_filtered.fetch_add(1, Ordering::Relaxed);
+        }
+        accepted
+    }
+
+    /// Consulta o `RateCeiling`: `false` se a mensagem deve ser descartada;
+    /// com `Throttle`, dorme o necessário antes de liberar.
+    fn admit(&self) -> bool {
+        let Some(ceiling) = &self.rate_ceiling else {
+            return true;
+        };
+        match ceiling.admit(self.clock.now_nanos()) {
+            Admission::Admit => true,
+            Admission::Drop => {
+                self.stats.rate_limited_drops.fetch_add(1, Ordering::Relaxed);
+                false
+            }
+            Admission::Wait(wait) => {
+                self.stats.rate_throttled.fetch_add(1, Ordering::Relaxed);
+                std::thread::sleep(wait);
+                true
+            }
+        }
+    }
+
+    fn allocate_frame(&self, total_size: usize) -> Result<ZeroCopyBuffer<A>, IngestionError> {
+        self.allocate_with_retry(total_size).map_err(|e| {
+            self.stats.allocation_drops.fetch_add(1, Ordering::Relaxed);
+            e.into()
+        })
+    }
+
+    fn enqueue(&self, mut buffer: ZeroCopyBuffer<A>, producer_timestamp: u64, start: Instant) {
+        let total_size = buffer.len();
+        self.publish_broadcast(&mut buffer);
+        if let Some(tape) = &self.tape {
+            tape.record(buffer.as_slice());
+        }
+
+        if let Some(sink) = &self.sink {
+            if let Err(e) = sink.consume(&buffer) {
+                self.stats.sink_errors.fetch_add(1, Ordering::Relaxed);
+                warn!("Sink recusou a mensagem: {}", e);
+            }
+        }
+
+        if self.sink.is_none() || self.sink_mode == SinkMode::Alongside {
+            if let Err(e) = self.tx.try_send(buffer) {
+                self.stats.messages_dropped.fetch_add(1, Ordering::Relaxed);
+                warn!("Canal cheio: {}", e);
+            }
+        }
+
+        let elapsed = start.elapsed();
+        if elapsed.as_micros() > 100 {
+            warn!("Processamento lento: {} μs", elapsed.as_micros());
+        }
+
+        self.stats.messages_received.fetch_add(1, Ordering::Relaxed);
+        self.stats.bytes_received.fetch_add(total_size as u64, Ordering::Relaxed);
+        let now = self.clock.now_nanos();
+        if let Some(throughput) = &self.throughput {
+            throughput.record(now, total_size as u64);
+        }
+        self.record_latency(now, producer_timestamp);
+        self.stats.last_message_nanos.store(now, Ordering::Relaxed);
+    }
+
+    /// Timestamp 0 significa "não informado" e não entra no histograma.
+    fn record_latency(&self, now: u64, producer_timestamp: u64) {
+        if producer_timestamp == 0 {
+            return;
+        }
+        let sent = clock::normalize_to_nanos(producer_timestamp);
+        if sent > now {
+            self.stats.clock_skew_events.fetch_add(1, Ordering::Relaxed);
+        }
+        self.stats.e2e_latency.record(now.saturating_sub(sent));
+    }
+
+    fn publish_broadcast(&self, buffer: &mut ZeroCopyBuffer<A>) {
+        if self.broadcaster.is_empty() {
+            return;
+        }
+
+        let dropped = self.broadcaster.publish(&buffer.share());
+        if dropped > 0 {
+            self.stats.messages_dropped.fetch_add(dropped as u64, Ordering::Relaxed);
+        }
+    }
+
+    fn allocate_with_retry(&self, size: usize) -> Result<ZeroCopyBuffer<A>, ArenaError> {
+        self.allocate_with_retry_in(&self.arena, size)
+    }
+
+    #[cfg(feature = "alloc-timing")]
+    fn timed_allocation(&self, size: usize, arena: &Arc<A>) -> Result<ZeroCopyBuffer<A>, ArenaError> {
+        let start = Instant::now();
+        let result = ZeroCopyBuffer::new(size, arena.clone());
+        let elapsed = start.elapsed().as_nanos() as u64;
+        self.stats.alloc_latency.record(elapsed);
+        if elapsed > self.slow_allocation_ns {
+            warn!("Alocação lenta na arena: {} ns ({} bytes)", elapsed, size);
+        }
+        result
+    }
+
+    #[cfg(not(feature = "alloc-timing"))]
+    fn timed_allocation(&self, size: usize, arena: &Arc<A>) -> Result<ZeroCopyBuffer<A>, ArenaError> {
+        ZeroCopyBuffer::new(size, arena.clone())
+    }
+
+    fn allocate_with_retry_in(&self, arena: &Arc<A>, size: usize) -> Result<ZeroCopyBuffer<A>, ArenaError> {
+        let mut attempt = 0;
+        let mut started: Option<Instant> = None;
+
+        loop {
+            let err = match self.timed_allocation(size, arena) {
+                Ok(buffer) => return Ok(buffer),
+                Err(e) => e,
+            };
+
+            let started = *started.get_or_insert_with(Instant::now);
+            if matches!(err, ArenaError::TooLarge { .. })
+                || attempt >= self.retry_policy.max_retries || started.elapsed() >= self.retry_policy.max_total {
+                return Err(err);
+            }
+            attempt += 1;
+            self.stats.allocation_retries.fetch_add(1, Ordering::Relaxed);
+
+            if arena.reset() {
+                continue;
+            }
+            if self.retry_policy.backoff.is_zero() {
+                std::thread::yield_now();
+            } else {
+                let spin_until = Instant::now() + self.retry_policy.backoff;
+                while Instant::now() < spin_until {
+                    std::hint::spin_loop();
+                }
+            }
+        }
+    }
+
+    /// Retira as mensagens pendentes do canal validando cada uma sob demanda, na
+    /// thread consumidora. A ingestão continua mínima; a validação só acontece
+    /// quando o iterador é avançado. O iterador termina quando o canal esvazia.
+    pub fn drain_validated<'a>(
+        &'a self,
+        validator: &'a mut CompositeValidator,
+    ) -> impl Iterator<Item = Result<ParsedMessage<A>, ValidationError>> + 'a {
+        self.rx.try_iter().map(move |buffer| {
+            let message = ParsedMessage::from_buffer(buffer).ok_or(ValidationError::CorruptedFormat)?;
+            #[cfg(feature = "otel")]
+            let span = self.spans.validate(&message.header, message.payload());
+            let result = validator
+                .validate_header_checksum(&message.header, message.buffer.as_slice())
+                .and_then(|()| validator.validate_message_cached(&message.header, message.payload(), message.buffer.cached_checksum()));
+            #[cfg(feature = "otel")]
+            otel::finish_validate(span, &result);
+            result.map(|()| message)
+        })
+    }
+
+    fn source_arena_stats(&self) -> Vec<SourceArenaStats> {
+        let mut stats: Vec<_> = self
+            .source_arenas
+            .iter()
+            .map(|(&source, entry)| SourceArenaStats {
+                source,
+                used_bytes: entry.arena.used(),
+                capacity_bytes: entry.arena.capacity(),
+                allocation_drops: entry.allocation_drops.load(Ordering::Relaxed),
+            })
+            .collect();
+        stats.sort_unstable_by_key(|s| s.source);
+        stats
+    }
+
+    pub fn parse_error_count(&self) -> u64 { self.stats.parse_errors.load(Ordering::Relaxed) }
+    pub fn messages_received_count(&self) -> u64 { self.stats.messages_received.load(Ordering::Relaxed) }
+    pub fn bytes_received_count(&self) -> u64 { self.stats.bytes_received.load(Ordering::Relaxed) }
+
+    /// Zera `parse_errors` e retorna o valor anterior, num único `swap`:
+    /// incrementos concorrentes caem ou no valor retornado ou no novo
+    /// intervalo, nunca se perdem. Útil para monitorar deltas por intervalo.
+    pub fn reset_parse_errors(&self) -> u64 { self.stats.parse_errors.swap(0, Ordering::Relaxed) }
+    /// Como `reset_parse_errors`, para `messages_received`.
+    pub fn reset_messages_received(&self) -> u64 { self.stats.messages_received.swap(0, Ordering::Relaxed) }
+    /// Como `reset_parse_errors`, para `bytes_received`. Zerado à parte de
+    /// `messages_received`: os dois não formam um par consistente entre si.
+    pub fn reset_bytes_received(&self) -> u64 { self.stats.bytes_received.swap(0, Ordering::Relaxed) }
+
+    pub fn stats(&self) -> IngestionStatsSnapshot {
+        IngestionStatsSnapshot {
+            messages_received: self.stats.messages_received.load(Ordering::Relaxed),
+            bytes_received: self.stats.bytes_received.load(Ordering::Relaxed),
+            parse_errors: self.stats.parse_errors.load(Ordering::Relaxed),
+            arena_used_mb: self.arena.used().div_ceil(1024 * 1024),
+            arena_capacity_mb: self.arena.capacity().div_ceil(1024 * 1024),
+            messages_per_second: 0.0,
+            messages_dropped: self.stats.messages_dropped.load(Ordering::Relaxed),
+            failed_allocations: self.arena.failed_allocations(),
+            allocation_retries: self.stats.allocation_retries.load(Ordering::Relaxed),
+            allocation_drops: self.stats.allocation_drops.load(Ordering::Relaxed),
+            arena_used_bytes: self.arena.used(),
+            arena_capacity_bytes: self.arena.capacity(),
+            source_arenas: self.source_arena_stats(),
+            e2e_latency_p99: self.stats.e2e_latency.percentile(0.99),
+            clock_skew_events: self.stats.clock_skew_events.load(Ordering::Relaxed),
+            sink_errors: self.stats.sink_errors.load(Ordering::Relaxed),
+            rate_limited_drops: self.stats.rate_limited_drops.load(Ordering::Relaxed),
+            rate_throttled: self.stats.rate_throttled.load(Ordering::Relaxed),
+            messages_filtered: self.stats.messages_filtered.load(Ordering::Relaxed),
+            #[cfg(feature = "alloc-timing")]
+            alloc_latency_p50: self.stats.alloc_latency.percentile(0.50),
+            #[cfg(feature = "alloc-timing")]
+            alloc_latency_p99: self.stats.alloc_latency.percentile(0.99),
+            #[cfg(feature = "alloc-timing")]
+            alloc_latency_max: self.stats.alloc_latency.max(),
+            #[cfg(not(feature = "alloc-timing"))]
+            alloc_latency_p50: 0,
+            #[cfg(not(feature = "alloc-timing"))]
+            alloc_latency_p99: 0,
+            #[cfg(not(feature = "alloc-timing"))]
+            alloc_latency_max: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use crate::validation::integrity::{DataBounds, SymbolValidator};
+
+    #[test]
+    fn test_arena_allocation() {
+        let arena = ZeroCopyArena::new(1024).unwrap();
+        assert_eq!(arena.capacity(), 1024);
+
+        let buf1 = arena.allocate(64).unwrap();
+        let buf2 = arena.allocate(128).unwrap();
+
+        assert_ne!(buf1.as_ptr(), buf2.as_ptr());
+        assert_eq!(buf1.as_ptr() as usize % 64, 0);
+    }
+
+    #[test]
+    fn test_sampled_allocation_logging() {
+        #[derive(Clone, Default)]
+        struct Capture(Arc<Mutex<Vec<u8>>>);
+
+        impl std::io::Write for Capture {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> std::io::Result<()> { Ok(()) }
+        }
+
+        fn logged_allocations(every: u64) -> usize {
+            let arena = ZeroCopyArena::new(1024).unwrap().with_allocation_sampling(every);
+            let capture = Capture::default();
+            let writer = capture.clone();
+            let subscriber = tracing_subscriber::fmt()
+                .with_max_level(tracing::Level::DEBUG)
+                .with_ansi(false)
+                .with_writer(move || writer.clone())
+                .finish();
+            tracing::subscriber::with_default(subscriber, || {
+                for _ in 0..5 {
+                    arena.allocate(64).unwrap();
+                }
+            });
+            let output = String::from_utf8(capture.0.lock().clone()).unwrap();
+            output.lines().filter(|line| line.contains("Alocação na arena")).count()
+        }
+
+        assert_eq!(logged_allocations(1), 5);
+        assert_eq!(logged_allocations(2), 3);
+        assert_eq!(logged_allocations(0), 0);
+    }
+
+    #[test]
+    fn test_header_validation() {
+        let header = MessageHeader {
+            magic: MessageHeader::MAGIC,
+            msg_type: 0,
+            version: 1,
+            priority: 0,
+            flags: 0,
+            timestamp: 0,
+            payload_size: 100,
+            checksum: 0,
+        };
+
+        assert!(header.is_valid());
+    }
+
+    #[test]
+    fn test_zero_copy_buffer() {
+        let arena = std::sync::Arc::new(ZeroCopyArena::new(1024).unwrap());
+        let mut buffer = ZeroCopyBuffer::new(128, arena).unwrap();
+
+        buffer.as_mut_slice().copy_from_slice(&[42u8; 128]);
+        let slice = buffer.as_slice();
+        assert_eq!(slice.len(), 128);
+        assert_eq!(slice[0], 42);
+    }
+
+    #[test]
+    fn test_into_bytes_zero_copy() {
+        let arena = std::sync::Arc::new(ZeroCopyArena::new(1024).unwrap());
+        let mut buffer = ZeroCopyBuffer::new(64, arena.clone()).unwrap();
+        buffer.as_mut_slice().copy_from_slice(&[7u8; 64]);
+        let ptr = buffer.as_slice().as_ptr();
+
+        let bytes = buffer.into_bytes();
+        let clone = bytes.clone();
+        assert_eq!(bytes.as_ptr(), ptr);
+        assert_eq!(clone.as_ptr(), ptr);
+        assert_eq!(std::sync::Arc::strong_count(&arena), 2);
+
+        drop(bytes);
+        assert_eq!(clone.len(), 64);
+        assert!(clone.iter().all(|&b| b == 7));
+        assert_eq!(std::sync::Arc::strong_count(&arena), 2);
+
+        drop(clone);
+        assert_eq!(std::sync::Arc::strong_count(&arena), 1);
+    }
+
+    #[test]
+    fn test_frame_round_trip() {
+        let ts = 1_700_000_000_000_000_000;
+        let trade = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts, 1, 42);
+        let mut frame = trade.to_frame();
+        assert_eq!(frame.len(), MessageHeader::SIZE + Trade::SIZE);
+
+        let header = MessageHeader::from_bytes(&frame).unwrap();
+        assert!(header.is_valid());
+        assert_eq!({ header.msg_type }, Trade::MSG_TYPE);
+        assert_eq!({ header.timestamp }, ts);
+        assert_eq!({ header.payload_size } as usize, Trade::SIZE);
+        assert_eq!(header.to_bytes(), frame[..MessageHeader::SIZE]);
+
+        let native: MessageHeader = read_struct(&frame).unwrap();
+        assert_eq!(native.to_bytes(), header.to_bytes());
+
+        let parsed: Trade = read_struct(&frame[MessageHeader::SIZE..]).unwrap();
+        assert_eq!(parsed.price(), trade.price());
+        assert_eq!(parsed.trade_id(), 42);
+
+        let mut validator = crate::validation::integrity::CompositeValidator::new(
+            crate::validation::integrity::DataBounds::crypto(),
+            crate::validation::integrity::SymbolValidator::whitelist(vec!["BTCUSD".to_string()]),
+        );
+        assert!(validator.validate_message(&header, &frame[MessageHeader::SIZE..]).is_ok());
+
+        let ingestor = MarketDataIngestor::new(1024 * 1024, 16);
+        ingestor.process_raw_data(&mut frame).unwrap();
+        assert!(frame.is_empty());
+        assert_eq!(ingestor.stats().messages_received, 1);
+    }
+
+    #[test]
+    fn test_exhausted_callback_rate_limited() {
+        let fired = Arc::new(Mutex::new(Vec::new()));
+        let sink = fired.clone();
+        let arena = ZeroCopyArena::new(256)
+            .unwrap()
+            .with_on_exhausted(Duration::from_secs(60), move |stats| sink.lock().push(*stats));
+
+        arena.allocate(256).unwrap();
+        assert!(arena.allocate(64).is_err());
+        assert!(arena.allocate(64).is_err());
+        assert!(arena.allocate(128).is_err());
+
+        let fired = fired.lock();
+        assert_eq!(fired.len(), 1);
+        assert_eq!((fired[0].capacity, fired[0].used, fired[0].failed_allocations), (256, 256, 1));
+        assert_eq!(arena.failed_allocations(), 3);
+    }
 
-        let payload_size = header.payload_size as usize;
-        let total_size = std::mem::size_of::<MessageHeader>() + payload_size;
+    #[cfg(feature = "arena-size-classes")]
+    #[test]
+    fn test_size_class_histogram() {
+        let arena = ZeroCopyArena::new(1024 * 1024).unwrap();
+        for size in [1, 24, 64, 65, 100, 128, 129, 4096, 4096, 4096] {
+            arena.allocate(size).unwrap();
+        }
+        assert!(arena.allocate(2 * 1024 * 1024).is_err());
 
-        if raw_data.len() < total_size {
-            return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "Payload incompleto"));
+        let classes = arena.stats().size_classes;
+        assert_eq!(classes[..3], [3, 3, 1]);
+        assert_eq!(classes[size_class(4096)], 3);
+        assert_eq!(classes.iter().sum::<u64>(), 10);
+        assert_eq!((size_class_limit(0), size_class_limit(6)), (64, 4096));
+        assert_eq!(size_class(usize::MAX), SIZE_CLASSES - 1);
+    }
+
+    #[test]
+    fn test_leaked_buffer_reported_after_max_age() {
+        use crate::ingestion::clock::MockClock;
+
+        let clock = Arc::new(MockClock::new(1_000));
+        let arena = Arc::new(ZeroCopyArena::new(4096).unwrap().with_leak_detection(Duration::from_secs(5), clock.clone()));
+        let held = ZeroCopyBuffer::new_tagged(100, Arc::clone(&arena), 42).unwrap();
+        clock.advance(Duration::from_secs(3));
+        let recent = ZeroCopyBuffer::new(10, Arc::clone(&arena)).unwrap();
+        assert!(ZeroCopyBuffer::new(8192, Arc::clone(&arena)).is_err());
+        assert!(arena.leaked_buffers().is_empty());
+
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(
+            arena.leaked_buffers(),
+            [BufferInfo { offset: 0, len: 100, tag: 42, age: Duration::from_secs(5) }]
+        );
+        assert_eq!(arena.check_leaks(), 1);
+
+        drop(held);
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(arena.leaked_buffers().iter().map(|info| (info.offset, info.tag)).collect::<Vec<_>>(), [(128, 0)]);
+        drop(recent);
+        assert!(arena.leaked_buffers().is_empty());
+    }
+
+    #[test]
+    fn test_drain_validated() {
+        let ingestor = MarketDataIngestor::new(1024 * 1024, 16);
+        let ts = 1_700_000_000_000_000_000;
+
+        let mut good = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts, 1, 1).to_frame();
+        let mut corrupt = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts + 1, 1, 2).to_frame();
+        let last = corrupt.len() - 1;
+        corrupt[last] ^= 0xFF;
+        let mut unknown = Trade::new(*b"ETHUSD\0\0", 3_000 * 100_000_000, 100_000_000, ts + 2, 1, 3).to_frame();
+
+        for frame in [&mut good, &mut corrupt, &mut unknown] {
+            ingestor.process_raw_data(frame).unwrap();
         }
 
-        let mut buffer = ZeroCopyBuffer::new(total_size, self.arena.clone())?;
-        buffer.as_mut_slice().copy_from_slice(&raw_data[..total_size]);
-        raw_data.advance(total_size);
+        let mut validator = CompositeValidator::new(
+            DataBounds::crypto(),
+            SymbolValidator::whitelist(vec!["BTCUSD".to_string()]),
+        );
+        let results: Vec<_> = ingestor.drain_validated(&mut validator).collect();
+        assert_eq!(results.len(), 3);
+
+        let message = results[0].as_ref().unwrap();
+        assert_eq!(message.trade().unwrap().trade_id(), 1);
+        assert!(matches!(results[1], Err(ValidationError::ChecksumMismatch { .. })));
+        assert!(matches!(results[2], Err(ValidationError::InvalidSymbol(_))));
 
-        if let Err(e) = self.tx.try_send(buffer) {
-            warn!("Canal cheio: {}", e);
+        drop(ingestor);
+        assert_eq!(message.payload().len(), Trade::SIZE);
+    }
+
+    #[test]
+    fn test_concurrent_stats_are_exact() {
+        const THREADS: usize = 8;
+        const PER_THREAD: usize = 500;
+
+        let ingestor = Arc::new(MarketDataIngestor::new(4 * 1024 * 1024, 16));
+        let frame = MessageHeader::builder().frame(&[1u8; 40]);
+        let mut bad_frame = frame.clone();
+        bad_frame[0] ^= 0xFF;
+
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let ingestor = ingestor.clone();
+                let (frame, bad_frame) = (frame.clone(), bad_frame.clone());
+                std::thread::spawn(move || {
+                    for _ in 0..PER_THREAD {
+                        ingestor.process_raw_data(&mut frame.clone()).unwrap();
+                        assert!(ingestor.process_raw_data(&mut bad_frame.clone()).is_err());
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
         }
 
-        let elapsed = start.elapsed();
-        if elapsed.as_micros() > 100 {
-            warn!("Processamento lento: {} μs", elapsed.as_micros());
+        let stats = ingestor.stats();
+        assert_eq!(stats.messages_received, (THREADS * PER_THREAD) as u64);
+        assert_eq!(stats.bytes_received, (THREADS * PER_THREAD * frame.len()) as u64);
+        assert_eq!(stats.parse_errors, (THREADS * PER_THREAD) as u64);
+    }
+
+    #[test]
+    fn test_oversized_allocation_is_too_large_and_not_retried() {
+        let arena = ZeroCopyArena::new(4096).unwrap();
+        assert!(matches!(arena.allocate(4097), Err(ArenaError::TooLarge { requested: 4097, capacity: 4096 })));
+        // Cabe na arena vazia: só está cheia agora
+        let _held = arena.allocate(64).unwrap();
+        assert!(matches!(arena.allocate(4096), Err(ArenaError::Exhausted { requested: 4096, available: 4032 })));
+        assert_eq!(std::io::Error::from(ArenaError::TooLarge { requested: 1, capacity: 0 }).kind(), std::io::ErrorKind::InvalidInput);
+
+        let ingestor = MarketDataIngestor::new(4096, 4).with_retry_policy(RetryPolicy {
+            max_retries: u32::MAX,
+            backoff: Duration::from_millis(1),
+            max_total: Duration::from_secs(60),
+        });
+        let error = ingestor.process_raw_data(&mut MessageHeader::builder().frame(&[0u8; 8192])).unwrap_err();
+        assert!(matches!(error, IngestionError::Arena(ArenaError::TooLarge { capacity: 4096, .. })));
+        assert_eq!(ingestor.stats().allocation_retries, 0);
+    }
+
+    #[test]
+    fn test_placed_regions_reject_overlap() {
+        let arena = ZeroCopyArena::new(4096).unwrap();
+        let first = arena.allocate_at(2048, 100).unwrap();
+        let second = arena.allocate_at(3072, 1024).unwrap();
+        assert_eq!(arena.offset_of(first), Some(2048));
+        assert_eq!(arena.offset_of(second), Some(3072));
+
+        // Cruza o fim (alinhado) da primeira
+        assert!(matches!(arena.allocate_at(2112, 64), Err(ArenaError::Overlap { offset: 2112, .. })));
+        assert!(matches!(arena.allocate_at(1024, 1025), Err(ArenaError::Overlap { .. })));
+        assert!(matches!(arena.allocate_at(100, 64), Err(ArenaError::InvalidLayout { .. })));
+        assert!(matches!(arena.allocate_at(3072, 2048), Err(ArenaError::TooLarge { .. })));
+
+        // O bump para na reserva de menor offset e não a invade
+        arena.allocate(2000).unwrap();
+        assert!(matches!(arena.allocate(64), Err(ArenaError::Exhausted { available: 0, .. })));
+        assert!(matches!(arena.allocate_at(1984, 64), Err(ArenaError::Overlap { .. })));
+
+        // Reservas sobrevivem ao reclaim; liberadas, o bump volta a crescer
+        assert!(arena.try_reclaim());
+        assert!(arena.allocate(2049).is_err());
+        assert!(arena.release_at(2048) && !arena.release_at(2048));
+        assert!(arena.allocate(3072).is_ok());
+    }
+
+    #[test]
+    fn test_retry_succeeds_when_consumer_frees_buffer() {
+        let frame = MessageHeader::builder().frame(&[3u8; 16]);
+        let ingestor = Arc::new(
+            MarketDataIngestor::new(AVX512_ALIGNMENT, 4).with_retry_policy(RetryPolicy {
+                max_retries: u32::MAX,
+                backoff: Duration::from_micros(10),
+                max_total: Duration::from_secs(5),
+            }),
+        );
+        ingestor.process_raw_data(&mut frame.clone()).unwrap();
+
+        let consumer = {
+            let ingestor = ingestor.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(5));
+                let mut validator = CompositeValidator::new(DataBounds::crypto(), SymbolValidator::permissive());
+                ingestor.drain_validated(&mut validator).count()
+            })
+        };
+
+        ingestor.process_raw_data(&mut frame.clone()).unwrap();
+        assert_eq!(consumer.join().unwrap(), 1);
+
+        let stats = ingestor.stats();
+        assert_eq!(stats.messages_received, 2);
+        assert!(stats.allocation_retries > 0);
+        assert_eq!(stats.allocation_drops, 0);
+
+        // Sem retentativas, a mesma situação descarta a mensagem
+        let strict = MarketDataIngestor::new(AVX512_ALIGNMENT, 4);
+        strict.process_raw_data(&mut frame.clone()).unwrap();
+        assert!(strict.process_raw_data(&mut frame.clone()).is_err());
+        assert_eq!(strict.stats().allocation_drops, 1);
+        assert_eq!(strict.stats().allocation_retries, 0);
+    }
+
+    #[test]
+    fn test_stats_report_sub_megabyte_arena_in_bytes() {
+        let ingestor = MarketDataIngestor::new(512 * 1024, 4);
+        ingestor.process_raw_data(&mut MessageHeader::builder().frame(&[0u8; 100])).unwrap();
+
+        let stats = ingestor.stats();
+        assert_eq!(stats.arena_capacity_bytes, 512 * 1024);
+        assert_eq!(stats.arena_used_bytes, (MessageHeader::SIZE + 100).div_ceil(AVX512_ALIGNMENT) * AVX512_ALIGNMENT);
+        assert_eq!(stats.arena_capacity_mb, 1);
+        assert_eq!(stats.arena_used_mb, 1);
+    }
+
+    #[test]
+    fn test_merge_stats_snapshots() {
+        let source = |source| SourceArenaStats { source, used_bytes: 64, capacity_bytes: 1024, allocation_drops: 1 };
+        let a = IngestionStatsSnapshot {
+            messages_received: 10,
+            bytes_received: 1_000,
+            parse_errors: 1,
+            arena_used_mb: 1,
+            arena_capacity_mb: 1,
+            messages_per_second: 100.0,
+            messages_dropped: 2,
+            failed_allocations: 3,
+            allocation_retries: 4,
+            allocation_drops: 5,
+            arena_used_bytes: 600 * 1024,
+            arena_capacity_bytes: 768 * 1024,
+            source_arenas: vec![source(1)],
+            e2e_latency_p99: 900,
+            clock_skew_events: 6,
+            sink_errors: 7,
+            rate_limited_drops: 8,
+            rate_throttled: 9,
+            messages_filtered: 10,
+            alloc_latency_p50: 40,
+            alloc_latency_p99: 300,
+            alloc_latency_max: 5_000,
+        };
+        let b = IngestionStatsSnapshot {
+            messages_received: 5,
+            bytes_received: 500,
+            arena_used_mb: 1,
+            arena_capacity_mb: 1,
+            messages_per_second: 50.5,
+            arena_used_bytes: 600 * 1024,
+            arena_capacity_bytes: 768 * 1024,
+            source_arenas: vec![source(2)],
+            e2e_latency_p99: 1_200,
+            alloc_latency_p50: 60,
+            alloc_latency_p99: 200,
+            alloc_latency_max: 1_000,
+            ..Default::default()
+        };
+
+        let merged = IngestionStatsSnapshot::merge(&[a, b]);
+        assert_eq!((merged.messages_received, merged.bytes_received, merged.parse_errors), (15, 1_500, 1));
+        assert_eq!(merged.messages_per_second, 150.5);
+        assert_eq!(
+            (merged.messages_dropped, merged.failed_allocations, merged.allocation_retries, merged.allocation_drops),
+            (2, 3, 4, 5)
+        );
+        assert_eq!((merged.arena_used_bytes, merged.arena_capacity_bytes), (1200 * 1024, 1536 * 1024));
+        // 1,17 MB e 1,5 MB: recalculados do total, não 1 + 1
+        assert_eq!((merged.arena_used_mb, merged.arena_capacity_mb), (2, 2));
+        assert_eq!(merged.source_arenas, vec![source(1), source(2)]);
+        assert_eq!(
+            (merged.clock_skew_events, merged.sink_errors, merged.rate_limited_drops, merged.rate_throttled),
+            (6, 7, 8, 9)
+        );
+        assert_eq!(merged.messages_filtered, 10);
+        assert_eq!(merged.e2e_latency_p99, 1_200);
+        assert_eq!((merged.alloc_latency_p50, merged.alloc_latency_p99, merged.alloc_latency_max), (60, 300, 5_000));
+
+        let empty = IngestionStatsSnapshot::merge(&[]);
+        assert_eq!((empty.messages_received, empty.messages_per_second, empty.arena_capacity_mb), (0, 0.0, 0));
+    }
+
+    #[test]
+    fn test_subscribers_split_load() {
+        const MESSAGES: u64 = 1_000;
+
+        let ingestor = Arc::new(MarketDataIngestor::new(4 * 1024 * 1024, MESSAGES as usize));
+        let consumers: Vec<_> = (0..2)
+            .map(|_| {
+                let rx = ingestor.subscribe();
+                std::thread::spawn(move || {
+                    (0..MESSAGES / 2)
+                        .map(|_| {
+                            let buffer = rx.recv().unwrap();
+                            u64::from_le_bytes(buffer.as_slice()[MessageHeader::SIZE..].try_into().unwrap())
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        for id in 0..MESSAGES {
+            ingestor.process_raw_data(&mut MessageHeader::builder().frame(&id.to_le_bytes())).unwrap();
         }
 
-        let mut stats = self.stats.lock();
-        stats.messages_received += 1;
-        stats.bytes_received += total_size as u64;
-        stats.last_message_time = Some(Instant::now());
+        let mut seen: Vec<u64> = consumers.into_iter().flat_map(|c| c.join().unwrap()).collect();
+        seen.sort_unstable();
+        assert_eq!(seen, (0..MESSAGES).collect::<Vec<_>>());
+    }
 
-        Ok(())
+    #[test]
+    fn test_broadcast_subscribers_receive_every_message() {
+        let ingestor = MarketDataIngestor::new(1024 * 1024, 4);
+        let first = ingestor.broadcast_subscribe();
+        let second = ingestor.broadcast_subscribe();
+        let dropped = ingestor.broadcast_subscribe();
+        drop(dropped);
+
+        let frame = MessageHeader::builder().frame(&[9u8; 16]);
+        ingestor.process_raw_data(&mut frame.clone()).unwrap();
+
+        assert_eq!(first.try_recv().unwrap(), frame);
+        assert_eq!(second.try_recv().unwrap(), frame);
+        assert_eq!(ingestor.broadcaster.subscriber_count(), 2);
+        // O canal principal continua recebendo normalmente
+        assert_eq!(ingestor.subscribe().try_recv().unwrap().as_slice(), &frame[..]);
     }
 
-    pub fn stats(&self) -> IngestionStatsSnapshot {
-        let stats = self.stats.lock();
-        IngestionStatsSnapshot {
-            messages_received: stats.messages_received,
-            bytes_received: stats.bytes_received,
-            parse_errors: stats.parse_errors,
-            arena_used_mb: self.arena.used() / (1024 * 1024),
-            arena_capacity_mb: self.arena.capacity() / (1024 * 1024),
-            messages_per_second: 0.0,
+    #[test]
+    fn test_broadcast_shares_the_enqueued_buffer() {
+        let ingestor = MarketDataIngestor::new(1024 * 1024, 4);
+        let broadcast = ingestor.broadcast_subscribe();
+        let frame = MessageHeader::builder().frame(&[9u8; 16]);
+        ingestor.process_raw_data(&mut frame.clone()).unwrap();
+
+        // Uma única alocação na arena, vista pelos dois lados
+        assert_eq!(ingestor.arena.used(), frame.len().next_multiple_of(AVX512_ALIGNMENT));
+        let shared = broadcast.try_recv().unwrap();
+        let mut buffer = ingestor.subscribe().try_recv().unwrap();
+        assert_eq!(shared.as_ptr(), buffer.as_slice().as_ptr());
+
+        // Escrever no buffer não altera o que o broadcast vê
+        buffer.as_mut_slice()[MessageHeader::SIZE] = 0;
+        assert_ne!(shared.as_ptr(), buffer.as_slice().as_ptr());
+        assert_eq!(shared, frame);
+        assert_eq!(ingestor.arena.live_buffers(), 2);
+        drop(buffer);
+        assert!(!ingestor.arena.reset());
+        drop(shared);
+        assert!(ingestor.arena.reset());
+    }
+
+    #[test]
+    fn test_buffer_reports_allocation_tag() {
+        let arena = Arc::new(ZeroCopyArena::new(4096).unwrap());
+        let untagged = ZeroCopyBuffer::new(32, arena.clone()).unwrap();
+        let tagged = ZeroCopyBuffer::new_tagged(32, arena.clone(), 0xFEED).unwrap();
+
+        assert_eq!(untagged.tag(), 0);
+        assert_eq!(tagged.tag(), 0xFEED);
+
+        #[cfg(debug_assertions)]
+        assert_eq!(arena.tagged_regions(), vec![(AVX512_ALIGNMENT, 0xFEED)]);
+        drop(tagged);
+        #[cfg(debug_assertions)]
+        assert!(arena.tagged_regions().is_empty());
+    }
+
+    #[test]
+    fn test_process_frame_round_trip() {
+        let ingestor = MarketDataIngestor::new(1024 * 1024, 4);
+        let ts = 1_700_000_000_000_000_000;
+        let trade = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts, 1, 77);
+        let header = MessageHeader::builder().msg_type(Trade::MSG_TYPE).timestamp(ts).build();
+
+        ingestor.process_frame(&header, &trade.to_bytes()).unwrap();
+
+        let mut validator = CompositeValidator::new(DataBounds::crypto(), SymbolValidator::permissive());
+        let messages: Vec<_> = ingestor.drain_validated(&mut validator).collect();
+        assert_eq!(messages.len(), 1);
+        let message = messages[0].as_ref().unwrap();
+        assert_eq!({ message.header.payload_size }, Trade::SIZE as u32);
+        assert_eq!(message.trade().unwrap().trade_id(), 77);
+        assert_eq!(message.buffer().as_slice(), &trade.to_frame()[..]);
+    }
+
+    #[test]
+    fn test_cached_checksum_matches_fresh_computation() {
+        use crate::validation::integrity::ChecksumAlgorithm;
+
+        let ingestor = MarketDataIngestor::new(1024 * 1024, 4);
+        let ts = 1_700_000_000_000_000_000;
+        let payload = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts, 1, 77).to_bytes();
+        let header = MessageHeader::builder().msg_type(Trade::MSG_TYPE).timestamp(ts).build();
+        ingestor.process_frame(&header, &payload).unwrap();
+        ingestor.process_raw_data(&mut MessageHeader::builder().msg_type(Trade::MSG_TYPE).timestamp(ts).frame(&payload)).unwrap();
+
+        let mut validator = CompositeValidator::new(DataBounds::crypto(), SymbolValidator::permissive());
+        let mut messages = ingestor.drain_validated(&mut validator).map(Result::unwrap);
+        let assembled = messages.next().unwrap();
+        let fresh = ChecksumValidator::new().calculate(&payload);
+        assert_eq!(assembled.buffer().cached_checksum(), Some(CachedChecksum { algorithm: ChecksumAlgorithm::Crc32, value: fresh }));
+        // Frames copiados como chegaram não têm CRC calculado pelo ingestor
+        assert_eq!(messages.next().unwrap().buffer().cached_checksum(), None);
+        drop(messages);
+
+        // O validador confia no cache (um valor errado é rejeitado sem olhar os
+        // bytes) e o ignora se o algoritmo difere
+        let frame_header = assembled.header;
+        let stale = Some(CachedChecksum { algorithm: ChecksumAlgorithm::Crc32, value: fresh ^ 1 });
+        assert!(matches!(
+            validator.validate_message_cached(&frame_header, assembled.payload(), stale),
+            Err(ValidationError::ChecksumMismatch { .. })
+        ));
+        let other = Some(CachedChecksum { algorithm: ChecksumAlgorithm::Crc32c, value: fresh ^ 1 });
+        assert!(ChecksumValidator::new().validate_cached(assembled.payload(), fresh, other).is_ok());
+
+        let mut buffer = assembled.into_buffer();
+        buffer.as_mut_slice();
+        assert_eq!(buffer.cached_checksum(), None);
+    }
+
+    #[test]
+    fn test_custom_checksum_threads_through_ingestor() {
+        use crate::ingestion::byte_order::{ByteOrder, Endian};
+        use crate::validation::integrity::{Checksum, ChecksumWidth};
+
+        struct ByteSum;
+        impl Checksum for ByteSum {
+            fn compute(&self, data: &[u8]) -> u64 { data.iter().map(|&b| u64::from(b)).sum() }
+            fn width(&self) -> ChecksumWidth { ChecksumWidth::Bits16 }
+        }
+
+        let ts = 1_700_000_000_000_000_000;
+        let trade = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts, 1, 1);
+        let order = ByteOrder { header_endian: Endian::Little, payload_endian: Endian::Big };
+        let ingestor = MarketDataIngestor::new(1024 * 1024, 4).with_byte_order(order).with_custom_checksum(ByteSum);
+
+        // Montado pelo ingestor
+        let header = MessageHeader::builder().msg_type(Trade::MSG_TYPE).version(MessageHeader::VERSION_HEADER_CHECKSUM).timestamp(ts).build();
+        ingestor.process_frame(&header, &trade.to_bytes()).unwrap();
+        // Convertido de big-endian: o checksum do fio também é o plugado
+        let wire = struct_bytes(&trade.swap_bytes()).to_vec();
+        let mut frame = MessageHeader::builder().msg_type(Trade::MSG_TYPE).timestamp(ts).payload(&wire).checksum(ByteSum.value(&wire)).build().to_bytes().to_vec();
+        frame.extend_from_slice(&wire);
+        ingestor.process_raw_data(&mut BytesMut::from(&frame[..])).unwrap();
+
+        let mut validator = CompositeValidator::builder().custom_checksum(ByteSum).build();
+        let messages: Vec<_> = ingestor.drain_validated(&mut validator).map(Result::unwrap).collect();
+        assert_eq!(messages.len(), 2);
+        for message in &messages {
+            assert_eq!(message.payload(), &trade.to_bytes()[..]);
+            assert_eq!(message.buffer().cached_checksum(), None);
+            let mut crc = CompositeValidator::builder().build();
+            assert!(matches!(crc.validate_message(&message.header, message.payload()), Err(ValidationError::ChecksumMismatch { .. })));
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_end_of_session_fires_handlers() {
+        let ingestor = MarketDataIngestor::new(1024 * 1024, 4);
+        let ended = Arc::new(Mutex::new(Vec::new()));
+        {
+            let ended = ended.clone();
+            ingestor.on_session_end(move |event| ended.lock().push(*event));
+        }
+
+        let event = EndOfSession { session_id: 20240102, timestamp: 1_700_000_000_000_000_000 };
+        let mut data = MessageHeader::builder().frame(&[1u8; 8]);
+        data.extend_from_slice(&event.to_frame());
+        ingestor.process_raw_data(&mut data).unwrap();
+        ingestor.process_raw_data(&mut data).unwrap();
+        assert!(data.is_empty());
+
+        assert_eq!(*ended.lock(), vec![event]);
+        // Só a mensagem de dados chega ao canal
+        let rx = ingestor.subscribe();
+        assert!(rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_err());
+    }
 
     #[test]
-    fn test_arena_allocation() {
-        let arena = ZeroCopyArena::new(1024).unwrap();
-        assert_eq!(arena.capacity(), 1024);
+    fn test_corrupted_end_of_session_does_not_fire_handlers() {
+        let ingestor = MarketDataIngestor::new(64 * 1024, 16);
+        let ended = Arc::new(AtomicU64::new(0));
+        {
+            let ended = ended.clone();
+            ingestor.on_session_end(move |_| { ended.fetch_add(1, Ordering::Relaxed); });
+        }
+        let ts = 1_700_000_000_000_000_000;
 
-        let buf1 = arena.allocate(64).unwrap();
-        let buf2 = arena.allocate(128).unwrap();
+        let mut payload_flip = EndOfSession { session_id: 7, timestamp: ts }.to_frame();
+        payload_flip[MessageHeader::SIZE] ^= 0xFF;
+        let err = ingestor.process_raw_data(&mut payload_flip).unwrap_err();
+        assert!(matches!(err, IngestionError::Validation(ValidationError::ChecksumMismatch { .. })));
+        assert!(payload_flip.is_empty());
 
-        assert_ne!(buf1.as_ptr(), buf2.as_ptr());
-        assert_eq!(buf1.as_ptr() as usize % 64, 0);
+        let mut forged = MessageHeader::builder().msg_type(EndOfSession::MSG_TYPE).version(2).timestamp(ts).frame(&7u64.to_le_bytes());
+        forged[8] ^= 0x01;
+        let err = ingestor.process_raw_data(&mut forged).unwrap_err();
+        assert!(matches!(err, IngestionError::Validation(ValidationError::HeaderChecksumMismatch { .. })));
+
+        assert_eq!(ended.load(Ordering::Relaxed), 0);
+        assert_eq!(ingestor.stats().parse_errors, 2);
+        let mut intact = MessageHeader::builder().msg_type(EndOfSession::MSG_TYPE).version(2).timestamp(ts).frame(&7u64.to_le_bytes());
+        ingestor.process_raw_data(&mut intact).unwrap();
+        assert_eq!(ended.load(Ordering::Relaxed), 1);
     }
 
     #[test]
-    fn test_header_validation() {
-        let header = MessageHeader {
-            magic: MessageHeader::MAGIC,
-            msg_type: 0,
-            version: 1,
-            priority: 0,
-            flags: 0,
-            timestamp: 0,
-            payload_size: 100,
-            checksum: 0,
+    fn test_header_integrity_distinguishes_corruption_from_protocol() {
+        let header = MessageHeader::builder().payload(&[1u8; 8]).build();
+        assert!(header.check_integrity().is_ok());
+
+        let mut corrupted = header;
+        corrupted.magic ^= 0xFF << 8;
+        assert!(matches!(corrupted.check_integrity(), Err(ValidationError::CorruptedFormat)));
+
+        let other_protocol = MessageHeader::builder().magic(u32::from_le_bytes(*b"HTTP")).payload(&[1u8; 8]).build();
+        assert!(matches!(
+            other_protocol.check_integrity(),
+            Err(ValidationError::UnknownProtocol { magic }) if magic == u32::from_le_bytes(*b"HTTP")
+        ));
+
+        let empty = MessageHeader::builder().build();
+        assert!(matches!(empty.check_integrity(), Err(ValidationError::CorruptedFormat)));
+    }
+
+    #[test]
+    fn test_tolerant_batch_resyncs_past_corrupt_frame() {
+        let ingestor = MarketDataIngestor::new(64 * 1024, 16);
+        let rx = ingestor.subscribe();
+        let ts = 1_700_000_000_000_000_000;
+        let frames: Vec<BytesMut> = (0..3).map(|i| Trade::new(*b"BTCUSD\0\0", 1, 1, ts + i, 1, i).to_frame()).collect();
+        let mut middle = frames[1].clone();
+        middle[1] ^= 0xFF;
+
+        let mut batch = BytesMut::new();
+        for frame in [&frames[0], &middle, &frames[2]] {
+            batch.extend_from_slice(frame);
+        }
+        let report = ingestor.process_batch_tolerant(&mut batch);
+
+        assert_eq!(report.ingested, 2);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].0, frames[0].len());
+        assert!(matches!(report.errors[0].1, IngestionError::Validation(ValidationError::CorruptedFormat)));
+        assert_eq!(report.skipped_bytes, middle.len());
+        assert!(batch.is_empty());
+
+        let received: Vec<u64> = rx.try_iter().map(|buffer| ParsedMessage::from_buffer(buffer).unwrap().trade().unwrap().trade_id()).collect();
+        assert_eq!(received, [0, 2]);
+
+        // Frame truncado no fim: não há magic depois dele
+        let mut tail = BytesMut::from(&frames[0][..]);
+        tail.extend_from_slice(&frames[1][..30]);
+        let report = ingestor.process_batch_tolerant(&mut tail);
+        assert_eq!((report.ingested, report.errors.len(), report.skipped_bytes), (1, 1, 30));
+        assert!(matches!(report.errors[0], (offset, IngestionError::Incomplete { .. }) if offset == frames[0].len()));
+
+        // `payload_size` corrompido no meio: o frame seguinte ainda é lido
+        let mut oversized = frames[1].clone();
+        oversized[16..20].copy_from_slice(&(3 * Trade::SIZE as u32).to_le_bytes());
+        let mut batch = BytesMut::new();
+        for frame in [&frames[0], &oversized, &frames[2]] {
+            batch.extend_from_slice(frame);
+        }
+        let report = ingestor.process_batch_tolerant(&mut batch);
+        assert_eq!((report.ingested, report.errors.len(), report.skipped_bytes), (2, 1, oversized.len()));
+        assert!(matches!(report.errors[0], (offset, IngestionError::Incomplete { .. }) if offset == frames[0].len()));
+        let received: Vec<u64> = rx.try_iter().map(|buffer| ParsedMessage::from_buffer(buffer).unwrap().trade().unwrap().trade_id()).collect();
+        assert_eq!(received, [0, 0, 2]);
+    }
+
+    #[test]
+    fn test_ingestion_error_variants_and_ffi_codes() {
+        let ingestor = MarketDataIngestor::new(AVX512_ALIGNMENT, 4);
+        let frame = MessageHeader::builder().frame(&[1u8; 16]);
+
+        let short = ingestor.process_raw_data(&mut BytesMut::from(&frame[..10])).unwrap_err();
+        assert!(matches!(short, IngestionError::Incomplete { needed: MessageHeader::SIZE, available: 10 }));
+        assert_eq!(short.ffi_code(), IngestionError::FFI_INCOMPLETE);
+
+        let truncated = ingestor.process_raw_data(&mut BytesMut::from(&frame[..30])).unwrap_err();
+        assert!(matches!(truncated, IngestionError::Incomplete { needed: 40, available: 30 }));
+
+        let mut foreign = frame.clone();
+        foreign[..4].copy_from_slice(b"HTTP");
+        let foreign = ingestor.process_raw_data(&mut foreign).unwrap_err();
+        assert!(matches!(foreign, IngestionError::Validation(ValidationError::UnknownProtocol { .. })));
+        assert_eq!(foreign.ffi_code(), IngestionError::FFI_VALIDATION);
+
+        ingestor.process_raw_data(&mut frame.clone()).unwrap();
+        let exhausted = ingestor.process_raw_data(&mut frame.clone()).unwrap_err();
+        assert!(matches!(exhausted, IngestionError::Arena(ArenaError::Exhausted { .. })));
+        assert_eq!(exhausted.ffi_code(), IngestionError::FFI_ARENA);
+
+        let io: std::io::Error = IngestionError::from(ArenaError::Exhausted { requested: 1, available: 0 }).into();
+        assert_eq!(io.kind(), std::io::ErrorKind::OutOfMemory);
+    }
+
+    #[test]
+    fn test_pause_rejects_without_dropping_and_resume_continues() {
+        let ingestor = MarketDataIngestor::new(64 * 1024, 16);
+        let rx = ingestor.subscribe();
+        let ts = 1_700_000_000_000_000_000;
+        let trade = |id: u64| Trade::new(*b"BTCUSD\0\0", 1, 1, ts + id, 1, id);
+        ingestor.process_raw_data(&mut trade(1).to_frame()).unwrap();
+
+        ingestor.pause();
+        assert!(ingestor.is_paused());
+        let mut held = trade(2).to_frame();
+        let error = ingestor.process_raw_data(&mut held).unwrap_err();
+        assert!(matches!(error, IngestionError::Paused));
+        assert_eq!(error.ffi_code(), IngestionError::FFI_PAUSED);
+        assert_eq!(held.len(), MessageHeader::SIZE + Trade::SIZE);
+        let header = MessageHeader::builder().msg_type(Trade::MSG_TYPE).timestamp(ts).build();
+        assert!(matches!(ingestor.process_frame(&header, &trade(3).to_bytes()), Err(IngestionError::Paused)));
+        let report = ingestor.process_batch_tolerant(&mut trade(4).to_frame());
+        assert!(report.ingested == 0 && matches!(report.errors[..], [(0, IngestionError::Paused)]));
+
+        let stats = ingestor.stats();
+        assert_eq!((stats.messages_received, stats.messages_dropped, stats.parse_errors), (1, 0, 0));
+        assert_eq!(rx.len(), 1);
+
+        // Retomado, o frame retido é reapresentado e a ingestão segue
+        ingestor.resume();
+        ingestor.process_raw_data(&mut held).unwrap();
+        ingestor.process_raw_data(&mut trade(5).to_frame()).unwrap();
+        let ids: Vec<u64> = rx.try_iter().map(|buffer| ParsedMessage::from_buffer(buffer).unwrap().trade().unwrap().trade_id()).collect();
+        assert_eq!(ids, [1, 2, 5]);
+    }
+
+    #[test]
+    fn test_ingestor_over_different_arenas() {
+        use crate::ingestion::fixed_arena::FixedArena;
+
+        fn exercise<A: Arena>(ingestor: MarketDataIngestor<A>) {
+            let rx = ingestor.subscribe();
+            let frame = MessageHeader::builder().msg_type(Trade::MSG_TYPE).frame(&[9u8; 40]);
+            for _ in 0..4 {
+                ingestor.process_raw_data(&mut frame.clone()).unwrap();
+            }
+            assert_eq!(ingestor.stats().arena_used_bytes, 4 * AVX512_ALIGNMENT);
+
+            let received: Vec<_> = rx.try_iter().collect();
+            assert_eq!(received.len(), 4);
+            assert!(received.iter().all(|buffer| buffer.as_slice() == &frame[..]));
+
+            // Com buffers vivos a arena não rebobina; sem eles, sim
+            assert!(!ingestor.arena.reset());
+            drop(received);
+            assert!(ingestor.arena.reset());
+            assert_eq!(ingestor.stats().arena_used_bytes, 0);
+        }
+
+        exercise(MarketDataIngestor::new(64 * 1024, 16));
+        exercise(MarketDataIngestor::with_arena(FixedArena::<{ 64 * 1024 }>::new(), 16));
+    }
+
+    #[test]
+    fn test_end_to_end_latency() {
+        use crate::ingestion::clock::MockClock;
+
+        let now = 1_700_000_000_000_000_000;
+        let ingestor = MarketDataIngestor::new(64 * 1024, 16).with_clock(Arc::new(MockClock::new(now)));
+        let _rx = ingestor.subscribe();
+
+        // 2^20 ns é limite exato de bucket do histograma
+        let latency = 1 << 20;
+        let frame = MessageHeader::builder().msg_type(Trade::MSG_TYPE).timestamp(now - latency).frame(&[1u8; 40]);
+        ingestor.process_raw_data(&mut frame.clone()).unwrap();
+        assert_eq!(ingestor.stats().e2e_latency_p99, latency);
+
+        // Produtor em microssegundos: mesma latência depois da normalização
+        let micros = MessageHeader::builder().msg_type(Trade::MSG_TYPE).timestamp((now - latency) / 1_000).frame(&[1u8; 40]);
+        ingestor.process_raw_data(&mut micros.clone()).unwrap();
+        assert!(ingestor.stats().e2e_latency_p99 <= latency + 1_000);
+
+        // Relógio do produtor adiantado: latência 0, contado como skew
+        let future = MessageHeader::builder().msg_type(Trade::MSG_TYPE).timestamp(now + 5_000).frame(&[1u8; 40]);
+        ingestor.process_raw_data(&mut future.clone()).unwrap();
+        let stats = ingestor.stats();
+        assert_eq!(stats.clock_skew_events, 1);
+        assert_eq!(ingestor.stats.e2e_latency.percentile(0.0), 0);
+        assert_eq!(ingestor.stats.e2e_latency.count(), 3);
+    }
+
+    #[test]
+    fn test_reassembly_across_calls() {
+        let ingestor = MarketDataIngestor::new(64 * 1024, 16).with_reassembly(1024);
+        let rx = ingestor.subscribe();
+        let ts = 1_700_000_000_000_000_000;
+        let frame = Trade::new(*b"BTCUSD\0\0", 1, 1, ts, 1, 1).to_frame();
+
+        // Header partido ao meio, depois o resto do header com parte do payload
+        for chunk in [&frame[..10], &frame[10..30]] {
+            let mut raw = BytesMut::from(chunk);
+            assert!(matches!(ingestor.process_raw_data(&mut raw), Err(IngestionError::Incomplete { .. })));
+            assert!(raw.is_empty());
+            assert!(rx.is_empty());
+        }
+        ingestor.process_raw_data(&mut BytesMut::from(&frame[30..])).unwrap();
+        assert_eq!(rx.try_recv().unwrap().as_slice(), &frame[..]);
+        assert_eq!(ingestor.stats().messages_received, 1);
+
+        // Frame que nunca caberia no limite é descartado em vez de acumulado
+        let huge = MessageHeader::builder().msg_type(Trade::MSG_TYPE).frame(&[0u8; 2048]);
+        let err = ingestor.process_raw_data(&mut BytesMut::from(&huge[..100])).unwrap_err();
+        assert!(matches!(err, IngestionError::Validation(ValidationError::CorruptedFormat)));
+        ingestor.process_raw_data(&mut frame.clone()).unwrap();
+        assert_eq!(ingestor.stats().messages_received, 2);
+    }
+
+    #[test]
+    fn test_reassembly_resyncs_corrupt_stash_and_drains_whole_frames() {
+        let ingestor = MarketDataIngestor::new(64 * 1024, 16).with_reassembly(1024);
+        let rx = ingestor.subscribe();
+        let ts = 1_700_000_000_000_000_000;
+        let frame = |id: u64| Trade::new(*b"BTCUSD\0\0", 1, 1, ts + id, 1, id).to_frame();
+
+        // Header com payload_size 0 chega partido: rejeitado ao se completar,
+        // o stash ressincroniza no frame seguinte em vez de travar a fonte
+        let mut corrupt = frame(1);
+        corrupt[16..20].copy_from_slice(&0u32.to_le_bytes());
+        let mut head = BytesMut::from(&corrupt[..10]);
+        assert!(matches!(ingestor.process_raw_data(&mut head), Err(IngestionError::Incomplete { .. })));
+        let mut rest = BytesMut::from(&corrupt[10..]);
+        rest.extend_from_slice(&frame(2));
+        let err = ingestor.process_raw_data(&mut rest).unwrap_err();
+        assert!(matches!(err, IngestionError::Validation(ValidationError::CorruptedFormat)));
+        assert!(rest.is_empty());
+        assert_eq!(rx.len(), 1);
+
+        // Dois frames inteiros e o começo de um terceiro num único pedaço
+        let mut head = BytesMut::from(&frame(3)[..10]);
+        assert!(matches!(ingestor.process_raw_data(&mut head), Err(IngestionError::Incomplete { .. })));
+        let mut chunk = BytesMut::from(&frame(3)[10..]);
+        chunk.extend_from_slice(&frame(4));
+        chunk.extend_from_slice(&frame(5)[..5]);
+        assert!(matches!(ingestor.process_raw_data(&mut chunk), Err(IngestionError::Incomplete { available: 5, .. })));
+        assert_eq!(rx.len(), 3);
+        ingestor.process_raw_data(&mut BytesMut::from(&frame(5)[5..])).unwrap();
+
+        let ids: Vec<u64> = rx.try_iter().map(|buffer| ParsedMessage::from_buffer(buffer).unwrap().trade().unwrap().trade_id()).collect();
+        assert_eq!(ids, [2, 3, 4, 5]);
+    }
+
+    #[cfg(feature = "alloc-timing")]
+    #[test]
+    fn test_allocation_latency_percentiles() {
+        let ingestor = MarketDataIngestor::new(64 * 1024, 16).with_slow_allocation_threshold(0);
+        let _rx = ingestor.subscribe();
+        assert_eq!(ingestor.stats().alloc_latency_max, 0);
+
+        let ts = 1_700_000_000_000_000_000;
+        for i in 0..10 {
+            let mut frame = Trade::new(*b"BTCUSD\0\0", 1, 1, ts + i, 1, i).to_frame();
+            ingestor.process_raw_data(&mut frame).unwrap();
+        }
+        let stats = ingestor.stats();
+        assert_eq!(ingestor.stats.alloc_latency.count(), 10);
+        assert!(stats.alloc_latency_max > 0);
+        assert!(stats.alloc_latency_p50 <= stats.alloc_latency_p99);
+        assert!(stats.alloc_latency_p99 <= stats.alloc_latency_max);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_warmup_prefaults_arena_without_touching_stats() {
+        let capacity = 4 * 1024 * 1024;
+        let ingestor = MarketDataIngestor::new(capacity, 16);
+        let rx = ingestor.subscribe();
+        let broadcast = ingestor.broadcast_subscribe();
+
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+        let base = ingestor.arena.base_ptr.as_ptr() as usize;
+        let start = base / page_size * page_size;
+        let pages = (base + capacity - start).div_ceil(page_size);
+        let resident_pages = || {
+            let mut residency = vec![0u8; pages];
+            let rc = unsafe { libc::mincore(start as *mut libc::c_void, pages * page_size, residency.as_mut_ptr()) };
+            assert_eq!(rc, 0);
+            residency.iter().filter(|&&page| page & 1 == 1).count()
         };
 
-        assert!(header.is_valid());
+        assert_eq!(ingestor.warmup(1_000), 1);
+        assert_eq!(resident_pages(), pages);
+
+        let stats = ingestor.stats();
+        assert_eq!((stats.messages_received, stats.bytes_received, stats.parse_errors), (0, 0, 0));
+        assert_eq!((stats.failed_allocations, stats.allocation_drops, stats.arena_used_bytes), (0, 0, 0));
+        assert_eq!(stats.e2e_latency_p99, 0);
+        assert!(rx.is_empty() && broadcast.is_empty());
+
+        // Com um buffer vivo a arena não é tocada
+        let held = ZeroCopyBuffer::new(64, Arc::clone(&ingestor.arena)).unwrap();
+        assert_eq!(ingestor.warmup(10), 0);
+        assert_eq!(ingestor.stats().arena_used_bytes, 64);
+        drop(held);
     }
 
     #[test]
-    fn test_zero_copy_buffer() {
-        let arena = std::sync::Arc::new(ZeroCopyArena::new(1024).unwrap());
-        let mut buffer = ZeroCopyBuffer::new(128, arena).unwrap();
+    fn test_reset_counters_returns_previous_values() {
+        let ingestor = Arc::new(MarketDataIngestor::new(64 * 1024, 16));
+        let mut bad = Trade::new(*b"BTCUSD\0\0", 1, 1, 1, 1, 1).to_frame();
+        bad[0] ^= 0xFF;
+        for _ in 0..3 {
+            let _ = ingestor.process_raw_data(&mut bad.clone());
+        }
+        let good = Trade::new(*b"BTCUSD\0\0", 1, 1, 1_700_000_000_000_000_000, 1, 1).to_frame();
+        ingestor.process_raw_data(&mut good.clone()).unwrap();
 
-        buffer.as_mut_slice().copy_from_slice(&[42u8; 128]);
-        let slice = buffer.as_slice();
-        assert_eq!(slice.len(), 128);
-        assert_eq!(slice[0], 42);
+        assert_eq!(ingestor.parse_error_count(), 3);
+        assert_eq!(ingestor.reset_parse_errors(), 3);
+        assert_eq!(ingestor.parse_error_count(), 0);
+        assert_eq!(ingestor.reset_messages_received(), 1);
+        assert_eq!(ingestor.reset_bytes_received(), good.len() as u64);
+        assert_eq!((ingestor.messages_received_count(), ingestor.bytes_received_count()), (0, 0));
+
+        // Resets concorrentes com a ingestão: a soma dos deltas é o total
+        let writer = {
+            let ingestor = Arc::clone(&ingestor);
+            let bad = bad.clone();
+            std::thread::spawn(move || {
+                for _ in 0..10_000 {
+                    let _ = ingestor.process_raw_data(&mut bad.clone());
+                }
+            })
+        };
+        let mut total = 0;
+        while !writer.is_finished() {
+            total += ingestor.reset_parse_errors();
+        }
+        writer.join().unwrap();
+        total += ingestor.reset_parse_errors();
+        assert_eq!(total, 10_000);
+    }
+
+    #[test]
+    fn test_page_warmer_sweeps_without_touching_arena() {
+        let arena = Arc::new(ZeroCopyArena::new(1024 * 1024).unwrap());
+        let mut held = ZeroCopyBuffer::new(128, Arc::clone(&arena)).unwrap();
+        held.as_mut_slice().fill(0xAB);
+        let before = arena.stats();
+
+        let warmer = arena.spawn_page_warmer(Duration::from_millis(1)).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while warmer.sweeps() < 3 && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert!(warmer.sweeps() >= 3);
+        assert_eq!(arena.stats(), before);
+        assert_eq!(arena.used(), 128.max(AVX512_ALIGNMENT));
+        assert!(held.as_slice().iter().all(|&byte| byte == 0xAB));
+
+        // O drop para a thread
+        drop(warmer);
+        drop(held);
+        assert_eq!(Arc::strong_count(&arena), 1);
+    }
+
+    #[test]
+    fn test_layered_frames_through_ingestor() {
+        let ingestor = MarketDataIngestor::new(64 * 1024, 16);
+        let ts = 1_700_000_000_000_000_000;
+        let trade = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts, 1, 1);
+        let builder = MessageHeader::builder().msg_type(Trade::MSG_TYPE).version(2).timestamp(ts);
+        let header = builder.build();
+
+        let mut raw = builder.frame(&trade.to_bytes());
+        raw.extend_from_slice(&Trade::new(*b"BTCUSD\0\0", 1, 1, ts + 1, 1, 2).to_frame());
+        ingestor.process_raw_data(&mut raw).unwrap();
+        ingestor.process_raw_data(&mut raw).unwrap();
+        assert!(raw.is_empty());
+        ingestor.process_frame(&header, &trade.to_bytes()).unwrap();
+
+        let mut validator = CompositeValidator::builder().build();
+        let messages: Vec<_> = ingestor.drain_validated(&mut validator).map(Result::unwrap).collect();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0].payload(), trade.to_bytes());
+        assert_eq!(messages[1].trade().unwrap().trade_id(), 2);
+        assert_eq!(messages[2].buffer().as_slice(), messages[0].buffer().as_slice());
+    }
+
+    #[test]
+    fn test_fallible_constructors_return_err() {
+        assert_eq!(ZeroCopyArena::new(usize::MAX).err().unwrap().kind(), std::io::ErrorKind::InvalidInput);
+        assert!(ZeroCopyArena::new(isize::MAX as usize).is_err());
+        assert!(MarketDataIngestor::try_new(usize::MAX, 4).is_err());
+        assert!(MarketDataIngestor::try_new(1024, 4)
+            .unwrap()
+            .with_source_arenas(HashMap::from([(1, usize::MAX)]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_alignment() {
+        let arena = ZeroCopyArena::new(4096).unwrap();
+        for size in [1, 63, 64, 65, 1000] {
+            let ptr = arena.allocate(size).unwrap();
+            assert!(arena.verify_alignment(ptr, size));
+        }
+        let at = |offset: usize| unsafe { NonNull::new_unchecked(arena.region_base().add(offset)) };
+        assert!(!arena.verify_alignment(at(1), 8));
+        assert!(!arena.verify_alignment(at(32), 8));
+        assert!(arena.verify_alignment(at(4096 - 64), 64));
+        assert!(!arena.verify_alignment(at(4096 - 64), 65));
+        assert!(!arena.verify_alignment(at(4096), 1));
+        assert!(arena.verify_alignment(zero_sized_ptr(), 0));
+    }
+
+    /// Região com base desalinhada: toda alocação viola o invariante.
+    #[repr(align(64))]
+    struct MisalignedRegion {
+        bytes: [u8; 256],
+        offset: AtomicU64,
+        live: AtomicU64,
+    }
+
+    // SAFETY: viola de propósito o alinhamento da base; o teste só chama
+    // `bump`, que entra em pânico antes de qualquer acesso à memória.
+    unsafe impl BumpAllocator for MisalignedRegion {
+        fn region_base(&self) -> *mut u8 { self.bytes.as_ptr().wrapping_add(1).cast_mut() }
+        fn region_capacity(&self) -> usize { 128 }
+        fn region_offset(&self) -> &AtomicU64 { &self.offset }
+        fn region_live_buffers(&self) -> &AtomicU64 { &self.live }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "viola alinhamento")]
+    fn test_bump_asserts_alignment_in_debug() {
+        let region = MisalignedRegion { bytes: [0; 256], offset: AtomicU64::new(0), live: AtomicU64::new(0) };
+        let _ = region.bump(8);
+    }
+
+    #[test]
+    fn test_tiny_arenas_and_zero_length_allocations() {
+        for capacity in [0, AVX512_ALIGNMENT - 1] {
+            let err = ZeroCopyArena::new(capacity).err().unwrap();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+            assert!(err.to_string().contains("não comporta uma alocação alinhada"), "{}", err);
+        }
+        assert!(MarketDataIngestor::try_new(0, 4).is_err());
+
+        let arena = Arc::new(ZeroCopyArena::new(MIN_ARENA_CAPACITY).unwrap().with_leak_detection(Duration::ZERO, Arc::new(SystemClock)));
+        let empty = ZeroCopyBuffer::new(0, Arc::clone(&arena)).unwrap();
+        assert!(empty.as_slice().is_empty());
+        assert_eq!(empty.as_slice().as_ptr() as usize % AVX512_ALIGNMENT, 0);
+        assert_eq!(arena.used(), 0);
+
+        // Arena cheia ainda entrega buffers vazios, sem consumir nem registrar nada
+        let full = ZeroCopyBuffer::new(MIN_ARENA_CAPACITY, Arc::clone(&arena)).unwrap();
+        let after = ZeroCopyBuffer::new_tagged(0, Arc::clone(&arena), 7).unwrap();
+        assert_eq!((arena.used(), arena.failed_allocations(), arena.leaked_buffers().len()), (MIN_ARENA_CAPACITY, 0, 1));
+        drop((empty, full, after));
+        assert!(arena.leaked_buffers().is_empty());
+    }
+
+    #[test]
+    fn test_arena_dump_round_trip() {
+        let ingestor = MarketDataIngestor::new(64 * 1024, 16);
+        let ts = 1_700_000_000_000_000_000;
+        let frames: Vec<_> = (0..3)
+            .map(|i| Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts + i, 1, i).to_frame())
+            .collect();
+        for frame in &frames {
+            ingestor.process_raw_data(&mut frame.clone()).unwrap();
+        }
+
+        let mut out = Vec::new();
+        ingestor.arena.dump(&mut out).unwrap();
+        let dump = ArenaDump::load_dump(&mut out.as_slice()).unwrap();
+
+        assert_eq!(dump.capacity, 64 * 1024);
+        assert_eq!(dump.offset, ingestor.arena.used());
+        assert_eq!(dump.alignment, AVX512_ALIGNMENT);
+        let recovered: Vec<&[u8]> = dump.frames().collect();
+        assert_eq!(recovered, frames.iter().map(|f| &f[..]).collect::<Vec<_>>());
+
+        out[0] ^= 0xFF;
+        assert!(ArenaDump::load_dump(&mut out.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_per_source_arenas_shed_independently() {
+        let ingestor = MarketDataIngestor::new(1024 * 1024, 1024)
+            .with_source_arenas(HashMap::from([(1, 4 * AVX512_ALIGNMENT), (2, 64 * 1024)]))
+            .unwrap();
+        let frame = MessageHeader::builder().frame(&[5u8; 16]);
+
+        let mut small_errors = 0;
+        for _ in 0..100 {
+            if ingestor.process_raw_data_from(1, &mut frame.clone()).is_err() {
+                small_errors += 1;
+            }
+            ingestor.process_raw_data_from(2, &mut frame.clone()).unwrap();
+        }
+        assert_eq!(small_errors, 96);
+
+        let stats = ingestor.stats();
+        assert_eq!(stats.source_arenas.len(), 2);
+        let (small, large) = (stats.source_arenas[0], stats.source_arenas[1]);
+        assert_eq!((small.source, small.capacity_bytes, small.used_bytes), (1, 256, 256));
+        assert_eq!(small.allocation_drops, 96);
+        assert_eq!((large.source, large.allocation_drops), (2, 0));
+        assert_eq!(large.used_bytes, 100 * AVX512_ALIGNMENT);
+        assert_eq!(stats.arena_used_bytes, 0);
+    }
+
+    #[test]
+    fn test_packed_getters() {
+        let trade = Trade::new(*b"ETHUSD\0\0", -5, 7, 11, 2, u64::MAX);
+        assert_eq!(trade.symbol(), *b"ETHUSD\0\0");
+        assert_eq!((trade.price(), trade.quantity(), trade.timestamp()), (-5, 7, 11));
+        assert_eq!((trade.side(), trade.trade_id()), (2, u64::MAX));
+
+        let quote = Quote::new(*b"ETHUSD\0\0", 100, 1, 101, 2, 99);
+        assert_eq!(quote.symbol(), *b"ETHUSD\0\0");
+        assert_eq!((quote.bid_price(), quote.bid_quantity()), (100, 1));
+        assert_eq!((quote.ask_price(), quote.ask_quantity(), quote.timestamp()), (101, 2, 99));
+
+        // Leitura a partir de um offset ímpar do buffer de origem
+        let mut bytes = [0u8; Trade::SIZE + 1];
+        bytes[1..].copy_from_slice(&trade.to_bytes());
+        let unaligned: Trade = read_struct(&bytes[1..]).unwrap();
+        assert_eq!(unaligned.trade_id(), u64::MAX);
+        assert_eq!(unaligned.price(), -5);
+    }
+
+    #[test]
+    fn test_read_struct_lengths() {
+        let frame = Trade::new(*b"BTCUSD\0\0", 7, 8, 9, 1, 10).to_frame();
+
+        let exact: MessageHeader = read_struct(&frame[..MessageHeader::SIZE]).unwrap();
+        assert_eq!(exact.to_bytes(), frame[..MessageHeader::SIZE]);
+
+        assert!(matches!(
+            read_struct::<MessageHeader>(&frame[..MessageHeader::SIZE - 1]),
+            Err(ValidationError::CorruptedFormat)
+        ));
+        assert!(matches!(read_struct::<Trade>(&[]), Err(ValidationError::CorruptedFormat)));
+
+        // Bytes excedentes são ignorados
+        let oversized: MessageHeader = read_struct(&frame).unwrap();
+        assert_eq!(oversized.to_bytes(), exact.to_bytes());
+        let trade: Trade = read_struct(&frame[MessageHeader::SIZE..]).unwrap();
+        assert_eq!((trade.price(), trade.trade_id()), (7, 10));
     }
 }
@@ -0,0 +1,179 @@
+//! Log de auditoria em disco das mensagens rejeitadas pela validação
+
+use std::ffi::OsString;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::validation::integrity::{ValidationError, ValidationErrorKind};
+
+/// Uma rejeição registrada: uma linha JSON no arquivo de auditoria.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Instante da rejeição, em ns desde a época Unix (relógio do validador).
+    pub timestamp: u64,
+    pub source: u8,
+    pub msg_type: u8,
+    pub kind: ValidationErrorKind,
+    /// `Display` do erro, com os valores que causaram a rejeição.
+    pub reason: String,
+    /// Frame completo (header + payload) em hexadecimal.
+    pub frame_hex: String,
+}
+
+impl AuditEntry {
+    /// Bytes do frame rejeitado; `None` se `frame_hex` não for hex válido.
+    pub fn frame(&self) -> Option<Vec<u8>> {
+        let hex = self.frame_hex.as_bytes();
+        if !hex.len().is_multiple_of(2) {
+            return None;
+        }
+        hex.chunks_exact(2)
+            .map(|pair| std::str::from_utf8(pair).ok().and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+            .collect()
+    }
+}
+
+/// Registro durável e só-de-acréscimo das mensagens rejeitadas (bytes brutos,
+/// motivo e instante), para compliance; complementa as contagens em memória
+/// de `MessageTypeStats`. Ligado ao validador por
+/// `CompositeValidatorBuilder::audit_log` e compartilhável entre validadores.
+///
+/// Cada entrada é montada inteira e gravada com `write_all` (que pode levar
+/// mais de um `write`) sob o lock do logger, no arquivo aberto em modo append:
+/// entradas do mesmo logger não se intercalam. Quando a próxima entrada
+/// passaria de `max_bytes`, o arquivo gira: `audit.log` vira `audit.log.1`,
+/// `audit.log.1` vira `audit.log.2` e assim por diante até `max_files`
+/// arquivos antigos; o mais velho é apagado.
+pub struct AuditLogger {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    /// Arquivo corrente e quantos bytes ele já tem.
+    file: Mutex<(File, u64)>,
+}
+
+impl AuditLogger {
+    pub fn open(path: impl AsRef<Path>, max_bytes: u64, max_files: usize) -> std::io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self { path, max_bytes, max_files, file: Mutex::new((file, size)) })
+    }
+
+    pub fn path(&self) -> &Path { &self.path }
+
+    /// Arquivo girado de número `index` (1 = o mais recente).
+    pub fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = OsString::from(self.path.as_os_str());
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    /// Acrescenta a rejeição do frame (header + payload) ao log; `frame` são
+    /// os pedaços do frame em ordem, gravados como um só.
+    pub fn record(&self, timestamp: u64, source: u8, frame: &[&[u8]], error: &ValidationError) -> std::io::Result<()> {
+        let entry = AuditEntry {
+            timestamp,
+            source,
+            msg_type: frame.iter().flat_map(|part| part.iter()).nth(4).copied().unwrap_or(0),
+            kind: error.kind(),
+            reason: error.to_string(),
+            frame_hex: hex(frame),
+        };
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+
+        let mut file = self.file.lock();
+        if file.1 > 0 && file.1 + line.len() as u64 > self.max_bytes {
+            *file = (self.rotate()?, 0);
+        }
+        file.0.write_all(&line)?;
+        file.1 += line.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&self) -> std::io::Result<File> {
+        if self.max_files == 0 {
+            return OpenOptions::new().write(true).truncate(true).open(&self.path);
+        }
+        for index in (1..self.max_files).rev() {
+            let from = self.rotated_path(index);
+            if from.exists() {
+                std::fs::rename(from, self.rotated_path(index + 1))?;
+            }
+        }
+        std::fs::rename(&self.path, self.rotated_path(1))?;
+        OpenOptions::new().create(true).append(true).open(&self.path)
+    }
+
+    /// Lê as entradas de um arquivo de auditoria, na ordem em que foram escritas.
+    pub fn read_entries(path: impl AsRef<Path>) -> std::io::Result<Vec<AuditEntry>> {
+        BufReader::new(File::open(path)?)
+            .lines()
+            .map(|line| Ok(serde_json::from_str(&line?)?))
+            .collect()
+    }
+}
+
+/// Hex minúsculo dos pedaços de `frame`, concatenados, numa única alocação.
+fn hex(frame: &[&[u8]]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(frame.iter().map(|part| part.len() * 2).sum());
+    for &byte in frame.iter().flat_map(|part| part.iter()) {
+        out.push(DIGITS[usize::from(byte >> 4)] as char);
+        out.push(DIGITS[usize::from(byte & 0x0f)] as char);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::ingestion::zero_copy::{MessageHeader, Trade};
+    use crate::validation::integrity::{CompositeValidator, SymbolValidator};
+
+    #[test]
+    fn test_rejections_are_audited_with_reason_and_bytes() {
+        let dir = std::env::temp_dir().join(format!("tensorwerk-audit-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("audit.log");
+        let audit = Arc::new(AuditLogger::open(&path, 4096, 2).unwrap());
+
+        let mut validator = CompositeValidator::builder()
+            .symbol_validator(SymbolValidator::whitelist(vec!["BTCUSD".to_string()]))
+            .audit_log(Arc::clone(&audit))
+            .build();
+        let ts = 1_700_000_000_000_000_000;
+        let good = Trade::new(*b"BTCUSD\0\0", 100_000_000, 100_000_000, ts, 1, 1).to_frame();
+        let unknown = Trade::new(*b"DOGEUSD\0", 100_000_000, 100_000_000, ts, 1, 2).to_frame();
+        let negative = Trade::new(*b"BTCUSD\0\0", -1, 100_000_000, ts + 1, 1, 3).to_frame();
+        for frame in [&good, &unknown, &negative] {
+            let _ = validator.validate_frame(frame);
+        }
+
+        let entries = AuditLogger::read_entries(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].kind, entries[1].kind), (ValidationErrorKind::InvalidSymbol, ValidationErrorKind::OutOfBounds));
+        assert!(entries[0].reason.contains("DOGEUSD"), "{}", entries[0].reason);
+        assert_eq!(entries[0].frame().unwrap(), &unknown[..]);
+        assert_eq!(entries[1].frame().unwrap(), &negative[..]);
+        assert_eq!(entries[1].msg_type, Trade::MSG_TYPE);
+
+        // Rotação: o arquivo nunca passa do limite e só 2 antigos ficam
+        for _ in 0..100 {
+            let _ = validator.validate_frame(&negative);
+        }
+        assert!(std::fs::metadata(&path).unwrap().len() <= 4096);
+        assert!(audit.rotated_path(2).exists() && !audit.rotated_path(3).exists());
+        let header = MessageHeader::from_bytes(&negative).unwrap();
+        assert!(AuditLogger::read_entries(audit.rotated_path(1)).unwrap().iter().all(|entry| entry.timestamp > 0
+            && entry.frame().unwrap()[..MessageHeader::SIZE] == header.to_bytes()));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,183 @@
+//! Baseline de validação: resumo determinístico de um replay para testes de regressão
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Read, Write};
+
+use crate::ingestion::zero_copy::MessageHeader;
+use crate::validation::integrity::CompositeValidator;
+use crate::validation::report::ValidationReport;
+
+const FNV_OFFSET: u64 = 0xCBF2_9CE4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeSummary {
+    pub received: u64,
+    pub rejected: u64,
+}
+
+/// Resumo de uma captura passada por um `CompositeValidator`: contagens por
+/// `msg_type`, histograma de motivos de rejeição e um hash encadeado (FNV-1a)
+/// de quais mensagens foram aceitas — posição na captura, tipo, timestamp e
+/// checksum de cada uma. Duas execuções com a mesma lógica de validação sobre
+/// a mesma captura geram baselines idênticos; salvo como JSON, serve de
+/// golden file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationBaseline {
+    pub report: ValidationReport,
+    pub per_type: BTreeMap<u8, TypeSummary>,
+    pub accepted_hash: u64,
+}
+
+/// Uma divergência entre o baseline salvo e a execução atual.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BaselineDifference {
+    Report { baseline: Box<ValidationReport>, current: Box<ValidationReport> },
+    MessageType { msg_type: u8, baseline: TypeSummary, current: TypeSummary },
+    /// Mesmas contagens, mas outro conjunto de mensagens aceitas.
+    AcceptedMessages { baseline: u64, current: u64 },
+}
+
+impl ValidationBaseline {
+    /// Valida `frames` na ordem com `validator` e resume o resultado. O estado
+    /// do validador avança como em qualquer validação.
+    pub fn record<I>(validator: &mut CompositeValidator, frames: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let mut baseline = Self { accepted_hash: FNV_OFFSET, ..Self::default() };
+
+        for (index, frame) in frames.into_iter().enumerate() {
+            let frame = frame.as_ref();
+            let Some(header) = MessageHeader::from_bytes(frame).filter(MessageHeader::is_valid) else {
+                baseline.report.record_malformed();
+                continue;
+            };
+            let Some(payload) = header.payload(frame) else {
+                baseline.report.record_malformed();
+                continue;
+            };
+
+            let result = validator.validate_header_checksum(&header, frame).and_then(|()| validator.validate_message(&header, payload));
+            baseline.report.record(&result);
+
+            let summary = baseline.per_type.entry(header.msg_type).or_default();
+            summary.received += 1;
+            if result.is_err() {
+                summary.rejected += 1;
+                continue;
+            }
+
+            baseline.accepted_hash = [index as u64, u64::from(header.msg_type), header.timestamp, u64::from(header.checksum)]
+                .iter()
+                .flat_map(|field| field.to_le_bytes())
+                .fold(baseline.accepted_hash, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME));
+        }
+
+        baseline
+    }
+
+    /// Diferenças entre este baseline (o salvo) e `current`; vazio se idênticos.
+    pub fn diff(&self, current: &ValidationBaseline) -> Vec<BaselineDifference> {
+        let mut differences = Vec::new();
+
+        if self.report != current.report {
+            differences.push(BaselineDifference::Report {
+                baseline: Box::new(self.report.clone()),
+                current: Box::new(current.report.clone()),
+            });
+        }
+
+        let msg_types: BTreeSet<u8> = self.per_type.keys().chain(current.per_type.keys()).copied().collect();
+        for msg_type in msg_types {
+            let baseline = self.per_type.get(&msg_type).copied().unwrap_or_default();
+            let now = current.per_type.get(&msg_type).copied().unwrap_or_default();
+            if baseline != now {
+                differences.push(BaselineDifference::MessageType { msg_type, baseline, current: now });
+            }
+        }
+
+        if self.accepted_hash != current.accepted_hash {
+            differences.push(BaselineDifference::AcceptedMessages {
+                baseline: self.accepted_hash,
+                current: current.accepted_hash,
+            });
+        }
+
+        differences
+    }
+
+    /// Reexecuta `frames` com `validator` e compara com este baseline.
+    pub fn compare<I>(&self, validator: &mut CompositeValidator, frames: I) -> Vec<BaselineDifference>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        self.diff(&Self::record(validator, frames))
+    }
+
+    pub fn save(&self, writer: &mut dyn Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(writer, self).map_err(io::Error::from)
+    }
+
+    pub fn load(reader: &mut dyn Read) -> io::Result<Self> {
+        serde_json::from_reader(reader).map_err(io::Error::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingestion::clock::MockClock;
+    use crate::ingestion::replay::MessageIter;
+    use crate::ingestion::synthetic::{SyntheticConfig, SyntheticFeed};
+    use crate::validation::integrity::{DataBounds, SymbolValidator};
+    use std::sync::Arc;
+
+    fn capture() -> Vec<u8> {
+        let config = SyntheticConfig {
+            symbols: vec![*b"BTCUSD\0\0", *b"ETHUSD\0\0", *b"XRPUSD\0\0"],
+            seed: 3,
+            ..SyntheticConfig::default()
+        };
+        let mut feed = SyntheticFeed::new(config, Arc::new(MockClock::new(1_700_000_000_000_000_000)));
+        let mut capture = Vec::new();
+        for i in 0..2_000 {
+            let mut frame = feed.next_frame();
+            if i % 53 == 0 {
+                frame[MessageHeader::SIZE + 12] ^= 0x01;
+            }
+            capture.extend_from_slice(&frame);
+        }
+        capture
+    }
+
+    fn validator(bounds: DataBounds) -> CompositeValidator {
+        CompositeValidator::builder()
+            .bounds(bounds)
+            .symbol_validator(SymbolValidator::whitelist(vec!["BTCUSD".to_string(), "ETHUSD".to_string()]))
+            .build()
+    }
+
+    #[test]
+    fn test_baseline_detects_logic_changes() {
+        let capture = capture();
+        let baseline = ValidationBaseline::record(&mut validator(DataBounds::crypto()), MessageIter::new(&capture));
+        assert_eq!(baseline.report.frames, 2_000);
+        assert!(baseline.report.accepted > 0 && baseline.report.rejected() > 0);
+
+        let mut saved = Vec::new();
+        baseline.save(&mut saved).unwrap();
+        let loaded = ValidationBaseline::load(&mut saved.as_slice()).unwrap();
+        assert_eq!(loaded, baseline);
+        assert!(loaded.compare(&mut validator(DataBounds::crypto()), MessageIter::new(&capture)).is_empty());
+
+        // Teto de preço no meio da faixa do random walk: outra lógica, outro resultado
+        let tighter = DataBounds::crypto().with_max_price(50_000.0);
+        let differences = loaded.compare(&mut validator(tighter), MessageIter::new(&capture));
+        assert!(differences.iter().any(|d| matches!(d, BaselineDifference::Report { .. })));
+        assert!(differences.iter().any(|d| matches!(d, BaselineDifference::AcceptedMessages { .. })));
+    }
+}
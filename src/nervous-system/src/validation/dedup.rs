@@ -0,0 +1,196 @@
+//! Deduplicação de mensagens entre feeds A/B com arbitragem por fonte
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
+
+/// Identidade de uma mensagem para deduplicação (ex.: símbolo + `trade_id`).
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub struct DedupKey {
+    pub symbol: [u8; 8],
+    pub id: u64,
+}
+
+/// Regra de arbitragem. Sem `primary_source`, a primeira cópia a chegar vence.
+/// Com `primary_source`, uma cópia de outra fonte fica retida por até `window`
+/// (medido nos timestamps das mensagens) esperando a cópia da fonte primária;
+/// se ela chegar, a secundária é descartada, senão a secundária é liberada.
+#[derive(Debug, Clone, Copy)]
+pub struct ArbitrationConfig {
+    pub primary_source: Option<u8>,
+    pub window: Duration,
+}
+
+impl Default for ArbitrationConfig {
+    fn default() -> Self {
+        Self { primary_source: None, window: Duration::from_millis(1) }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delivered<T> {
+    pub source: u8,
+    pub item: T,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DedupStats {
+    pub delivered: u64,
+    pub duplicates_dropped: u64,
+    /// Cópias secundárias descartadas porque a primária chegou dentro da janela.
+    pub secondaries_preempted: u64,
+    /// Cópias secundárias liberadas porque a primária não chegou a tempo.
+    pub secondaries_released: u64,
+}
+
+struct Pending<T> {
+    key: DedupKey,
+    deadline: u64,
+    delivered: Delivered<T>,
+}
+
+pub struct DedupValidator<T> {
+    default: ArbitrationConfig,
+    per_symbol: HashMap<[u8; 8], ArbitrationConfig>,
+    seen: HashSet<DedupKey>,
+    seen_order: VecDeque<DedupKey>,
+    max_tracked: usize,
+    pending: VecDeque<Pending<T>>,
+    stats: DedupStats,
+}
+
+impl<T> DedupValidator<T> {
+    pub const DEFAULT_MAX_TRACKED: usize = 100_000;
+
+    pub fn new(default: ArbitrationConfig) -> Self {
+        Self {
+            default,
+            per_symbol: HashMap::new(),
+            seen: HashSet::new(),
+            seen_order: VecDeque::new(),
+            max_tracked: Self::DEFAULT_MAX_TRACKED,
+            pending: VecDeque::new(),
+            stats: DedupStats::default(),
+        }
+    }
+
+    /// Limita quantas identidades entregues são lembradas (as mais antigas saem primeiro).
+    pub fn with_max_tracked(mut self, max_tracked: usize) -> Self {
+        self.max_tracked = max_tracked.max(1);
+        self
+    }
+
+    pub fn set_symbol_config(&mut self, symbol: [u8; 8], config: ArbitrationConfig) {
+        self.per_symbol.insert(symbol, config);
+    }
+
+    pub fn stats(&self) -> DedupStats { self.stats }
+
+    /// Oferece uma cópia recebida de `source`. Retorna as mensagens liberadas
+    /// nesta chamada, em ordem: secundárias cuja janela expirou até `timestamp`
+    /// e, se for o caso, a própria mensagem oferecida.
+    pub fn offer(&mut self, key: DedupKey, source: u8, timestamp: u64, item: T) -> Vec<Delivered<T>> {
+        let mut out = self.flush_expired(timestamp);
+
+        if self.seen.contains(&key) {
+            self.stats.duplicates_dropped += 1;
+            return out;
+        }
+
+        let config = self.per_symbol.get(&key.symbol).copied().unwrap_or(self.default);
+        let is_preferred = config.primary_source.is_none_or(|primary| primary == source);
+
+        if is_preferred {
+            if let Some(index) = self.pending.iter().position(|p| p.key == key) {
+                self.pending.remove(index);
+                self.stats.secondaries_preempted += 1;
+            }
+            self.mark_delivered(key);
+            out.push(Delivered { source, item });
+        } else if self.pending.iter().any(|p| p.key == key) {
+            self.stats.duplicates_dropped += 1;
+        } else {
+            let deadline = timestamp.saturating_add(config.window.as_nanos() as u64);
+            self.pending.push_back(Pending { key, deadline, delivered: Delivered { source, item } });
+        }
+
+        out
+    }
+
+    /// Libera as cópias secundárias cuja janela terminou até `now`.
+    pub fn flush_expired(&mut self, now: u64) -> Vec<Delivered<T>> {
+        let mut out = Vec::new();
+        let mut index = 0;
+        while index < self.pending.len() {
+            if self.pending[index].deadline <= now {
+                let pending = self.pending.remove(index).expect("índice válido");
+                self.mark_delivered(pending.key);
+                self.stats.secondaries_released += 1;
+                out.push(pending.delivered);
+            } else {
+                index += 1;
+            }
+        }
+        out
+    }
+
+    /// Libera todas as secundárias retidas (ex.: fim de sessão).
+    pub fn flush_all(&mut self) -> Vec<Delivered<T>> {
+        self.flush_expired(u64::MAX)
+    }
+
+    fn mark_delivered(&mut self, key: DedupKey) {
+        self.stats.delivered += 1;
+        if self.seen.insert(key) {
+            self.seen_order.push_back(key);
+        }
+        while self.seen_order.len() > self.max_tracked {
+            if let Some(old) = self.seen_order.pop_front() {
+                self.seen.remove(&old);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SYMBOL: [u8; 8] = *b"BTCUSD\0\0";
+
+    #[test]
+    fn test_first_arrival_wins_without_preference() {
+        let mut dedup = DedupValidator::new(ArbitrationConfig::default());
+        let key = DedupKey { symbol: SYMBOL, id: 7 };
+
+        assert_eq!(dedup.offer(key, 1, 100, "b"), vec![Delivered { source: 1, item: "b" }]);
+        assert!(dedup.offer(key, 0, 110, "a").is_empty());
+        assert_eq!(dedup.stats().duplicates_dropped, 1);
+    }
+
+    #[test]
+    fn test_preferred_source_kept_even_when_secondary_arrives_first() {
+        let mut dedup = DedupValidator::new(ArbitrationConfig::default());
+        dedup.set_symbol_config(SYMBOL, ArbitrationConfig { primary_source: Some(0), window: Duration::from_micros(10) });
+
+        let key = DedupKey { symbol: SYMBOL, id: 7 };
+        assert!(dedup.offer(key, 1, 1_000, "secondary").is_empty());
+        assert_eq!(dedup.offer(key, 0, 1_500, "primary"), vec![Delivered { source: 0, item: "primary" }]);
+        assert!(dedup.flush_all().is_empty());
+
+        // Sem a primária, a secundária sai quando a janela expira
+        let late = DedupKey { symbol: SYMBOL, id: 8 };
+        assert!(dedup.offer(late, 1, 2_000, "secondary").is_empty());
+        let next = DedupKey { symbol: SYMBOL, id: 9 };
+        let released = dedup.offer(next, 0, 20_000, "primary");
+        assert_eq!(released, vec![
+            Delivered { source: 1, item: "secondary" },
+            Delivered { source: 0, item: "primary" },
+        ]);
+        assert!(dedup.offer(late, 0, 20_001, "primary").is_empty());
+
+        let stats = dedup.stats();
+        assert_eq!(stats.secondaries_preempted, 1);
+        assert_eq!(stats.secondaries_released, 1);
+        assert_eq!(stats.duplicates_dropped, 1);
+    }
+}
@@ -0,0 +1,113 @@
+//! Desequilíbrio de volume comprador/vendedor por símbolo em janela móvel
+
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use crate::ingestion::zero_copy::Trade;
+
+/// Volume comprado e vendido por símbolo nos trades dos últimos `window`
+/// (medido nos timestamps dos trades, a partir do mais recente do símbolo).
+/// Trades com lado desconhecido não entram nos volumes nem na razão; são só
+/// contados em `unknown_side`.
+pub struct ImbalanceTracker {
+    window: u64,
+    symbols: HashMap<[u8; 8], SymbolWindow>,
+    unknown_side: u64,
+}
+
+#[derive(Default)]
+struct SymbolWindow {
+    /// (timestamp, volume com sinal: positivo = compra), em ordem de chegada.
+    trades: VecDeque<(u64, i64)>,
+    buy: i128,
+    sell: i128,
+    newest: u64,
+}
+
+impl SymbolWindow {
+    /// Remove os trades com timestamp até `cutoff`, inclusive.
+    fn evict_through(&mut self, cutoff: u64) {
+        while let Some(&(timestamp, volume)) = self.trades.front() {
+            if timestamp > cutoff {
+                break;
+            }
+            self.trades.pop_front();
+            if volume >= 0 {
+                self.buy -= i128::from(volume);
+            } else {
+                self.sell += i128::from(volume);
+            }
+        }
+    }
+}
+
+impl ImbalanceTracker {
+    pub fn new(window: Duration) -> Self {
+        Self { window: window.as_nanos() as u64, symbols: HashMap::new(), unknown_side: 0 }
+    }
+
+    pub fn record(&mut self, trade: &Trade) {
+        let volume = trade.quantity().saturating_abs();
+        let signed = match trade.side() {
+            Trade::SIDE_BUY => volume,
+            Trade::SIDE_SELL => -volume,
+            _ => {
+                self.unknown_side += 1;
+                return;
+            }
+        };
+
+        let timestamp = trade.timestamp();
+        let entry = self.symbols.entry(trade.symbol()).or_default();
+        if signed >= 0 {
+            entry.buy += i128::from(signed);
+        } else {
+            entry.sell -= i128::from(signed);
+        }
+        entry.trades.push_back((timestamp, signed));
+        entry.newest = entry.newest.max(timestamp);
+        entry.evict_through(entry.newest.saturating_sub(self.window));
+    }
+
+    /// `(compra − venda) / (compra + venda)` em `[-1, 1]`; `None` sem volume na janela.
+    pub fn imbalance(&self, symbol: &[u8; 8]) -> Option<f64> {
+        let entry = self.symbols.get(symbol)?;
+        let total = entry.buy + entry.sell;
+        (total > 0).then(|| (entry.buy - entry.sell) as f64 / total as f64)
+    }
+
+    /// Volumes (compra, venda) na janela, nas unidades do protocolo (× 1e8).
+    pub fn volumes(&self, symbol: &[u8; 8]) -> Option<(i128, i128)> {
+        self.symbols.get(symbol).map(|entry| (entry.buy, entry.sell))
+    }
+
+    pub fn unknown_side(&self) -> u64 { self.unknown_side }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skewed_flow_imbalance() {
+        let mut tracker = ImbalanceTracker::new(Duration::from_secs(1));
+        let ts = 1_700_000_000_000_000_000;
+        let btc = *b"BTCUSD\0\0";
+        let trade = |side: u8, quantity: i64, offset_ms: u64| Trade::new(btc, 1, quantity, ts + offset_ms * 1_000_000, side, 0);
+
+        // 3 compras de 3 contra 1 venda de 3 (mais um lado desconhecido): (9 − 3) / 12
+        for offset in 0..3 {
+            tracker.record(&trade(Trade::SIDE_BUY, 300_000_000, offset));
+        }
+        tracker.record(&trade(Trade::SIDE_SELL, 300_000_000, 3));
+        tracker.record(&trade(7, 5_000_000_000, 4));
+        assert_eq!(tracker.imbalance(&btc), Some(0.5));
+        assert_eq!(tracker.unknown_side(), 1);
+        assert_eq!(tracker.imbalance(b"ETHUSD\0\0"), None);
+
+        // Um segundo depois as compras saíram da janela; só vendas restam
+        tracker.record(&trade(Trade::SIDE_SELL, 100_000_000, 1_002));
+        assert_eq!(tracker.volumes(&btc), Some((0, 400_000_000)));
+        assert_eq!(tracker.imbalance(&btc), Some(-1.0));
+    }
+}
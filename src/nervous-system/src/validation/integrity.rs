@@ -1,43 +1,368 @@
 //! Validação de integridade de dados: checksum, bounds, timestamps
 
+use arc_swap::ArcSwap;
+use crossbeam_channel::{bounded, Receiver, Sender};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
 use thiserror::Error;
 use tracing::warn;
 
+use crate::ingestion::clock::{Clock, SystemClock};
+use crate::ingestion::zero_copy::{read_struct, TradeBatch};
+use crate::validation::audit::AuditLogger;
+use crate::validation::registry::{KeyedFields, MessageTypeDescriptor, MessageTypeRegistry};
+use crate::validation::report::ValidationReport;
+use crate::validation::stats::{MessageTypeCounts, MessageTypeStats};
+#[cfg(feature = "stage-timing")]
+use crate::validation::stats::{StageClock, StageTimings};
+
+/// Avalia `$body` acumulando o tempo gasto em `$slot` (um `AtomicU64` de
+/// `StageClock`). Sem a feature `stage-timing`, é só `$body`.
+#[cfg(feature = "stage-timing")]
+macro_rules! timed {
+    ($slot:expr, $body:expr) => {{
+        let start = std::time::Instant::now();
+        let result = $body;
+        $slot.fetch_add(start.elapsed().as_nanos() as u64, std::sync::atomic::Ordering::Relaxed);
+        result
+    }};
+}
+
+#[cfg(not(feature = "stage-timing"))]
+macro_rules! timed {
+    ($slot:expr, $body:expr) => {
+        $body
+    };
+}
+
+/// Os erros de validação carregam só os dados crus (números, bytes, nomes
+/// estáticos); o texto é montado em `Display`. Rejeitar uma mensagem no
+/// caminho quente não aloca, mesmo sob uma enxurrada de mensagens inválidas.
 #[derive(Debug, Error)]
 pub enum ValidationError {
     #[error("Checksum: esperado={expected:?}, calculado={calculated:?}")]
     ChecksumMismatch { expected: u32, calculated: u32 },
     #[error("Timestamp inválido: {0}")]
-    InvalidTimestamp(String),
+    InvalidTimestamp(TimestampIssue),
     #[error("Valor fora dos limites: campo={field}, valor={value}")]
-    OutOfBounds { field: String, value: f64 },
+    OutOfBounds { field: BoundsField, value: f64 },
     #[error("Tipo desconhecido: {0}")]
     UnknownMessageType(u8),
     #[error("Ordem temporal violada: prev={prev}, atual={current}")]
     TemporalOrderViolation { prev: u64, current: u64 },
     #[error("Símbolo inválido: {0}")]
-    InvalidSymbol(String),
+    InvalidSymbol(SymbolIssue),
     #[error("Formato corrompido")]
     CorruptedFormat,
+    #[error("Nível de book inválido: índice={index}, motivo={reason}")]
+    InvalidBookLevel { index: usize, reason: &'static str },
+    #[error("Sequência violada: esperado={expected}, recebido={received}")]
+    SequenceViolation { expected: u64, received: u64 },
+    #[error("Limite de taxa excedido: máximo={limit} mensagens por janela")]
+    RateLimitExceeded { limit: u32 },
+    #[error("trade_id regrediu: prev={prev}, atual={current}")]
+    TradeIdRegression { prev: u64, current: u64 },
+    #[error("Protocolo desconhecido: magic={magic:#010x}")]
+    UnknownProtocol { magic: u32 },
+    /// Checksum do header (v2) não confere: o header foi corrompido, mesmo que
+    /// o payload esteja íntegro.
+    #[error("Checksum do header: esperado={expected:?}, calculado={calculated:?}")]
+    HeaderChecksumMismatch { expected: u32, calculated: u32 },
+    /// A fonte não está autorizada a enviar esse tipo (ver
+    /// `CompositeValidatorBuilder::allowed_types_for_source`).
+    #[error("Tipo {msg_type} não permitido para a fonte {source_id}")]
+    TypeNotAllowedForSource { msg_type: u8, source_id: u8 },
+    /// Trade `index` de um lote (`TradeBatch`) rejeitado; a categoria
+    /// (`kind`) é a do erro do trade.
+    #[error("Trade {index} do lote: {error}")]
+    BatchTrade { index: usize, error: TradeIssue },
+    /// Padding de trade/quote com bytes não nulos: layout do produtor diverge
+    /// do consumidor (ver `CompositeValidatorBuilder::strict_padding`). Conta
+    /// como `CorruptedFormat`.
+    #[error("Padding não nulo em mensagem do tipo {msg_type}")]
+    NonZeroPadding { msg_type: u8 },
+}
+
+/// Erro de um trade dentro de um `ValidationError::BatchTrade`: as variantes
+/// de `ValidationError` que um trade isolado produz, sem recursão (rejeitar
+/// um lote também não aloca).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TradeIssue {
+    InvalidTimestamp(TimestampIssue),
+    OutOfBounds { field: BoundsField, value: f64 },
+    InvalidSymbol(SymbolIssue),
+    TemporalOrderViolation { prev: u64, current: u64 },
+    SequenceViolation { expected: u64, received: u64 },
+    TradeIdRegression { prev: u64, current: u64 },
+    RateLimitExceeded { limit: u32 },
+    NonZeroPadding,
+}
+
+impl From<TradeIssue> for ValidationError {
+    fn from(issue: TradeIssue) -> Self {
+        match issue {
+            TradeIssue::InvalidTimestamp(issue) => Self::InvalidTimestamp(issue),
+            TradeIssue::OutOfBounds { field, value } => Self::OutOfBounds { field, value },
+            TradeIssue::InvalidSymbol(issue) => Self::InvalidSymbol(issue),
+            TradeIssue::TemporalOrderViolation { prev, current } => Self::TemporalOrderViolation { prev, current },
+            TradeIssue::SequenceViolation { expected, received } => Self::SequenceViolation { expected, received },
+            TradeIssue::TradeIdRegression { prev, current } => Self::TradeIdRegression { prev, current },
+            TradeIssue::RateLimitExceeded { limit } => Self::RateLimitExceeded { limit },
+            TradeIssue::NonZeroPadding => Self::NonZeroPadding { msg_type: crate::ingestion::zero_copy::Trade::MSG_TYPE },
+        }
+    }
+}
+
+impl std::fmt::Display for TradeIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { ValidationError::from(*self).fmt(f) }
+}
+
+/// Motivo de um `ValidationError::InvalidTimestamp`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampIssue {
+    /// Fora de `DataBounds::min_timestamp..=max_timestamp`.
+    OutOfRange(u64),
+    /// Além da tolerância de timestamp futuro em relação ao relógio local.
+    Future { timestamp: u64, limit: u64 },
+}
+
+impl std::fmt::Display for TimestampIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OutOfRange(timestamp) => write!(f, "{} fora do intervalo", timestamp),
+            Self::Future { timestamp, limit } => write!(f, "no futuro: {} > limite {}", timestamp, limit),
+        }
+    }
+}
+
+/// Campo de um `ValidationError::OutOfBounds`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundsField {
+    /// Campo de um tipo de mensagem ("price", "bid_price", ...).
+    Named(&'static str),
+    /// Profundidade do book acima do limite (o valor é o limite): `levels[N]`.
+    BookDepth(usize),
+    /// Quantidade do nível `index` do book: `levels[index].quantity`.
+    LevelQuantity(usize),
+}
+
+impl std::fmt::Display for BoundsField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Named(name) => f.write_str(name),
+            Self::BookDepth(limit) => write!(f, "levels[{}]", limit),
+            Self::LevelQuantity(index) => write!(f, "levels[{}].quantity", index),
+        }
+    }
+}
+
+/// Motivo de um `ValidationError::InvalidSymbol`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolIssue {
+    /// Byte não nulo depois do terminador (ver `symbol_str`).
+    ByteAfterNul([u8; 8]),
+    InvalidUtf8([u8; 8]),
+    InvalidChar(u8),
+    /// Fora da whitelist (já normalizado).
+    Unknown([u8; 8]),
+    /// Só NULs (depois de normalizado), rejeitado por `SymbolValidator::format_only`.
+    Empty,
+    /// Quote com bid maior ou igual ao ask.
+    CrossedQuote,
+}
+
+impl std::fmt::Display for SymbolIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ByteAfterNul(symbol) => write!(f, "Byte após o terminador nulo: {:02X?}", symbol),
+            Self::InvalidUtf8(symbol) => write!(f, "UTF-8 inválido: {:02X?}", symbol),
+            Self::InvalidChar(byte) => write!(f, "Caractere inválido: {}", byte),
+            Self::Unknown(symbol) => {
+                let len = symbol.iter().position(|&b| b == 0).unwrap_or(symbol.len());
+                write!(f, "Símbolo desconhecido: {}", String::from_utf8_lossy(&symbol[..len]))
+            }
+            Self::Empty => f.write_str("Símbolo vazio"),
+            Self::CrossedQuote => f.write_str("Bid deve ser menor que Ask"),
+        }
+    }
+}
+
+/// Categoria de um `ValidationError`, sem dados associados; usada como índice
+/// em contadores por motivo de rejeição.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ValidationErrorKind {
+    ChecksumMismatch,
+    InvalidTimestamp,
+    OutOfBounds,
+    UnknownMessageType,
+    TemporalOrderViolation,
+    InvalidSymbol,
+    CorruptedFormat,
+    InvalidBookLevel,
+    SequenceViolation,
+    RateLimitExceeded,
+    TradeIdRegression,
+    UnknownProtocol,
+    HeaderChecksumMismatch,
+    TypeNotAllowedForSource,
+}
+
+impl ValidationErrorKind {
+    pub const COUNT: usize = 14;
+
+    pub const ALL: [ValidationErrorKind; Self::COUNT] = [
+        Self::ChecksumMismatch,
+        Self::InvalidTimestamp,
+        Self::OutOfBounds,
+        Self::UnknownMessageType,
+        Self::TemporalOrderViolation,
+        Self::InvalidSymbol,
+        Self::CorruptedFormat,
+        Self::InvalidBookLevel,
+        Self::SequenceViolation,
+        Self::RateLimitExceeded,
+        Self::TradeIdRegression,
+        Self::UnknownProtocol,
+        Self::HeaderChecksumMismatch,
+        Self::TypeNotAllowedForSource,
+    ];
+
+    #[inline]
+    pub fn index(self) -> usize { self as usize }
+}
+
+impl ValidationError {
+    pub fn kind(&self) -> ValidationErrorKind {
+        match self {
+            Self::ChecksumMismatch { .. } => ValidationErrorKind::ChecksumMismatch,
+            Self::InvalidTimestamp(_) => ValidationErrorKind::InvalidTimestamp,
+            Self::OutOfBounds { .. } => ValidationErrorKind::OutOfBounds,
+            Self::UnknownMessageType(_) => ValidationErrorKind::UnknownMessageType,
+            Self::TemporalOrderViolation { .. } => ValidationErrorKind::TemporalOrderViolation,
+            Self::InvalidSymbol(_) => ValidationErrorKind::InvalidSymbol,
+            Self::CorruptedFormat => ValidationErrorKind::CorruptedFormat,
+            Self::InvalidBookLevel { .. } => ValidationErrorKind::InvalidBookLevel,
+            Self::SequenceViolation { .. } => ValidationErrorKind::SequenceViolation,
+            Self::RateLimitExceeded { .. } => ValidationErrorKind::RateLimitExceeded,
+            Self::TradeIdRegression { .. } => ValidationErrorKind::TradeIdRegression,
+            Self::UnknownProtocol { .. } => ValidationErrorKind::UnknownProtocol,
+            Self::HeaderChecksumMismatch { .. } => ValidationErrorKind::HeaderChecksumMismatch,
+            Self::TypeNotAllowedForSource { .. } => ValidationErrorKind::TypeNotAllowedForSource,
+            Self::BatchTrade { error, .. } => Self::from(*error).kind(),
+            Self::NonZeroPadding { .. } => ValidationErrorKind::CorruptedFormat,
+        }
+    }
+
+    /// Erros que não são de um trade (nenhum dos estágios por trade os
+    /// produz) voltam sem o índice.
+    fn in_batch(self, index: usize) -> Self {
+        let error = match self {
+            Self::InvalidTimestamp(issue) => TradeIssue::InvalidTimestamp(issue),
+            Self::OutOfBounds { field, value } => TradeIssue::OutOfBounds { field, value },
+            Self::InvalidSymbol(issue) => TradeIssue::InvalidSymbol(issue),
+            Self::TemporalOrderViolation { prev, current } => TradeIssue::TemporalOrderViolation { prev, current },
+            Self::SequenceViolation { expected, received } => TradeIssue::SequenceViolation { expected, received },
+            Self::TradeIdRegression { prev, current } => TradeIssue::TradeIdRegression { prev, current },
+            Self::RateLimitExceeded { limit } => TradeIssue::RateLimitExceeded { limit },
+            Self::NonZeroPadding { .. } => TradeIssue::NonZeroPadding,
+            other => return other,
+        };
+        Self::BatchTrade { index, error }
+    }
+}
+
+/// Polinômio (forma refletida) usado pela tabela do `ChecksumValidator`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    /// CRC-32 IEEE 802.3 (padrão do protocolo).
+    #[default]
+    Crc32,
+    /// CRC-32C (Castagnoli).
+    Crc32c,
+}
+
+impl ChecksumAlgorithm {
+    fn polynomial(self) -> u32 {
+        match self {
+            Self::Crc32 => 0xEDB88320,
+            Self::Crc32c => 0x82F63B78,
+        }
+    }
+}
+
+/// Largura útil do valor de um `Checksum`. O campo do header tem 32 bits,
+/// então não há larguras maiores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumWidth {
+    Bits16,
+    Bits32,
+}
+
+impl ChecksumWidth {
+    pub fn bits(self) -> u32 {
+        match self {
+            Self::Bits16 => 16,
+            Self::Bits32 => 32,
+        }
+    }
+
+    fn mask(self) -> u64 { u64::MAX >> (64 - self.bits()) }
+}
+
+/// Função de integridade do payload plugável no `CompositeValidator` (ver
+/// `CompositeValidatorBuilder::custom_checksum`), para feeds com algoritmos
+/// próprios. Os CRCs embutidos a implementam via `ChecksumValidator`.
+pub trait Checksum: Send + Sync {
+    /// Valor sobre `data`; só os `width().bits()` bits baixos são comparados.
+    fn compute(&self, data: &[u8]) -> u64;
+    fn width(&self) -> ChecksumWidth;
+
+    /// `compute` truncado para `width()`, como vai no campo do header.
+    fn value(&self, data: &[u8]) -> u32 { (self.compute(data) & self.width().mask()) as u32 }
 }
 
 pub struct ChecksumValidator {
     table: [u32; 256],
+    algorithm: ChecksumAlgorithm,
+}
+
+/// CRC parcial de um payload recebido em pedaços; ver `ChecksumValidator::start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChecksumState {
+    crc: u32,
+}
+
+/// CRC do payload já calculado sobre os bytes atuais de um buffer (ver
+/// `ZeroCopyBuffer::cached_checksum`), para revalidar sem percorrê-los de novo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachedChecksum {
+    pub algorithm: ChecksumAlgorithm,
+    pub value: u32,
 }
 
+static CRC32: LazyLock<ChecksumValidator> = LazyLock::new(ChecksumValidator::new);
+
 impl ChecksumValidator {
     pub fn new() -> Self {
-        const POLYNOMIAL: u32 = 0xEDB88320;
+        Self::with_algorithm(ChecksumAlgorithm::Crc32)
+    }
+
+    /// Instância CRC32 compartilhada, com a tabela montada uma única vez; para
+    /// quem calcula checksums avulsos sem guardar um validador.
+    pub fn crc32() -> &'static Self { &CRC32 }
+
+    pub fn with_algorithm(algorithm: ChecksumAlgorithm) -> Self {
+        let polynomial = algorithm.polynomial();
         let mut table = [0u32; 256];
 
         for i in 0..256 {
             let mut crc = i as u32;
             for _ in 0..8 {
                 if crc & 1 != 0 {
-                    crc = (crc >> 1) ^ POLYNOMIAL;
+                    crc = (crc >> 1) ^ polynomial;
                 } else {
                     crc >>= 1;
                 }
@@ -45,24 +370,51 @@ impl ChecksumValidator {
             table[i as usize] = crc;
         }
 
-        Self { table }
+        Self { table, algorithm }
     }
 
+    pub fn algorithm(&self) -> ChecksumAlgorithm { self.algorithm }
+
     #[inline]
     pub fn calculate(&self, data: &[u8]) -> u32 {
-        let mut crc = 0xFFFFFFFF_u32;
+        let mut state = self.start();
+        self.update(&mut state, data);
+        self.finalize(state)
+    }
+
+    /// Inicia um CRC incremental: `start`, um `update` por pedaço na ordem de
+    /// chegada e `finalize` equivalem a `calculate` sobre o payload inteiro.
+    #[inline]
+    pub fn start(&self) -> ChecksumState {
+        ChecksumState { crc: 0xFFFFFFFF }
+    }
 
-        for &byte in data {
-            let index = ((crc as u8) ^ byte) as usize;
-            crc = (crc >> 8) ^ self.table[index];
+    #[inline]
+    pub fn update(&self, state: &mut ChecksumState, chunk: &[u8]) {
+        for &byte in chunk {
+            let index = ((state.crc as u8) ^ byte) as usize;
+            state.crc = (state.crc >> 8) ^ self.table[index];
         }
+    }
 
-        !crc
+    #[inline]
+    pub fn finalize(&self, state: ChecksumState) -> u32 {
+        !state.crc
     }
 
     #[inline]
     pub fn validate(&self, data: &[u8], expected: u32) -> Result<(), ValidationError> {
-        let calculated = self.calculate(data);
+        self.validate_cached(data, expected, None)
+    }
+
+    /// Como `validate`, usando `cached` no lugar de recalcular sobre `data`
+    /// quando foi obtido com o mesmo algoritmo.
+    #[inline]
+    pub fn validate_cached(&self, data: &[u8], expected: u32, cached: Option<CachedChecksum>) -> Result<(), ValidationError> {
+        let calculated = match cached {
+            Some(cached) if cached.algorithm == self.algorithm => cached.value,
+            _ => self.calculate(data),
+        };
         if calculated != expected {
             return Err(ValidationError::ChecksumMismatch { expected, calculated });
         }
@@ -74,6 +426,11 @@ impl Default for ChecksumValidator {
     fn default() -> Self { Self::new() }
 }
 
+impl Checksum for ChecksumValidator {
+    fn compute(&self, data: &[u8]) -> u64 { u64::from(self.calculate(data)) }
+    fn width(&self) -> ChecksumWidth { ChecksumWidth::Bits32 }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct DataBounds {
     pub min_price: f64,
@@ -82,6 +439,14 @@ pub struct DataBounds {
     pub max_quantity: f64,
     pub min_timestamp: u64,
     pub max_timestamp: u64,
+    /// Aceita preços negativos (spreads de calendário, basis, taxas de juros).
+    /// Quando ligado, o intervalo válido passa a ser `[-max_price, max_price]`.
+    pub allow_negative_price: bool,
+    /// Máximo de níveis (bids + asks) num snapshot de book; protege a
+    /// reconstrução do book contra snapshots patológicos.
+    pub max_book_levels: usize,
+    /// Quantidade máxima de um único nível de book.
+    pub max_level_quantity: f64,
 }
 
 impl DataBounds {
@@ -96,6 +461,9 @@ impl DataBounds {
             max_quantity: 10_000_000.0,
             min_timestamp: min_ts,
             max_timestamp: max_ts,
+            allow_negative_price: false,
+            max_book_levels: 10_000,
+            max_level_quantity: 10_000_000.0,
         }
     }
 
@@ -107,35 +475,93 @@ impl DataBounds {
             max_quantity: 1_000_000_000.0,
             min_timestamp: 1_577_836_800_000_000_000,
             max_timestamp: 1_893_456_000_000_000_000,
+            allow_negative_price: false,
+            max_book_levels: 10_000,
+            max_level_quantity: 1_000_000_000.0,
         }
     }
 
+    // Ajustes pontuais sobre um preset, ex.: `DataBounds::crypto().with_max_price(1e9)`
+    pub fn with_min_price(mut self, min_price: f64) -> Self { self.min_price = min_price; self }
+    pub fn with_max_price(mut self, max_price: f64) -> Self { self.max_price = max_price; self }
+    pub fn with_min_quantity(mut self, min_quantity: f64) -> Self { self.min_quantity = min_quantity; self }
+    pub fn with_max_quantity(mut self, max_quantity: f64) -> Self { self.max_quantity = max_quantity; self }
+    pub fn with_min_timestamp(mut self, min_timestamp: u64) -> Self { self.min_timestamp = min_timestamp; self }
+    pub fn with_max_timestamp(mut self, max_timestamp: u64) -> Self { self.max_timestamp = max_timestamp; self }
+    pub fn with_negative_prices(mut self, allow: bool) -> Self { self.allow_negative_price = allow; self }
+    pub fn with_max_book_levels(mut self, max_levels: usize) -> Self { self.max_book_levels = max_levels; self }
+    pub fn with_max_level_quantity(mut self, max_quantity: f64) -> Self { self.max_level_quantity = max_quantity; self }
+
     #[inline]
-    pub fn validate_price(&self, price: f64, field: &str) -> Result<(), ValidationError> {
-        if price < self.min_price || price > self.max_price {
+    pub fn validate_price(&self, price: f64, field: &'static str) -> Result<(), ValidationError> {
+        Self::ensure_finite(price, field)?;
+
+        let min_price = if self.allow_negative_price { -self.max_price } else { self.min_price };
+        if price < min_price || price > self.max_price {
             return Err(ValidationError::OutOfBounds {
-                field: field.to_string(),
+                field: BoundsField::Named(field),
                 value: price,
             });
         }
         Ok(())
     }
 
+    /// Valida um lote de preços com a regra de `validate_price` (NaN e
+    /// infinitos são rejeitados), comparando vários preços por instrução:
+    /// AVX-512 (8 por vez) ou AVX (4), detectados em tempo de execução, e
+    /// escalar nos demais casos. Retorna o índice e o valor do primeiro preço
+    /// fora dos limites. Complementa as colunas do `SoABatch`.
+    pub fn validate_prices_simd(&self, prices: &[f64]) -> Result<(), (usize, f64)> {
+        let min_price = if self.allow_negative_price { -self.max_price } else { self.min_price };
+        // Limites finitos: ±inf fica de fora, como em `ensure_finite`
+        let (min, max) = (min_price.max(f64::MIN), self.max_price.min(f64::MAX));
+
+        #[cfg(target_arch = "x86_64")]
+        let first = if std::arch::is_x86_feature_detected!("avx512f") {
+            // SAFETY: AVX-512F detectado em tempo de execução
+            unsafe { simd_bounds::first_outside_avx512(prices, min, max) }
+        } else if std::arch::is_x86_feature_detected!("avx") {
+            // SAFETY: AVX detectado em tempo de execução
+            unsafe { simd_bounds::first_outside_avx(prices, min, max) }
+        } else {
+            simd_bounds::first_outside_scalar(prices, min, max)
+        };
+        #[cfg(not(target_arch = "x86_64"))]
+        let first = simd_bounds::first_outside_scalar(prices, min, max);
+
+        first.map_or(Ok(()), |index| Err((index, prices[index])))
+    }
+
     #[inline]
-    pub fn validate_quantity(&self, qty: f64, field: &str) -> Result<(), ValidationError> {
+    pub fn validate_quantity(&self, qty: f64, field: &'static str) -> Result<(), ValidationError> {
+        Self::ensure_finite(qty, field)?;
+
         if qty < self.min_quantity || qty > self.max_quantity {
             return Err(ValidationError::OutOfBounds {
-                field: field.to_string(),
+                field: BoundsField::Named(field),
                 value: qty,
             });
         }
         Ok(())
     }
 
+    /// Comparações com NaN são sempre falsas, então um NaN passaria por qualquer
+    /// checagem `<`/`>`; valores não finitos são rejeitados antes delas.
+    #[inline]
+    fn ensure_finite(value: f64, field: &'static str) -> Result<(), ValidationError> {
+        if !value.is_finite() {
+            return Err(ValidationError::OutOfBounds {
+                field: BoundsField::Named(field),
+                value,
+            });
+        }
+        Ok(())
+    }
+
     #[inline]
     pub fn validate_timestamp(&self, ts: u64) -> Result<(), ValidationError> {
         if ts < self.min_timestamp || ts > self.max_timestamp {
-            return Err(ValidationError::InvalidTimestamp(format!("Timestamp inválido: {}", ts)));
+            return Err(ValidationError::InvalidTimestamp(TimestampIssue::OutOfRange(ts)));
         }
         Ok(())
     }
@@ -147,15 +573,60 @@ struct LastTimestamp {
     source: u8,
 }
 
+/// Último valor visto por (símbolo, fonte); base dos validadores com estado.
+/// Cada entrada guarda o instante da última atualização (pelo `Clock`
+/// injetado) para que `compact` descarte símbolos que pararam de negociar.
+struct LastPerSymbol {
+    values: HashMap<LastTimestamp, (u64, u64)>,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for LastPerSymbol {
+    fn default() -> Self {
+        Self { values: HashMap::new(), clock: Arc::new(SystemClock) }
+    }
+}
+
+impl LastPerSymbol {
+    fn get(&self, symbol: &[u8; 8], source: u8) -> Option<u64> {
+        self.values.get(&LastTimestamp { symbol: *symbol, source }).map(|&(value, _)| value)
+    }
+
+    fn set(&mut self, symbol: &[u8; 8], source: u8, value: u64) {
+        let now = self.clock.now_nanos();
+        self.values.insert(LastTimestamp { symbol: *symbol, source }, (value, now));
+    }
+
+    fn remove(&mut self, symbol: &[u8; 8], source: u8) {
+        self.values.remove(&LastTimestamp { symbol: *symbol, source });
+    }
+
+    fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    /// Remove entradas sem atualização há mais de `older_than`; retorna quantas saíram.
+    fn compact(&mut self, older_than: Duration) -> usize {
+        let cutoff = self.clock.now_nanos().saturating_sub(older_than.as_nanos() as u64);
+        let before = self.values.len();
+        self.values.retain(|_, &mut (_, updated_at)| updated_at >= cutoff);
+        before - self.values.len()
+    }
+
+    fn len(&self) -> usize { self.values.len() }
+
+    fn clear(&mut self) { self.values.clear() }
+}
+
 pub struct TemporalValidator {
-    last_timestamps: std::collections::HashMap<LastTimestamp, u64>,
+    last_timestamps: LastPerSymbol,
     clock_skew_tolerance: u64,
 }
 
 impl TemporalValidator {
     pub fn new(clock_skew_tolerance: Duration) -> Self {
         Self {
-            last_timestamps: std::collections::HashMap::new(),
+            last_timestamps: LastPerSymbol::default(),
             clock_skew_tolerance: clock_skew_tolerance.as_nanos() as u64,
         }
     }
@@ -166,165 +637,2562 @@ impl TemporalValidator {
         source: u8,
         timestamp: u64,
     ) -> Result<(), ValidationError> {
-        let key = LastTimestamp { symbol: *symbol, source };
+        self.check_monotonic(symbol, source, timestamp)?;
+        self.record_monotonic(symbol, source, timestamp);
+        Ok(())
+    }
+
+    /// Registra `timestamp` como o último visto, sem checar; par de `check_monotonic`.
+    pub fn record_monotonic(&mut self, symbol: &[u8; 8], source: u8, timestamp: u64) {
+        self.last_timestamps.set(symbol, source, timestamp);
+    }
 
-        if let Some(&last_ts) = self.last_timestamps.get(&key) {
+    /// Como `validate_monotonic`, sem registrar `timestamp` como o último visto.
+    pub fn check_monotonic(&self, symbol: &[u8; 8], source: u8, timestamp: u64) -> Result<(), ValidationError> {
+        if let Some(last_ts) = self.last_timestamps.get(symbol, source) {
             if timestamp < last_ts.saturating_sub(self.clock_skew_tolerance) {
                 return Err(ValidationError::TemporalOrderViolation { prev: last_ts, current: timestamp });
             }
         }
-
-        self.last_timestamps.insert(key, timestamp);
         Ok(())
     }
 
     pub fn forget_symbol(&mut self, symbol: &[u8; 8], source: u8) {
+        self.last_timestamps.remove(symbol, source);
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.last_timestamps.set_clock(clock);
+        self
+    }
+
+    /// Esquece pares (símbolo, fonte) sem mensagens há mais de `older_than`.
+    pub fn compact(&mut self, older_than: Duration) -> usize { self.last_timestamps.compact(older_than) }
+
+    /// Esquece todos os timestamps; a tolerância é mantida.
+    pub fn reset(&mut self) { self.last_timestamps.clear() }
+
+    pub fn tracked(&self) -> usize { self.last_timestamps.len() }
+}
+
+/// Lacuna de sequência detectada pelo `SequenceValidator`: faltam as
+/// mensagens `from_seq..=to_seq` de (`symbol`, `source`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GapEvent {
+    pub symbol: [u8; 8],
+    pub source: u8,
+    pub from_seq: u64,
+    pub to_seq: u64,
+}
+
+/// Lacunas abertas por (símbolo, fonte) e o canal que as anuncia.
+struct GapRecovery {
+    tx: Sender<GapEvent>,
+    rx: Receiver<GapEvent>,
+    pending: HashMap<LastTimestamp, Vec<RangeInclusive<u64>>>,
+}
+
+impl GapRecovery {
+    fn contains(&self, symbol: &[u8; 8], source: u8, sequence: u64) -> bool {
+        self.pending
+            .get(&LastTimestamp { symbol: *symbol, source })
+            .is_some_and(|gaps| gaps.iter().any(|gap| gap.contains(&sequence)))
+    }
+
+    /// Tira `filled` das lacunas abertas de (símbolo, fonte), partindo as que
+    /// ficarem com buracos no meio.
+    fn fill(&mut self, symbol: &[u8; 8], source: u8, filled: RangeInclusive<u64>) {
         let key = LastTimestamp { symbol: *symbol, source };
-        self.last_timestamps.remove(&key);
+        let Some(gaps) = self.pending.get_mut(&key) else { return };
+        let (start, end) = (*filled.start(), *filled.end());
+        *gaps = gaps
+            .iter()
+            .flat_map(|gap| {
+                if end < *gap.start() || start > *gap.end() {
+                    return [Some(gap.clone()), None];
+                }
+                let before = (start > *gap.start()).then(|| *gap.start()..=start - 1);
+                let after = (end < *gap.end()).then(|| end + 1..=*gap.end());
+                [before, after]
+            })
+            .flatten()
+            .collect();
+        if gaps.is_empty() {
+            self.pending.remove(&key);
+        }
     }
 }
 
-pub struct SymbolValidator {
-    known_symbols: HashSet<[u8; 8]>,
-    allow_unknown: bool,
+/// Exige números de sequência contíguos por (símbolo, fonte): o primeiro valor
+/// visto é aceito; a partir dele cada mensagem deve trazer exatamente `anterior + 1`.
+/// Lacunas, duplicatas e regressões são rejeitadas com `SequenceViolation`.
+///
+/// Com `with_gap_events`, um salto para frente é aceito: a lacuna fica aberta
+/// e é anunciada como `GapEvent` para quem pede a retransmissão. As mensagens
+/// retransmitidas (com sequência dentro de uma lacuna aberta) passam fora de
+/// ordem e vão fechando a lacuna; `mark_filled` a fecha explicitamente.
+pub struct SequenceValidator {
+    last_sequences: LastPerSymbol,
+    gaps: Option<GapRecovery>,
 }
 
-impl SymbolValidator {
-    pub fn whitelist(symbols: Vec<String>) -> Self {
-        let mut known = HashSet::new();
+impl SequenceValidator {
+    pub fn new() -> Self {
+        Self { last_sequences: LastPerSymbol::default(), gaps: None }
+    }
 
-        for sym in symbols {
-            let mut bytes = [0u8; 8];
-            let sym_bytes = sym.as_bytes();
-            let len = sym_bytes.len().min(8);
-            bytes[..len].copy_from_slice(&sym_bytes[..len]);
-            known.insert(bytes);
+    /// Liga a recuperação de lacunas; os eventos vão para um canal com até
+    /// `capacity` pendentes (ver `gap_events`). Com o canal cheio o evento é
+    /// descartado com um aviso, sem travar a validação.
+    pub fn with_gap_events(mut self, capacity: usize) -> Self {
+        let (tx, rx) = bounded(capacity);
+        self.gaps = Some(GapRecovery { tx, rx, pending: HashMap::new() });
+        self
+    }
+
+    /// Canal de lacunas detectadas; `None` sem `with_gap_events`.
+    pub fn gap_events(&self) -> Option<Receiver<GapEvent>> { self.gaps.as_ref().map(|gaps| gaps.rx.clone()) }
+
+    /// Fecha `range` nas lacunas abertas de (símbolo, fonte): a retransmissão
+    /// chegou (ou foi abandonada) e sequências nesse intervalo voltam a ser
+    /// rejeitadas como duplicatas.
+    pub fn mark_filled(&mut self, symbol: &[u8; 8], source: u8, range: RangeInclusive<u64>) {
+        if let Some(gaps) = &mut self.gaps {
+            gaps.fill(symbol, source, range);
         }
+    }
 
-        Self { known_symbols: known, allow_unknown: false }
+    /// Lacunas ainda abertas de (símbolo, fonte), em ordem.
+    pub fn pending_gaps(&self, symbol: &[u8; 8], source: u8) -> Vec<RangeInclusive<u64>> {
+        let key = LastTimestamp { symbol: *symbol, source };
+        self.gaps.as_ref().and_then(|gaps| gaps.pending.get(&key)).cloned().unwrap_or_default()
     }
 
-    pub fn permissive() -> Self {
-        Self { known_symbols: HashSet::new(), allow_unknown: true }
+    /// Se `sequence` é a retransmissão de uma lacuna aberta.
+    pub fn is_gap_fill(&self, symbol: &[u8; 8], source: u8, sequence: u64) -> bool {
+        self.gaps.as_ref().is_some_and(|gaps| gaps.contains(symbol, source, sequence))
     }
 
-    pub fn validate(&self, symbol: &[u8; 8]) -> Result<(), ValidationError> {
-        for &byte in symbol {
-            if byte != 0 && !(byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_') {
-                return Err(ValidationError::InvalidSymbol(format!("Caractere inválido: {}", byte)));
+    pub fn validate_sequence(
+        &mut self,
+        symbol: &[u8; 8],
+        source: u8,
+        sequence: u64,
+    ) -> Result<(), ValidationError> {
+        self.check_sequence(symbol, source, sequence)?;
+        self.record_sequence(symbol, source, sequence);
+        Ok(())
+    }
+
+    /// Avança a sequência esperada (e abre ou fecha lacunas) sem checar; par
+    /// de `check_sequence`, para registrar só depois que os demais estágios
+    /// aceitaram a mensagem.
+    pub fn record_sequence(&mut self, symbol: &[u8; 8], source: u8, sequence: u64) {
+        if let Some(gaps) = &mut self.gaps {
+            if gaps.contains(symbol, source, sequence) {
+                gaps.fill(symbol, source, sequence..=sequence);
+                return;
+            }
+            let expected = self.last_sequences.get(symbol, source).map(|last| last.wrapping_add(1));
+            if let Some(expected) = expected.filter(|&expected| sequence > expected) {
+                let event = GapEvent { symbol: *symbol, source, from_seq: expected, to_seq: sequence - 1 };
+                gaps.pending.entry(LastTimestamp { symbol: *symbol, source }).or_default().push(expected..=sequence - 1);
+                if gaps.tx.try_send(event).is_err() {
+                    warn!("Evento de lacuna descartado (canal cheio): {:?}", event);
+                }
             }
         }
+        self.last_sequences.set(symbol, source, sequence);
+    }
 
-        if !self.allow_unknown && !self.known_symbols.contains(symbol) {
-            return Err(ValidationError::InvalidSymbol(format!("Símbolo desconhecido: {}", String::from_utf8_lossy(symbol))));
+    /// Como `validate_sequence`, sem avançar a sequência esperada.
+    pub fn check_sequence(&self, symbol: &[u8; 8], source: u8, sequence: u64) -> Result<(), ValidationError> {
+        if let Some(last) = self.last_sequences.get(symbol, source) {
+            let expected = last.wrapping_add(1);
+            let recoverable = self.gaps.is_some() && (sequence > expected || self.is_gap_fill(symbol, source, sequence));
+            if sequence != expected && !recoverable {
+                return Err(ValidationError::SequenceViolation { expected, received: sequence });
+            }
         }
-
         Ok(())
     }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.last_sequences.set_clock(clock);
+        self
+    }
+
+    /// Como `TemporalValidator::compact`; lacunas abertas dos pares esquecidos
+    /// também são descartadas.
+    pub fn compact(&mut self, older_than: Duration) -> usize {
+        let removed = self.last_sequences.compact(older_than);
+        if let Some(gaps) = &mut self.gaps {
+            let last_sequences = &self.last_sequences;
+            gaps.pending.retain(|key, _| last_sequences.get(&key.symbol, key.source).is_some());
+        }
+        removed
+    }
+
+    /// Esquece todas as sequências e lacunas abertas. Eventos já anunciados
+    /// continuam no canal, que segue o mesmo.
+    pub fn reset(&mut self) {
+        self.last_sequences.clear();
+        if let Some(gaps) = &mut self.gaps {
+            gaps.pending.clear();
+        }
+    }
+
+    pub fn tracked(&self) -> usize { self.last_sequences.len() }
 }
 
-pub struct CompositeValidator {
-    checksum: ChecksumValidator,
-    bounds: DataBounds,
-    temporal: TemporalValidator,
-    symbol: SymbolValidator,
+impl Default for SequenceValidator {
+    fn default() -> Self { Self::new() }
 }
 
-impl CompositeValidator {
-    pub fn new(bounds: DataBounds, symbol_validator: SymbolValidator) -> Self {
-        Self {
-            checksum: ChecksumValidator::new(),
-            bounds,
-            temporal: TemporalValidator::new(Duration::from_millis(1)),
-            symbol: symbol_validator,
-        }
+/// Quão estrita é a monotonicidade de `trade_id` exigida pelo `TradeIdValidator`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TradeIdStrictness {
+    /// Cada `trade_id` deve ser maior que o anterior; duplicatas são rejeitadas.
+    #[default]
+    Strict,
+    /// Repetições do último `trade_id` são aceitas (feeds que reenviam trades).
+    AllowDuplicates,
+}
+
+/// Exige `trade_id` crescente por (símbolo, fonte), sem exigir contiguidade
+/// como o `SequenceValidator`. O primeiro `trade_id` visto é sempre aceito.
+pub struct TradeIdValidator {
+    last_ids: LastPerSymbol,
+    strictness: TradeIdStrictness,
+}
+
+impl TradeIdValidator {
+    pub fn new(strictness: TradeIdStrictness) -> Self {
+        Self { last_ids: LastPerSymbol::default(), strictness }
     }
 
-    pub fn validate_message(
+    pub fn validate_trade_id(
         &mut self,
-        header: &crate::ingestion::zero_copy::MessageHeader,
-        payload: &[u8],
+        symbol: &[u8; 8],
+        source: u8,
+        trade_id: u64,
     ) -> Result<(), ValidationError> {
-        self.checksum.validate(payload, header.checksum)?;
+        self.check_trade_id(symbol, source, trade_id)?;
+        self.record_trade_id(symbol, source, trade_id);
+        Ok(())
+    }
+
+    /// Registra `trade_id` como o último visto, sem checar; par de `check_trade_id`.
+    pub fn record_trade_id(&mut self, symbol: &[u8; 8], source: u8, trade_id: u64) {
+        self.last_ids.set(symbol, source, trade_id);
+    }
+
+    /// Como `validate_trade_id`, sem registrar `trade_id` como o último visto.
+    pub fn check_trade_id(&self, symbol: &[u8; 8], source: u8, trade_id: u64) -> Result<(), ValidationError> {
+        if let Some(prev) = self.last_ids.get(symbol, source) {
+            let regressed = match self.strictness {
+                TradeIdStrictness::Strict => trade_id <= prev,
+                TradeIdStrictness::AllowDuplicates => trade_id < prev,
+            };
+            if regressed {
+                return Err(ValidationError::TradeIdRegression { prev, current: trade_id });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.last_ids.set_clock(clock);
+        self
+    }
+
+    pub fn compact(&mut self, older_than: Duration) -> usize { self.last_ids.compact(older_than) }
+
+    pub fn reset(&mut self) { self.last_ids.clear() }
 
-        match header.msg_type {
-            0 | 1 | 2 | 3 => {},
-            _ => return Err(ValidationError::UnknownMessageType(header.msg_type)),
+    pub fn tracked(&self) -> usize { self.last_ids.len() }
+}
+
+/// Limita o número de mensagens por símbolo dentro de uma janela fixa, medida
+/// pelos timestamps das próprias mensagens (determinístico em replay).
+pub struct RateLimiter {
+    max_messages: u32,
+    window: u64,
+    windows: std::collections::HashMap<[u8; 8], (u64, u32)>,
+}
+
+impl RateLimiter {
+    pub fn new(max_messages: u32, window: Duration) -> Self {
+        Self {
+            max_messages,
+            window: window.as_nanos() as u64,
+            windows: std::collections::HashMap::new(),
         }
+    }
 
-        self.bounds.validate_timestamp(header.timestamp)?;
+    pub fn check(&mut self, symbol: &[u8; 8], timestamp: u64) -> Result<(), ValidationError> {
+        let (window_start, count) = self.windows.entry(*symbol).or_insert((timestamp, 0));
+
+        if timestamp.saturating_sub(*window_start) >= self.window {
+            *window_start = timestamp;
+            *count = 0;
+        }
 
-        match header.msg_type {
-            0 => self.validate_trade(payload)?,
-            1 => self.validate_quote(payload)?,
-            _ => {},
+        if *count >= self.max_messages {
+            return Err(ValidationError::RateLimitExceeded { limit: self.max_messages });
         }
 
+        *count += 1;
         Ok(())
     }
 
-    fn validate_trade(&mut self, payload: &[u8]) -> Result<(), ValidationError> {
-        use crate::ingestion::zero_copy::Trade;
+    /// Zera as janelas de todos os símbolos.
+    pub fn reset(&mut self) { self.windows.clear() }
 
-        if payload.len() < std::mem::size_of::<Trade>() {
-            return Err(ValidationError::CorruptedFormat);
+    /// Resultado que `check` daria agora, sem contar a mensagem.
+    pub fn peek(&self, symbol: &[u8; 8], timestamp: u64) -> Result<(), ValidationError> {
+        match self.windows.get(symbol) {
+            Some(&(window_start, count))
+                if timestamp.saturating_sub(window_start) < self.window && count >= self.max_messages =>
+            {
+                Err(ValidationError::RateLimitExceeded { limit: self.max_messages })
+            }
+            _ => Ok(()),
         }
+    }
+}
 
-        let trade = unsafe { *(payload.as_ptr() as *const Trade) };
+/// Símbolo do fio como `&str`: termina no primeiro byte nulo, e o restante
+/// dos 8 bytes precisa ser só preenchimento nulo. Bytes que não formam UTF-8
+/// válido são erro, em vez de virarem `U+FFFD` como numa conversão lossy.
+pub fn symbol_str(symbol: &[u8; 8]) -> Result<&str, ValidationError> {
+    let len = symbol.iter().position(|&b| b == 0).unwrap_or(symbol.len());
+    if symbol[len..].iter().any(|&b| b != 0) {
+        return Err(ValidationError::InvalidSymbol(SymbolIssue::ByteAfterNul(*symbol)));
+    }
+    std::str::from_utf8(&symbol[..len])
+        .map_err(|_| ValidationError::InvalidSymbol(SymbolIssue::InvalidUtf8(*symbol)))
+}
 
-        self.symbol.validate(&trade.symbol)?;
+/// Regras para levar variantes de um símbolo ("btc-usd", "BTC/USD") a uma forma
+/// canônica ("BTCUSD"). A padrão não altera nada.
+///
+/// Símbolos do fio já chegam truncados em 8 bytes, então a normalização é feita
+/// depois do truncamento: "BTC-USDTX" vira "BTC-USDT" no fio e "BTCUSDT" aqui.
+/// Entradas da whitelist são normalizadas antes de truncar, e portanto só casam
+/// com variantes cuja forma com separadores também coube em 8 bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolNormalization {
+    uppercase: bool,
+    separators: Vec<u8>,
+}
 
-        let price = trade.price as f64 / 1e8;
-        let qty = trade.quantity as f64 / 1e8;
+impl SymbolNormalization {
+    pub fn new() -> Self { Self::default() }
 
-        self.bounds.validate_price(price, "price")?;
-        self.bounds.validate_quantity(qty, "quantity")?;
-        self.temporal.validate_monotonic(&trade.symbol, 0, trade.timestamp)?;
+    pub fn uppercase(mut self) -> Self { self.uppercase = true; self }
 
-        Ok(())
-    }
+    /// Bytes removidos do símbolo (o restante é compactado à esquerda).
+    pub fn strip_separators(mut self, separators: &[u8]) -> Self { self.separators = separators.to_vec(); self }
 
-    fn validate_quote(&mut self, payload: &[u8]) -> Result<(), ValidationError> {
-        use crate::ingestion::zero_copy::Quote;
+    pub fn is_identity(&self) -> bool { !self.uppercase && self.separators.is_empty() }
 
-        if payload.len() < std::mem::size_of::<Quote>() {
-            return Err(ValidationError::CorruptedFormat);
+    pub fn normalize(&self, symbol: &[u8; 8]) -> [u8; 8] {
+        if self.is_identity() {
+            return *symbol;
         }
+        let mut out = [0u8; 8];
+        self.normalize_into(symbol.iter().copied().take_while(|&b| b != 0), &mut out);
+        out
+    }
 
-        let quote = unsafe { *(payload.as_ptr() as *const Quote) };
+    fn normalize_into(&self, bytes: impl Iterator<Item = u8>, out: &mut [u8; 8]) {
+        let kept = bytes
+            .filter(|b| !self.separators.contains(b))
+            .map(|b| if self.uppercase { b.to_ascii_uppercase() } else { b });
+        for (slot, byte) in out.iter_mut().zip(kept) {
+            *slot = byte;
+        }
+    }
+}
 
-        self.symbol.validate(&quote.symbol)?;
+pub struct SymbolValidator {
+    known_symbols: HashSet<[u8; 8]>,
+    allow_unknown: bool,
+    strict_utf8: bool,
+    reject_empty: bool,
+    normalization: SymbolNormalization,
+    /// Whitelist original, para renormalizar quando as regras mudam.
+    whitelist: Vec<String>,
+    /// Prefixos normalizados (completados com NUL), ordenados para busca binária.
+    known_prefixes: Vec<[u8; 8]>,
+    /// Bit `n - 1` ligado se há algum prefixo de `n` bytes.
+    prefix_lengths: u8,
+    prefixes: Vec<String>,
+}
 
-        let bid_price = quote.bid_price as f64 / 1e8;
-        let ask_price = quote.ask_price as f64 / 1e8;
+impl SymbolValidator {
+    pub fn whitelist(symbols: Vec<String>) -> Self {
+        let mut validator = Self {
+            known_symbols: HashSet::new(),
+            allow_unknown: false,
+            strict_utf8: false,
+            reject_empty: false,
+            normalization: SymbolNormalization::default(),
+            whitelist: symbols,
+            known_prefixes: Vec::new(),
+            prefix_lengths: 0,
+            prefixes: Vec::new(),
+        };
+        validator.rebuild_known();
+        validator
+    }
 
-        if bid_price >= ask_price {
-            return Err(ValidationError::InvalidSymbol("Bid deve ser menor que Ask".to_string()));
+    pub fn permissive() -> Self {
+        Self {
+            known_symbols: HashSet::new(),
+            allow_unknown: true,
+            strict_utf8: false,
+            reject_empty: false,
+            normalization: SymbolNormalization::default(),
+            whitelist: Vec::new(),
+            known_prefixes: Vec::new(),
+            prefix_lengths: 0,
+            prefixes: Vec::new(),
         }
+    }
 
-        self.bounds.validate_price(bid_price, "bid_price")?;
-        self.bounds.validate_price(ask_price, "ask_price")?;
+    /// Aceita qualquer símbolo bem formado, sem consultar whitelist: UTF-8
+    /// com preenchimento nulo só no final (como `strict_utf8`), caracteres
+    /// permitidos e ao menos um byte. Para universos de símbolos que crescem
+    /// em runtime, onde `permissive` deixaria passar lixo.
+    pub fn format_only() -> Self {
+        Self { reject_empty: true, ..Self::permissive().strict_utf8() }
+    }
 
-        Ok(())
+    /// Aceita também qualquer símbolo que comece com um destes prefixos
+    /// (normalizados como a whitelist). A busca exata tem precedência: os
+    /// prefixos só são consultados para símbolos fora da whitelist, e um
+    /// prefixo casa com o próprio símbolo ("BTC" aceita "BTC"). Prefixos vazios
+    /// são ignorados (para aceitar tudo, use `permissive`).
+    pub fn with_prefixes(mut self, prefixes: Vec<String>) -> Self {
+        self.prefixes = prefixes;
+        self.rebuild_known();
+        self
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Normaliza símbolos antes da whitelist e das chaves de estado por símbolo
+    /// do `CompositeValidator`.
+    pub fn with_normalization(mut self, normalization: SymbolNormalization) -> Self {
+        self.normalization = normalization;
+        self.rebuild_known();
+        self
+    }
 
-    #[test]
-    fn test_checksum_validation() {
-        let validator = ChecksumValidator::new();
-        let data = b"Hello, world!";
-        let checksum = validator.calculate(data);
-        assert!(validator.validate(data, checksum).is_ok());
+    /// Exige que o símbolo seja UTF-8 válido com preenchimento nulo só no
+    /// final (ver `symbol_str`) antes das demais checagens, inclusive no
+    /// validador permissivo.
+    pub fn strict_utf8(mut self) -> Self {
+        self.strict_utf8 = true;
+        self
     }
 
-    #[test]
-    fn test_bounds_validation() {
-        let bounds = DataBounds::crypto();
+    fn rebuild_known(&mut self) {
+        self.known_symbols = self
+            .whitelist
+            .iter()
+            .map(|sym| {
+                let mut bytes = [0u8; 8];
+                self.normalization.normalize_into(sym.bytes(), &mut bytes);
+                bytes
+            })
+            .collect();
+
+        self.known_prefixes.clear();
+        self.prefix_lengths = 0;
+        for prefix in &self.prefixes {
+            let mut bytes = [0u8; 8];
+            self.normalization.normalize_into(prefix.bytes(), &mut bytes);
+            let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+            if len > 0 {
+                self.known_prefixes.push(bytes);
+                self.prefix_lengths |= 1 << (len - 1);
+            }
+        }
+        self.known_prefixes.sort_unstable();
+        self.known_prefixes.dedup();
+    }
+
+    /// Uma busca binária por comprimento de prefixo registrado (no máximo 8).
+    /// Símbolo mais curto que o prefixo não casa: o NUL fica dentro de `head`.
+    fn matches_prefix(&self, symbol: &[u8; 8]) -> bool {
+        (1..=8).filter(|len| self.prefix_lengths & (1 << (len - 1)) != 0).any(|len| {
+            let mut head = [0u8; 8];
+            head[..len].copy_from_slice(&symbol[..len]);
+            self.known_prefixes.binary_search(&head).is_ok()
+        })
+    }
+
+    fn is_known(&self, symbol: &[u8; 8]) -> bool {
+        self.known_symbols.contains(symbol) || (self.prefix_lengths != 0 && self.matches_prefix(symbol))
+    }
+
+    #[inline]
+    pub fn normalize(&self, symbol: &[u8; 8]) -> [u8; 8] { self.normalization.normalize(symbol) }
+
+    /// Símbolos da whitelist na forma canônica (já normalizados), em ordem
+    /// arbitrária. Vazio para o validador permissivo.
+    pub fn known_symbols(&self) -> impl Iterator<Item = String> + '_ {
+        // Entradas truncadas no meio de um caractere não casam com nenhum símbolo válido
+        self.known_symbols.iter().filter_map(|symbol| symbol_str(symbol).ok().map(str::to_owned))
+    }
+
+    /// Se `symbol` (depois de normalizado) é aceito pela whitelist; sempre
+    /// `true` no validador permissivo. Não verifica os caracteres (ver `validate`).
+    pub fn contains(&self, symbol: &str) -> bool {
+        if self.allow_unknown {
+            return true;
+        }
+        self.is_known(&self.normalize_str(symbol))
+    }
+
+    fn normalize_str(&self, symbol: &str) -> [u8; 8] {
+        let mut bytes = [0u8; 8];
+        self.normalization.normalize_into(symbol.bytes(), &mut bytes);
+        bytes
+    }
+
+    pub fn validate(&self, symbol: &[u8; 8]) -> Result<(), ValidationError> {
+        if self.strict_utf8 {
+            symbol_str(symbol)?;
+        }
+        let symbol = &self.normalize(symbol);
+        if self.reject_empty && symbol[0] == 0 {
+            return Err(ValidationError::InvalidSymbol(SymbolIssue::Empty));
+        }
+        for &byte in symbol {
+            if byte != 0 && !(byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_') {
+                return Err(ValidationError::InvalidSymbol(SymbolIssue::InvalidChar(byte)));
+            }
+        }
+
+        if !self.allow_unknown && !self.is_known(symbol) {
+            symbol_str(symbol)?;
+            return Err(ValidationError::InvalidSymbol(SymbolIssue::Unknown(*symbol)));
+        }
+
+        Ok(())
+    }
+}
+
+/// Ordem de execução dos estágios de `CompositeValidator::validate_message`.
+///
+/// `FastReject` roda primeiro as verificações baratas (tipo, timestamp, bounds,
+/// símbolo) e só calcula o CRC se todas passarem. Isso significa confiar em campos
+/// ainda não verificados pelo checksum para rejeitar cedo: uma mensagem corrompida
+/// pode ser rejeitada pelo motivo "errado" (ex.: bounds em vez de checksum), mas
+/// nenhuma mensagem é aceita sem CRC válido e o estado temporal só é atualizado
+/// depois do checksum em ambos os modos.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ValidationOrder {
+    #[default]
+    ChecksumFirst,
+    FastReject,
+}
+
+/// O que fazer com `msg_type` fora dos tipos conhecidos.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnknownTypePolicy {
+    /// Rejeita com `UnknownMessageType`.
+    #[default]
+    Reject,
+    /// Aceita após checksum e timestamp, sem validação específica de payload.
+    Accept,
+}
+
+/// O que fazer quando uma mensagem falha na validação.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FailureAction {
+    /// Retorna o erro (a mensagem é rejeitada).
+    #[default]
+    Reject,
+    /// Registra um `warn!` e nas estatísticas por tipo, mas aceita a mensagem.
+    LogAndPass,
+}
+
+/// Presets do `CompositeValidator` inteiro, aplicados com
+/// `CompositeValidatorBuilder::profile` antes de ajustes individuais.
+///
+/// | verificação                      | Strict    | Lenient      | Development |
+/// |----------------------------------|-----------|--------------|-------------|
+/// | checksum (CRC)                   | sim       | sim          | não         |
+/// | tipo desconhecido                | rejeita   | rejeita      | aceita      |
+/// | timestamp (janela de época)      | sim       | sim          | sim         |
+/// | timestamp futuro (> agora + 5s)  | sim       | sim          | não         |
+/// | bounds, símbolo, book            | sim       | sim          | sim         |
+/// | ordem temporal (tolerância 1ms)  | sim       | sim          | não         |
+/// | trade_id crescente               | sim       | sim          | não         |
+/// | falha                            | rejeita   | loga e passa | rejeita     |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrictnessProfile {
+    /// Produção: rejeita tudo que for suspeito.
+    Strict,
+    /// Staging: roda as mesmas verificações de `Strict`, mas só registra as falhas.
+    Lenient,
+    /// Desenvolvimento: só verificações baratas e sem estado, para velocidade.
+    Development,
+}
+
+pub struct CompositeValidator {
+    checksum: ChecksumValidator,
+    /// Substitui o CRC no checksum do payload (o do header v2 segue CRC).
+    custom_checksum: Option<Arc<dyn Checksum>>,
+    /// Trocável em runtime (ver `update_bounds`); cada etapa da validação lê
+    /// um snapshot inteiro, sem lock.
+    bounds: Arc<ArcSwap<DataBounds>>,
+    temporal: TemporalValidator,
+    symbol: SymbolValidator,
+    sequence: Option<SequenceValidator>,
+    trade_id: Option<TradeIdValidator>,
+    rate_limiter: Option<RateLimiter>,
+    unknown_type_policy: UnknownTypePolicy,
+    registry: MessageTypeRegistry,
+    checksum_exempt: [bool; 256],
+    source_types: HashMap<u8, Box<[bool; 256]>>,
+    future_tolerance: FutureTolerance,
+    spread_limits: SpreadLimits,
+    clock: Arc<dyn Clock>,
+    verify_checksums: bool,
+    temporal_checks: bool,
+    failure_action: FailureAction,
+    order: ValidationOrder,
+    strict_padding: bool,
+    audit: Option<Arc<AuditLogger>>,
+    type_stats: MessageTypeStats,
+    batch_scratch: BatchScratch,
+    #[cfg(feature = "stage-timing")]
+    timings: StageClock,
+}
+
+/// Estado por símbolo de um `TradeBatch` em validação, reaproveitado entre
+/// lotes (limpo a cada um, mantendo a capacidade).
+#[derive(Default)]
+struct BatchScratch {
+    /// Último (timestamp, trade_id) de cada símbolo já conferido no lote.
+    last: HashMap<[u8; 8], (u64, u64)>,
+    /// Janela do `RateLimiter` de cada símbolo antes do lote, para desfazer.
+    windows: HashMap<[u8; 8], Option<(u64, u32)>>,
+}
+
+/// Acesso compartilhado aos limites de um `CompositeValidator` (ver
+/// `CompositeValidator::bounds_handle`).
+#[derive(Clone)]
+pub struct BoundsHandle(Arc<ArcSwap<DataBounds>>);
+
+impl BoundsHandle {
+    /// Como `CompositeValidator::update_bounds`.
+    pub fn update(&self, bounds: DataBounds) { self.0.store(Arc::new(bounds)); }
+    pub fn current(&self) -> DataBounds { **self.0.load() }
+}
+
+/// Quanto à frente do relógio local um timestamp pode estar, por fonte.
+/// Fontes sem tolerância própria usam `default`; sem nenhuma, não há checagem.
+#[derive(Debug, Clone, Default)]
+struct FutureTolerance {
+    default: Option<u64>,
+    per_source: HashMap<u8, u64>,
+}
+
+impl FutureTolerance {
+    fn for_source(&self, source: u8) -> Option<u64> {
+        self.per_source.get(&source).copied().or(self.default)
+    }
+}
+
+/// Spread relativo máximo `(ask - bid) / bid` de uma quote, por símbolo
+/// (8 bytes como chegam, completados com NUL). Símbolos sem limite próprio
+/// usam `default`; sem nenhum, não há checagem.
+#[derive(Debug, Clone, Default)]
+struct SpreadLimits {
+    default: Option<f64>,
+    per_symbol: HashMap<[u8; 8], f64>,
+}
+
+impl SpreadLimits {
+    /// As chaves já estão normalizadas; só normaliza `symbol` se houver alguma.
+    fn for_symbol(&self, normalizer: &SymbolValidator, symbol: &[u8; 8]) -> Option<f64> {
+        if self.per_symbol.is_empty() {
+            return self.default;
+        }
+        self.per_symbol.get(&normalizer.normalize(symbol)).copied().or(self.default)
+    }
+}
+
+/// Monta um `CompositeValidator` a partir das opções desejadas. Sem ajustes,
+/// equivale a `CompositeValidator::new(DataBounds::crypto(), SymbolValidator::permissive())`.
+pub struct CompositeValidatorBuilder {
+    bounds: DataBounds,
+    symbol: SymbolValidator,
+    temporal_tolerance: Duration,
+    checksum_algorithm: ChecksumAlgorithm,
+    custom_checksum: Option<Arc<dyn Checksum>>,
+    order: ValidationOrder,
+    sequence: bool,
+    gap_events: Option<usize>,
+    trade_id: Option<TradeIdStrictness>,
+    rate_limit: Option<(u32, Duration)>,
+    unknown_type_policy: UnknownTypePolicy,
+    registry: MessageTypeRegistry,
+    checksum_exempt: [bool; 256],
+    source_types: HashMap<u8, Box<[bool; 256]>>,
+    future_tolerance: FutureTolerance,
+    spread_limits: SpreadLimits,
+    symbol_spreads: Vec<(String, f64)>,
+    clock: Arc<dyn Clock>,
+    verify_checksums: bool,
+    temporal_checks: bool,
+    failure_action: FailureAction,
+    strict_padding: bool,
+    audit: Option<Arc<AuditLogger>>,
+}
+
+impl Default for CompositeValidatorBuilder {
+    fn default() -> Self {
+        Self {
+            bounds: DataBounds::crypto(),
+            symbol: SymbolValidator::permissive(),
+            temporal_tolerance: Duration::from_millis(1),
+            checksum_algorithm: ChecksumAlgorithm::default(),
+            custom_checksum: None,
+            order: ValidationOrder::default(),
+            sequence: false,
+            gap_events: None,
+            trade_id: None,
+            rate_limit: None,
+            unknown_type_policy: UnknownTypePolicy::default(),
+            registry: MessageTypeRegistry::standard(),
+            checksum_exempt: [false; 256],
+            source_types: HashMap::new(),
+            future_tolerance: FutureTolerance::default(),
+            spread_limits: SpreadLimits::default(),
+            symbol_spreads: Vec::new(),
+            clock: Arc::new(SystemClock),
+            verify_checksums: true,
+            temporal_checks: true,
+            failure_action: FailureAction::default(),
+            strict_padding: false,
+            audit: None,
+        }
+    }
+}
+
+impl CompositeValidatorBuilder {
+    pub fn bounds(mut self, bounds: DataBounds) -> Self { self.bounds = bounds; self }
+    pub fn symbol_validator(mut self, symbol: SymbolValidator) -> Self { self.symbol = symbol; self }
+    pub fn temporal_tolerance(mut self, tolerance: Duration) -> Self { self.temporal_tolerance = tolerance; self }
+    pub fn checksum_algorithm(mut self, algorithm: ChecksumAlgorithm) -> Self { self.checksum_algorithm = algorithm; self }
+
+    /// Valida o checksum do payload com `checksum` em vez do CRC de
+    /// `checksum_algorithm`. O checksum do próprio header (v2) continua CRC, e
+    /// o CRC guardado na ingestão (`cached_checksum`) deixa de ser reaproveitado.
+    /// Frames montados pelo ingestor só passam se ele usar o mesmo checksum
+    /// (`MarketDataIngestor::with_custom_checksum`).
+    pub fn custom_checksum(mut self, checksum: impl Checksum + 'static) -> Self {
+        self.custom_checksum = Some(Arc::new(checksum));
+        self
+    }
+    pub fn order(mut self, order: ValidationOrder) -> Self { self.order = order; self }
+    pub fn unknown_type_policy(mut self, policy: UnknownTypePolicy) -> Self { self.unknown_type_policy = policy; self }
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self { self.clock = clock; self }
+    pub fn verify_checksums(mut self, enabled: bool) -> Self { self.verify_checksums = enabled; self }
+    pub fn temporal_checks(mut self, enabled: bool) -> Self { self.temporal_checks = enabled; self }
+    pub fn failure_action(mut self, action: FailureAction) -> Self { self.failure_action = action; self }
+
+    /// Rejeita trades e quotes (inclusive dentro de lotes) com padding não
+    /// nulo (`NonZeroPadding`), pegando produtores com layout divergente.
+    pub fn strict_padding(mut self, enabled: bool) -> Self { self.strict_padding = enabled; self }
+
+    /// Grava cada mensagem rejeitada (frame, motivo e instante) em `audit`.
+    /// Falhas aceitas por `FailureAction::LogAndPass` não são gravadas.
+    pub fn audit_log(mut self, audit: Arc<AuditLogger>) -> Self { self.audit = Some(audit); self }
+    pub fn registry(mut self, registry: MessageTypeRegistry) -> Self { self.registry = registry; self }
+
+    /// Registra (ou substitui) um tipo de mensagem no registro padrão.
+    pub fn message_type(mut self, msg_type: u8, descriptor: MessageTypeDescriptor) -> Self {
+        self.registry.register(msg_type, descriptor);
+        self
+    }
+
+    /// Aplica um preset completo (ver `StrictnessProfile`). Bounds, símbolos e
+    /// relógio não são alterados; ajustes chamados depois sobrescrevem o preset.
+    pub fn profile(self, profile: StrictnessProfile) -> Self {
+        let strict = self
+            .verify_checksums(true)
+            .unknown_type_policy(UnknownTypePolicy::Reject)
+            .future_tolerance(Duration::from_secs(5))
+            .temporal_checks(true)
+            .temporal_tolerance(Duration::from_millis(1))
+            .with_trade_id_validator(TradeIdStrictness::Strict);
+
+        match profile {
+            StrictnessProfile::Strict => strict.failure_action(FailureAction::Reject),
+            StrictnessProfile::Lenient => strict.failure_action(FailureAction::LogAndPass),
+            StrictnessProfile::Development => {
+                let mut dev = strict
+                    .verify_checksums(false)
+                    .unknown_type_policy(UnknownTypePolicy::Accept)
+                    .temporal_checks(false)
+                    .failure_action(FailureAction::Reject);
+                dev.future_tolerance.default = None;
+                dev.trade_id = None;
+                dev
+            }
+        }
+    }
+
+    /// Tipos cujo CRC não é verificado (heartbeats e mensagens de controle que
+    /// trafegam com checksum zerado). Trades e quotes são ignorados aqui e
+    /// sempre têm o checksum validado.
+    pub fn checksum_exempt_types(mut self, msg_types: impl IntoIterator<Item = u8>) -> Self {
+        use crate::ingestion::zero_copy::{Quote, Trade};
+
+        for msg_type in msg_types {
+            if msg_type != Trade::MSG_TYPE && msg_type != Quote::MSG_TYPE {
+                self.checksum_exempt[msg_type as usize] = true;
+            }
+        }
+        self
+    }
+
+    /// Restringe `source` aos tipos em `msg_types` (chamadas repetidas
+    /// acumulam): os demais são rejeitados com `TypeNotAllowedForSource`, o
+    /// que pega dados roteados para a fonte errada ou forjados. Fontes sem
+    /// restrição aceitam qualquer tipo. Mensagens de controle (fim de sessão)
+    /// também precisam constar na lista.
+    pub fn allowed_types_for_source(mut self, source: u8, msg_types: impl IntoIterator<Item = u8>) -> Self {
+        let allowed = self.source_types.entry(source).or_insert_with(|| Box::new([false; 256]));
+        for msg_type in msg_types {
+            allowed[msg_type as usize] = true;
+        }
+        self
+    }
+
+    /// Rejeita mensagens com timestamp além de `agora + tolerance` (pelo relógio
+    /// do validador), pegando produtores com relógio adiantado.
+    pub fn future_tolerance(mut self, tolerance: Duration) -> Self {
+        self.future_tolerance.default = Some(tolerance.as_nanos() as u64);
+        self
+    }
+
+    /// Tolerância de timestamp futuro específica de uma fonte (ver `validate_message_from`).
+    pub fn future_tolerance_for_source(mut self, source: u8, tolerance: Duration) -> Self {
+        self.future_tolerance.per_source.insert(source, tolerance.as_nanos() as u64);
+        self
+    }
+
+    /// Rejeita quotes com `(ask - bid) / bid` acima de `max_relative` (ex.:
+    /// 0.05 = 5%): spreads absurdos costumam ser um lado velho da cotação.
+    pub fn max_relative_spread(mut self, max_relative: f64) -> Self {
+        self.spread_limits.default = Some(max_relative);
+        self
+    }
+
+    /// Spread relativo máximo específico de um símbolo (ver `max_relative_spread`),
+    /// casado depois da normalização do `SymbolValidator`.
+    pub fn max_relative_spread_for_symbol(mut self, symbol: &str, max_relative: f64) -> Self {
+        self.symbol_spreads.push((symbol.to_owned(), max_relative));
+        self
+    }
+
+    /// Exige `trade_id` contíguo por símbolo (ver `SequenceValidator`).
+    pub fn with_sequence_validator(mut self) -> Self { self.sequence = true; self }
+
+    /// Como `with_sequence_validator`, aceitando lacunas e anunciando-as em
+    /// `CompositeValidator::gap_events` (ver `SequenceValidator::with_gap_events`).
+    /// Retransmissões passam sem as checagens de ordem temporal e de `trade_id`.
+    pub fn with_gap_recovery(mut self, capacity: usize) -> Self {
+        self.sequence = true;
+        self.gap_events = Some(capacity);
+        self
+    }
+
+    /// Exige `trade_id` crescente por símbolo (ver `TradeIdValidator`).
+    pub fn with_trade_id_validator(mut self, strictness: TradeIdStrictness) -> Self {
+        self.trade_id = Some(strictness);
+        self
+    }
+
+    /// No máximo `max_messages` trades/quotes por símbolo a cada `window`.
+    pub fn with_rate_limiter(mut self, max_messages: u32, window: Duration) -> Self {
+        self.rate_limit = Some((max_messages, window));
+        self
+    }
+
+    pub fn build(self) -> CompositeValidator {
+        // O validador de símbolos pode ser trocado depois do override
+        let mut spread_limits = self.spread_limits;
+        for (symbol, max_relative) in &self.symbol_spreads {
+            spread_limits.per_symbol.insert(self.symbol.normalize_str(symbol), *max_relative);
+        }
+        CompositeValidator {
+            checksum: ChecksumValidator::with_algorithm(self.checksum_algorithm),
+            custom_checksum: self.custom_checksum,
+            bounds: Arc::new(ArcSwap::from_pointee(self.bounds)),
+            temporal: TemporalValidator::new(self.temporal_tolerance).with_clock(self.clock.clone()),
+            symbol: self.symbol,
+            sequence: self.sequence.then(|| {
+                let sequence = SequenceValidator::new().with_clock(self.clock.clone());
+                match self.gap_events {
+                    Some(capacity) => sequence.with_gap_events(capacity),
+                    None => sequence,
+                }
+            }),
+            trade_id: self.trade_id.map(|strictness| TradeIdValidator::new(strictness).with_clock(self.clock.clone())),
+            rate_limiter: self.rate_limit.map(|(max, window)| RateLimiter::new(max, window)),
+            unknown_type_policy: self.unknown_type_policy,
+            registry: self.registry,
+            checksum_exempt: self.checksum_exempt,
+            source_types: self.source_types,
+            future_tolerance: self.future_tolerance,
+            spread_limits,
+            clock: self.clock,
+            verify_checksums: self.verify_checksums,
+            temporal_checks: self.temporal_checks,
+            failure_action: self.failure_action,
+            order: self.order,
+            strict_padding: self.strict_padding,
+            audit: self.audit,
+            type_stats: MessageTypeStats::new(),
+            batch_scratch: BatchScratch::default(),
+            #[cfg(feature = "stage-timing")]
+            timings: StageClock::default(),
+        }
+    }
+}
+
+impl CompositeValidator {
+    pub fn new(bounds: DataBounds, symbol_validator: SymbolValidator) -> Self {
+        Self::builder().bounds(bounds).symbol_validator(symbol_validator).build()
+    }
+
+    pub fn builder() -> CompositeValidatorBuilder {
+        CompositeValidatorBuilder::default()
+    }
+
+    pub fn with_order(mut self, order: ValidationOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub fn order(&self) -> ValidationOrder { self.order }
+
+    /// Descarta o estado por símbolo (timestamps, sequências, trade_ids) sem
+    /// atualização há mais de `older_than`, segundo o relógio do validador.
+    /// Pensado para ser agendado periodicamente pelo operador; um símbolo
+    /// compactado volta a ser tratado como visto pela primeira vez.
+    pub fn compact(&mut self, older_than: Duration) -> usize {
+        self.temporal.compact(older_than)
+            + self.sequence.as_mut().map_or(0, |sequence| sequence.compact(older_than))
+            + self.trade_id.as_mut().map_or(0, |trade_ids| trade_ids.compact(older_than))
+    }
+
+    /// Limpa todo o estado acumulado na sessão (timestamps, sequências e
+    /// lacunas, trade_ids, janelas do rate limit), preservando a configuração
+    /// (bounds, whitelist, tolerâncias, política de tipos). Depois dele todo
+    /// símbolo é tratado como visto pela primeira vez. Feito para o fim de
+    /// sessão: ver `MarketDataIngestor::on_session_end`. Os contadores de
+    /// `type_stats` são métricas cumulativas e não são zerados.
+    pub fn reset_session(&mut self) {
+        self.temporal.reset();
+        if let Some(sequence) = &mut self.sequence {
+            sequence.reset();
+        }
+        if let Some(trade_ids) = &mut self.trade_id {
+            trade_ids.reset();
+        }
+        if let Some(rate_limiter) = &mut self.rate_limiter {
+            rate_limiter.reset();
+        }
+    }
+
+    /// Contadores por `msg_type` (recebidas, rejeitadas e motivo) dos tipos já vistos.
+    pub fn type_stats(&self) -> Vec<MessageTypeCounts> { self.type_stats.snapshot() }
+
+    /// Lacunas de sequência detectadas; `None` sem `with_gap_recovery`.
+    pub fn gap_events(&self) -> Option<Receiver<GapEvent>> { self.sequence.as_ref()?.gap_events() }
+
+    /// Fecha `range` nas lacunas abertas de (símbolo, fonte); ver
+    /// `SequenceValidator::mark_filled`.
+    pub fn mark_filled(&mut self, symbol: &[u8; 8], source: u8, range: RangeInclusive<u64>) {
+        let symbol = self.symbol.normalize(symbol);
+        if let Some(sequence) = &mut self.sequence {
+            sequence.mark_filled(&symbol, source, range);
+        }
+    }
+
+    pub fn bounds(&self) -> DataBounds { **self.bounds.load() }
+
+    /// Troca os limites atomicamente (ex.: circuit breaker alargando as
+    /// faixas), sem reiniciar o validador. Validações em andamento terminam a
+    /// etapa corrente com os limites antigos; nenhuma vê uma mistura dos dois.
+    pub fn update_bounds(&self, bounds: DataBounds) { self.bounds.store(Arc::new(bounds)); }
+
+    /// Handle para trocar os limites de outra thread enquanto o validador
+    /// segue em uso exclusivo na thread de validação.
+    pub fn bounds_handle(&self) -> BoundsHandle { BoundsHandle(Arc::clone(&self.bounds)) }
+    pub fn symbol_validator(&self) -> &SymbolValidator { &self.symbol }
+    pub fn registry(&self) -> &MessageTypeRegistry { &self.registry }
+
+    /// Forma canônica de `symbol` usada como chave do estado por símbolo.
+    pub fn normalize_symbol(&self, symbol: &[u8; 8]) -> [u8; 8] { self.symbol.normalize(symbol) }
+
+    /// Tempo acumulado em cada estágio desde a construção do validador.
+    #[cfg(feature = "stage-timing")]
+    pub fn stage_timings(&self) -> StageTimings { self.timings.snapshot() }
+
+    pub fn validate_message(
+        &mut self,
+        header: &crate::ingestion::zero_copy::MessageHeader,
+        payload: &[u8],
+    ) -> Result<(), ValidationError> {
+        self.validate_message_from(0, header, payload)
+    }
+
+    /// Valida um frame completo. Em headers v2 confere antes o checksum do
+    /// próprio header (`HeaderChecksumMismatch`), independente do checksum do
+    /// payload (`ChecksumMismatch`), para distinguir qual parte se corrompeu.
+    pub fn validate_frame(&mut self, frame: &[u8]) -> Result<(), ValidationError> {
+        use crate::ingestion::zero_copy::MessageHeader;
+
+        let checked = MessageHeader::from_bytes(frame).ok_or(ValidationError::CorruptedFormat).and_then(|header| {
+            header.check_integrity()?;
+            let payload = header.payload(frame).ok_or(ValidationError::CorruptedFormat)?;
+            self.validate_header_checksum(&header, frame)?;
+            Ok((header, payload))
+        });
+        match checked {
+            Ok((header, payload)) => self.validate_message(&header, payload),
+            Err(e) => {
+                self.audit_rejection(0, &[frame], &e);
+                Err(e)
+            }
+        }
+    }
+
+    #[cold]
+    fn audit_rejection(&self, source: u8, frame: &[&[u8]], error: &ValidationError) {
+        let Some(audit) = &self.audit else { return };
+        if let Err(e) = audit.record(self.clock.now_nanos(), source, frame, error) {
+            warn!("Falha ao gravar a auditoria em {}: {}", audit.path().display(), e);
+        }
+    }
+
+    /// Confere o checksum do header gravado em `frame` (só v2; sem efeito em
+    /// versões anteriores ou com `verify_checksums(false)`).
+    pub fn validate_header_checksum(
+        &self,
+        header: &crate::ingestion::zero_copy::MessageHeader,
+        frame: &[u8],
+    ) -> Result<(), ValidationError> {
+        use crate::ingestion::zero_copy::MessageHeader;
+
+        if !self.verify_checksums || !header.has_header_checksum() {
+            return Ok(());
+        }
+        let expected = header.stored_header_checksum(frame).ok_or(ValidationError::CorruptedFormat)?;
+        let calculated = self.checksum.calculate(&frame[..MessageHeader::SIZE]);
+        if calculated != expected {
+            return Err(ValidationError::HeaderChecksumMismatch { expected, calculated });
+        }
+        Ok(())
+    }
+
+    /// Valida uma sequência de frames completos (header + payload), em ordem.
+    /// Frames com header inválido ou payload truncado contam como `malformed`.
+    pub fn validate_batch<F: AsRef<[u8]>>(&mut self, frames: &[F]) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        for (position, frame) in frames.iter().enumerate() {
+            self.validate_frame_into(position as u64, frame.as_ref(), &mut report);
+        }
+        report
+    }
+
+    pub(crate) fn validate_frame_into(&mut self, position: u64, frame: &[u8], report: &mut ValidationReport) {
+        use crate::ingestion::zero_copy::MessageHeader;
+
+        let Some(header) = MessageHeader::from_bytes(frame).filter(MessageHeader::is_valid) else {
+            return report.record_malformed_at(position);
+        };
+        let Some(payload) = header.payload(frame) else {
+            return report.record_malformed_at(position);
+        };
+        let result = self.validate_header_checksum(&header, frame).and_then(|()| self.validate_message(&header, payload));
+        report.record_at(position, &result);
+    }
+
+    /// Como `validate_message`, para uma mensagem vinda de `source` (feed A/B,
+    /// venue). O estado por símbolo e a tolerância de timestamp futuro são
+    /// mantidos por fonte.
+    pub fn validate_message_from(
+        &mut self,
+        source: u8,
+        header: &crate::ingestion::zero_copy::MessageHeader,
+        payload: &[u8],
+    ) -> Result<(), ValidationError> {
+        self.validate_with(source, header, payload, None)
+    }
+
+    /// Como `validate_message`, reaproveitando o CRC do payload já calculado
+    /// na ingestão (`ZeroCopyBuffer::cached_checksum`) em vez de recalculá-lo.
+    pub fn validate_message_cached(
+        &mut self,
+        header: &crate::ingestion::zero_copy::MessageHeader,
+        payload: &[u8],
+        cached: Option<CachedChecksum>,
+    ) -> Result<(), ValidationError> {
+        self.validate_with(0, header, payload, cached)
+    }
+
+    fn validate_with(
+        &mut self,
+        source: u8,
+        header: &crate::ingestion::zero_copy::MessageHeader,
+        payload: &[u8],
+        cached: Option<CachedChecksum>,
+    ) -> Result<(), ValidationError> {
+        let result = self.run_stages(source, header, payload, cached);
+        self.type_stats.record(header.msg_type, result.as_ref().err().map(ValidationError::kind));
+
+        match (result, self.failure_action) {
+            (Err(e), FailureAction::LogAndPass) => {
+                warn!("Validação falhou (aceita): msg_type={} fonte={}: {}", { header.msg_type }, source, e);
+                Ok(())
+            }
+            (Err(e), _) if self.audit.is_some() => {
+                let extension = header.header_extension(&self.checksum);
+                let frame = [&header.to_bytes()[..], extension.as_ref().map_or(&[][..], |ext| &ext[..]), payload];
+                self.audit_rejection(source, &frame, &e);
+                Err(e)
+            }
+            (result, _) => result,
+        }
+    }
+
+    /// Roda todos os estágios como `validate_message_from` sem alterar estado:
+    /// as checagens com estado (ordem temporal, sequência, `trade_id`, limite
+    /// de taxa) comparam com o que já foi visto mas não registram a mensagem,
+    /// e as estatísticas por tipo não mudam. Retorna o veredito bruto, sem
+    /// aplicar `FailureAction::LogAndPass`. Útil para análises "e se" e fuzzing.
+    pub fn validate_dry_run(
+        &self,
+        source: u8,
+        header: &crate::ingestion::zero_copy::MessageHeader,
+        payload: &[u8],
+    ) -> Result<(), ValidationError> {
+        let Some((symbol, fields)) = self.run_stateless_stages(source, header, payload, None)? else { return Ok(()) };
+
+        if let Some(trade_id) = fields.trade_id.filter(|_| self.temporal_checks) {
+            self.check_trade_ordering(source, &symbol, fields.timestamp, trade_id)?;
+        }
+
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.peek(&symbol, fields.timestamp)?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn validate_payload_checksum(&self, payload: &[u8], expected: u32, cached: Option<CachedChecksum>) -> Result<(), ValidationError> {
+        let Some(custom) = &self.custom_checksum else {
+            return self.checksum.validate_cached(payload, expected, cached);
+        };
+        let calculated = custom.value(payload);
+        if calculated != expected {
+            return Err(ValidationError::ChecksumMismatch { expected, calculated });
+        }
+        Ok(())
+    }
+
+    fn run_stages(
+        &mut self,
+        source: u8,
+        header: &crate::ingestion::zero_copy::MessageHeader,
+        payload: &[u8],
+        cached: Option<CachedChecksum>,
+    ) -> Result<(), ValidationError> {
+        let fields = self.run_stateless_stages(source, header, payload, cached)?;
+
+        if header.msg_type == TradeBatch::MSG_TYPE && self.registry.get(TradeBatch::MSG_TYPE).is_some() {
+            self.run_batch_stages(source, payload)?;
+        }
+
+        let Some((symbol, fields)) = fields else { return Ok(()) };
+        let ordering = fields.trade_id.filter(|_| self.temporal_checks);
+        if let Some(trade_id) = ordering {
+            timed!(self.timings.temporal, self.check_trade_ordering(source, &symbol, fields.timestamp, trade_id))?;
+        }
+
+        if let Some(limiter) = &mut self.rate_limiter {
+            limiter.check(&symbol, fields.timestamp)?;
+        }
+
+        // Só registra a ordem depois de todos os estágios: uma mensagem
+        // rejeitada não avança sequência nem trade_id, e a retransmissão
+        // válida dela continua aceita
+        if let Some(trade_id) = ordering {
+            self.commit_trade_ordering(source, &symbol, fields.timestamp, trade_id);
+        }
+        Ok(())
+    }
+
+    /// Checksum, tipo, timestamp e payload. Retorna o símbolo normalizado e os
+    /// campos para os estágios com estado, se o tipo os informa.
+    fn run_stateless_stages(
+        &self,
+        source: u8,
+        header: &crate::ingestion::zero_copy::MessageHeader,
+        payload: &[u8],
+        cached: Option<CachedChecksum>,
+    ) -> Result<Option<([u8; 8], KeyedFields)>, ValidationError> {
+        let verify_checksum = self.verify_checksums && !self.checksum_exempt[header.msg_type as usize];
+
+        if verify_checksum && self.order == ValidationOrder::ChecksumFirst {
+            timed!(self.timings.checksum, self.validate_payload_checksum(payload, header.checksum, cached))?;
+        }
+
+        if self.source_types.get(&source).is_some_and(|allowed| !allowed[header.msg_type as usize]) {
+            return Err(ValidationError::TypeNotAllowedForSource { msg_type: header.msg_type, source_id: source });
+        }
+
+        let descriptor = self.registry.get(header.msg_type).copied();
+        if descriptor.is_none() && self.unknown_type_policy == UnknownTypePolicy::Reject {
+            return Err(ValidationError::UnknownMessageType(header.msg_type));
+        }
+
+        timed!(self.timings.bounds, self.validate_header_timestamp(source, header))?;
+
+        let fields = match descriptor {
+            Some(descriptor) if payload.len() < descriptor.min_size => return Err(ValidationError::CorruptedFormat),
+            Some(descriptor) => (descriptor.validate)(self, payload)?,
+            None => None,
+        };
+
+        if verify_checksum && self.order == ValidationOrder::FastReject {
+            timed!(self.timings.checksum, self.validate_payload_checksum(payload, header.checksum, cached))?;
+        }
+
+        Ok(fields.map(|fields| (self.symbol.normalize(&fields.symbol), fields)))
+    }
+
+    fn validate_header_timestamp(
+        &self,
+        source: u8,
+        header: &crate::ingestion::zero_copy::MessageHeader,
+    ) -> Result<(), ValidationError> {
+        self.bounds.load().validate_timestamp(header.timestamp)?;
+        if let Some(tolerance) = self.future_tolerance.for_source(source) {
+            let limit = self.clock.now_nanos().saturating_add(tolerance);
+            if header.timestamp > limit {
+                return Err(ValidationError::InvalidTimestamp(TimestampIssue::Future { timestamp: header.timestamp, limit }));
+            }
+        }
+        Ok(())
+    }
+
+    /// Ordem temporal, sequência e `trade_id`, sem registrar nada.
+    fn check_trade_ordering(&self, source: u8, symbol: &[u8; 8], timestamp: u64, trade_id: u64) -> Result<(), ValidationError> {
+        // Retransmissão de uma lacuna: fora de ordem por definição
+        if self.is_gap_fill(source, symbol, trade_id) {
+            return Ok(());
+        }
+        self.temporal.check_monotonic(symbol, source, timestamp)?;
+        if let Some(sequence) = &self.sequence {
+            sequence.check_sequence(symbol, source, trade_id)?;
+        }
+        if let Some(trade_ids) = &self.trade_id {
+            trade_ids.check_trade_id(symbol, source, trade_id)?;
+        }
+        Ok(())
+    }
+
+    /// Registra uma mensagem já aceita por `check_trade_ordering`.
+    fn commit_trade_ordering(&mut self, source: u8, symbol: &[u8; 8], timestamp: u64, trade_id: u64) {
+        let gap_fill = self.is_gap_fill(source, symbol, trade_id);
+        if let Some(sequence) = &mut self.sequence {
+            sequence.record_sequence(symbol, source, trade_id);
+        }
+        if gap_fill {
+            return;
+        }
+        self.temporal.record_monotonic(symbol, source, timestamp);
+        if let Some(trade_ids) = &mut self.trade_id {
+            trade_ids.record_trade_id(symbol, source, trade_id);
+        }
+    }
+
+    fn is_gap_fill(&self, source: u8, symbol: &[u8; 8], trade_id: u64) -> bool {
+        self.sequence.as_ref().is_some_and(|sequence| sequence.is_gap_fill(symbol, source, trade_id))
+    }
+
+    /// Estágios com estado de cada trade do lote, em ordem, como se tivessem
+    /// chegado em frames separados: ordem temporal, sequência e `trade_id`
+    /// (com `temporal_checks`) e o `RateLimiter`. Tudo ou nada: se algum trade
+    /// falhar, nenhum do lote fica registrado nem conta na janela de taxa.
+    fn run_batch_stages(&mut self, source: u8, payload: &[u8]) -> Result<(), ValidationError> {
+        let batch = TradeBatch::parse(payload).ok_or(ValidationError::CorruptedFormat)?;
+        if self.temporal_checks {
+            timed!(self.timings.temporal, self.check_batch_ordering(source, &batch))?;
+        }
+        self.check_batch_rate(&batch)?;
+        if self.temporal_checks {
+            for trade in batch.trades() {
+                let symbol = self.symbol.normalize(&trade.symbol());
+                self.commit_trade_ordering(source, &symbol, trade.timestamp(), trade.trade_id());
+            }
+        }
+        Ok(())
+    }
+
+    fn check_batch_ordering(&mut self, source: u8, batch: &TradeBatch<'_>) -> Result<(), ValidationError> {
+        let mut last = std::mem::take(&mut self.batch_scratch.last);
+        last.clear();
+        let result = batch.trades().enumerate().try_for_each(|(index, trade)| {
+            let symbol = self.symbol.normalize(&trade.symbol());
+            let (timestamp, trade_id) = (trade.timestamp(), trade.trade_id());
+            if self.is_gap_fill(source, &symbol, trade_id) {
+                return Ok(());
+            }
+            // O antecessor no próprio lote ainda não foi registrado: compara com ele
+            match last.insert(symbol, (timestamp, trade_id)) {
+                Some(prev) => self.check_batch_successor(prev, timestamp, trade_id),
+                None => self.check_trade_ordering(source, &symbol, timestamp, trade_id),
+            }
+            .map_err(|e| e.in_batch(index))
+        });
+        self.batch_scratch.last = last;
+        result
+    }
+
+    /// Conta cada trade do lote no `RateLimiter`; se um estourar, devolve as
+    /// janelas dos símbolos do lote ao estado anterior a ele.
+    fn check_batch_rate(&mut self, batch: &TradeBatch<'_>) -> Result<(), ValidationError> {
+        let Some(limiter) = &mut self.rate_limiter else { return Ok(()) };
+        let saved = &mut self.batch_scratch.windows;
+        saved.clear();
+        for (index, trade) in batch.trades().enumerate() {
+            let symbol = self.symbol.normalize(&trade.symbol());
+            saved.entry(symbol).or_insert_with(|| limiter.windows.get(&symbol).copied());
+            if let Err(e) = limiter.check(&symbol, trade.timestamp()) {
+                for (symbol, window) in saved.drain() {
+                    match window {
+                        Some(window) => limiter.windows.insert(symbol, window),
+                        None => limiter.windows.remove(&symbol),
+                    };
+                }
+                return Err(e.in_batch(index));
+            }
+        }
+        Ok(())
+    }
+
+    /// `check_trade_ordering` contra um trade anterior do mesmo lote, como se
+    /// ele já tivesse sido registrado.
+    fn check_batch_successor(&self, (prev_ts, prev_id): (u64, u64), timestamp: u64, trade_id: u64) -> Result<(), ValidationError> {
+        if timestamp < prev_ts.saturating_sub(self.temporal.clock_skew_tolerance) {
+            return Err(ValidationError::TemporalOrderViolation { prev: prev_ts, current: timestamp });
+        }
+        if let Some(sequence) = &self.sequence {
+            let expected = prev_id.wrapping_add(1);
+            if trade_id != expected && !(sequence.gaps.is_some() && trade_id > expected) {
+                return Err(ValidationError::SequenceViolation { expected, received: trade_id });
+            }
+        }
+        if let Some(trade_ids) = &self.trade_id {
+            let regressed = match trade_ids.strictness {
+                TradeIdStrictness::Strict => trade_id <= prev_id,
+                TradeIdStrictness::AllowDuplicates => trade_id < prev_id,
+            };
+            if regressed {
+                return Err(ValidationError::TradeIdRegression { prev: prev_id, current: trade_id });
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn validate_trade(&self, payload: &[u8]) -> Result<crate::ingestion::zero_copy::Trade, ValidationError> {
+        let trade: crate::ingestion::zero_copy::Trade = read_struct(payload)?;
+        self.validate_trade_fields(&self.bounds.load(), &trade)?;
+        Ok(trade)
+    }
+
+    /// Valida cada trade de um lote como `validate_trade`, mais os limites do
+    /// timestamp de cada um (o header só traz um). Exige que a contagem
+    /// declarada bata com o tamanho do payload (`CorruptedFormat`). Rejeita no
+    /// primeiro trade inválido com `BatchTrade`, informando seu índice.
+    pub fn validate_trade_batch(&self, payload: &[u8]) -> Result<(), ValidationError> {
+        let batch = TradeBatch::parse(payload).ok_or(ValidationError::CorruptedFormat)?;
+        let bounds = self.bounds.load();
+        for (index, trade) in batch.trades().enumerate() {
+            self.validate_trade_fields(&bounds, &trade)
+                .and_then(|()| bounds.validate_timestamp(trade.timestamp()))
+                .map_err(|e| e.in_batch(index))?;
+        }
+        Ok(())
+    }
+
+    fn validate_trade_fields(
+        &self,
+        bounds: &DataBounds,
+        trade: &crate::ingestion::zero_copy::Trade,
+    ) -> Result<(), ValidationError> {
+        if self.strict_padding && !trade.has_zero_padding() {
+            return Err(ValidationError::NonZeroPadding { msg_type: crate::ingestion::zero_copy::Trade::MSG_TYPE });
+        }
+        timed!(self.timings.symbol, self.symbol.validate(&trade.symbol()))?;
+
+        let price = trade.price() as f64 / 1e8;
+        let qty = trade.quantity() as f64 / 1e8;
+
+        timed!(self.timings.bounds, {
+            bounds.validate_price(price, "price")?;
+            bounds.validate_quantity(qty, "quantity")
+        })
+    }
+
+    pub(crate) fn validate_quote(&self, payload: &[u8]) -> Result<crate::ingestion::zero_copy::Quote, ValidationError> {
+        let quote: crate::ingestion::zero_copy::Quote = read_struct(payload)?;
+        if self.strict_padding && !quote.has_zero_padding() {
+            return Err(ValidationError::NonZeroPadding { msg_type: crate::ingestion::zero_copy::Quote::MSG_TYPE });
+        }
+
+        timed!(self.timings.symbol, self.symbol.validate(&quote.symbol()))?;
+
+        let bid_price = quote.bid_price() as f64 / 1e8;
+        let ask_price = quote.ask_price() as f64 / 1e8;
+        let bounds = self.bounds.load();
+
+        if bid_price >= ask_price {
+            return Err(ValidationError::InvalidSymbol(SymbolIssue::CrossedQuote));
+        }
+
+        timed!(self.timings.bounds, {
+            bounds.validate_price(bid_price, "bid_price")?;
+            bounds.validate_price(ask_price, "ask_price")
+        })?;
+
+        if let Some(max_relative) = self.spread_limits.for_symbol(&self.symbol, &quote.symbol()) {
+            // Bid nulo ou quase: o divisor tem piso de `min_price` (ou de 1e-8,
+            // com preços negativos) e o spread sai enorme em vez de infinito/NaN
+            let floor = if bounds.allow_negative_price { 1e-8 } else { bounds.min_price.max(1e-8) };
+            let relative = (ask_price - bid_price) / bid_price.abs().max(floor);
+            if relative > max_relative {
+                return Err(ValidationError::OutOfBounds { field: BoundsField::Named("relative_spread"), value: relative });
+            }
+        }
+
+        Ok(quote)
+    }
+
+    /// Valida um snapshot de book nível a nível, direto no payload: limites de
+    /// preço/quantidade, bids estritamente decrescentes, asks estritamente
+    /// crescentes e book não cruzado. Rejeita no primeiro nível inválido,
+    /// informando seu índice no array completo (bids seguidos de asks).
+    pub fn validate_book_snapshot(&self, payload: &[u8]) -> Result<(), ValidationError> {
+        use crate::ingestion::zero_copy::BookSnapshot;
+
+        let snapshot = BookSnapshot::parse(payload).ok_or(ValidationError::CorruptedFormat)?;
+        let symbol = snapshot.header.symbol;
+        timed!(self.timings.symbol, self.symbol.validate(&symbol))?;
+
+        let bounds = self.bounds.load();
+        if snapshot.level_count() > bounds.max_book_levels {
+            return Err(ValidationError::OutOfBounds {
+                field: BoundsField::BookDepth(bounds.max_book_levels),
+                value: snapshot.level_count() as f64,
+            });
+        }
+
+        let mut prev_bid: Option<i64> = None;
+        for (index, level) in snapshot.bids().enumerate() {
+            let price = level.price;
+            Self::validate_level(&bounds, index, &level)?;
+            if prev_bid.is_some_and(|prev| price >= prev) {
+                return Err(ValidationError::InvalidBookLevel { index, reason: "bids fora de ordem decrescente" });
+            }
+            prev_bid = Some(price);
+        }
+
+        let offset = snapshot.header.bid_count as usize;
+        let mut prev_ask: Option<i64> = None;
+        for (i, level) in snapshot.asks().enumerate() {
+            let index = offset + i;
+            let price = level.price;
+            Self::validate_level(&bounds, index, &level)?;
+            if prev_ask.is_some_and(|prev| price <= prev) {
+                return Err(ValidationError::InvalidBookLevel { index, reason: "asks fora de ordem crescente" });
+            }
+            if i == 0 && prev_bid.is_some_and(|best_bid| price <= best_bid) {
+                return Err(ValidationError::InvalidBookLevel { index, reason: "book cruzado" });
+            }
+            prev_ask = Some(price);
+        }
+
+        Ok(())
+    }
+
+    fn validate_level(
+        bounds: &DataBounds,
+        index: usize,
+        level: &crate::ingestion::zero_copy::BookLevel,
+    ) -> Result<(), ValidationError> {
+        let price = level.price as f64 / 1e8;
+        let qty = level.quantity as f64 / 1e8;
+
+        if bounds.validate_price(price, "level_price").is_err() {
+            return Err(ValidationError::InvalidBookLevel { index, reason: "preço fora dos limites" });
+        }
+        if bounds.validate_quantity(qty, "level_quantity").is_err() {
+            return Err(ValidationError::InvalidBookLevel { index, reason: "quantidade fora dos limites" });
+        }
+        if qty > bounds.max_level_quantity {
+            return Err(ValidationError::OutOfBounds { field: BoundsField::LevelQuantity(index), value: qty });
+        }
+        Ok(())
+    }
+}
+
+/// Kernels de `DataBounds::validate_prices_simd`. Comparações ordenadas
+/// (`_OQ`) dão falso para NaN, que então cai fora dos limites.
+mod simd_bounds {
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::{
+        _mm256_and_pd, _mm256_cmp_pd, _mm256_loadu_pd, _mm256_movemask_pd, _mm256_set1_pd, _mm512_cmp_pd_mask,
+        _mm512_loadu_pd, _mm512_set1_pd, _CMP_GE_OQ, _CMP_LE_OQ,
+    };
+
+    pub(super) fn first_outside_scalar(prices: &[f64], min: f64, max: f64) -> Option<usize> {
+        prices.iter().position(|price| !(min..=max).contains(price))
+    }
+
+    /// Completa um kernel vetorial com o resto escalar que não encheu um registrador.
+    fn remainder(prices: &[f64], rest: &[f64], min: f64, max: f64) -> Option<usize> {
+        first_outside_scalar(rest, min, max).map(|index| prices.len() - rest.len() + index)
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx512f")]
+    pub(super) unsafe fn first_outside_avx512(prices: &[f64], min: f64, max: f64) -> Option<usize> {
+        const LANES: usize = 8;
+        let (lo, hi) = (_mm512_set1_pd(min), _mm512_set1_pd(max));
+        let mut chunks = prices.chunks_exact(LANES);
+        for (i, chunk) in chunks.by_ref().enumerate() {
+            // SAFETY: `chunk` tem exatamente `LANES` f64
+            let value = unsafe { _mm512_loadu_pd(chunk.as_ptr()) };
+            let inside = _mm512_cmp_pd_mask::<_CMP_GE_OQ>(value, lo) & _mm512_cmp_pd_mask::<_CMP_LE_OQ>(value, hi);
+            if inside != u8::MAX {
+                return Some(i * LANES + (!inside).trailing_zeros() as usize);
+            }
+        }
+        remainder(prices, chunks.remainder(), min, max)
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx")]
+    pub(super) unsafe fn first_outside_avx(prices: &[f64], min: f64, max: f64) -> Option<usize> {
+        const LANES: usize = 4;
+        let (lo, hi) = (_mm256_set1_pd(min), _mm256_set1_pd(max));
+        let mut chunks = prices.chunks_exact(LANES);
+        for (i, chunk) in chunks.by_ref().enumerate() {
+            // SAFETY: `chunk` tem exatamente `LANES` f64
+            let value = unsafe { _mm256_loadu_pd(chunk.as_ptr()) };
+            let inside = _mm256_and_pd(_mm256_cmp_pd::<_CMP_GE_OQ>(value, lo), _mm256_cmp_pd::<_CMP_LE_OQ>(value, hi));
+            let inside = _mm256_movemask_pd(inside) as u32;
+            if inside != 0b1111 {
+                return Some(i * LANES + (!inside).trailing_zeros() as usize);
+            }
+        }
+        remainder(prices, chunks.remainder(), min, max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simd_price_bounds_match_scalar() {
+        let scalar = |bounds: &DataBounds, prices: &[f64]| {
+            prices
+                .iter()
+                .position(|&price| bounds.validate_price(price, "price").is_err())
+                .map_or(Ok(()), |index| Err((index, prices[index])))
+        };
+        let same = |a: Result<(), (usize, f64)>, b: Result<(), (usize, f64)>| match (a, b) {
+            (Ok(()), Ok(())) => true,
+            (Err((i, x)), Err((j, y))) => i == j && x.to_bits() == y.to_bits(),
+            _ => false,
+        };
+
+        let crypto = DataBounds::crypto();
+        let unbounded = DataBounds::crypto().with_min_price(f64::NEG_INFINITY).with_max_price(f64::INFINITY);
+        let negative = DataBounds::crypto().with_negative_prices(true);
+        let poison = [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, -1.0, 0.0, 1e300, crypto.min_price, crypto.max_price];
+        for bounds in [crypto, unbounded, negative] {
+            // Tamanhos que deixam resto escalar e valores ruins em cada faixa
+            for len in [0, 1, 3, 4, 7, 8, 9, 17, 100] {
+                let valid: Vec<f64> = (0..len).map(|i| 1.0 + i as f64).collect();
+                assert!(same(bounds.validate_prices_simd(&valid), scalar(&bounds, &valid)));
+                for position in 0..len {
+                    for bad in poison {
+                        let mut prices = valid.clone();
+                        prices[position] = bad;
+                        prices.push(f64::NAN);
+                        let simd = bounds.validate_prices_simd(&prices);
+                        assert!(same(simd, scalar(&bounds, &prices)), "len={} pos={} bad={}", len, position, bad);
+                    }
+                }
+            }
+        }
+        assert_eq!(crypto.validate_prices_simd(&[1.0, 2.0, -3.0, 4.0]), Err((2, -3.0)));
+
+        // Kernel AVX direto (a CPU pode ter AVX-512 e nunca passar por ele)
+        #[cfg(target_arch = "x86_64")]
+        if std::arch::is_x86_feature_detected!("avx") {
+            let prices: Vec<f64> = (0..37).map(|i| if i % 11 == 10 { f64::NAN } else { i as f64 }).collect();
+            for start in 0..prices.len() {
+                let expected = simd_bounds::first_outside_scalar(&prices[start..], 0.5, 30.0);
+                assert_eq!(unsafe { simd_bounds::first_outside_avx(&prices[start..], 0.5, 30.0) }, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_checksum_plugin() {
+        use crate::ingestion::zero_copy::{MessageHeader, Trade};
+
+        struct ByteSum;
+        impl Checksum for ByteSum {
+            fn compute(&self, data: &[u8]) -> u64 { data.iter().map(|&b| u64::from(b)).sum() }
+            fn width(&self) -> ChecksumWidth { ChecksumWidth::Bits16 }
+        }
+
+        let ts = 1_700_000_000_000_000_000;
+        let payload = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts, 1, 1).to_bytes();
+        let sum = (ByteSum.compute(&payload) & 0xFFFF) as u32;
+        let header = MessageHeader::builder().msg_type(Trade::MSG_TYPE).timestamp(ts).payload(&payload).checksum(sum).build();
+
+        let mut validator = CompositeValidator::builder().custom_checksum(ByteSum).build();
+        assert!(validator.validate_message(&header, &payload).is_ok());
+        let crc = MessageHeader { checksum: ChecksumValidator::new().calculate(&payload), ..header };
+        assert!(matches!(validator.validate_message(&crc, &payload), Err(ValidationError::ChecksumMismatch { .. })));
+
+        // Os embutidos pelo mesmo trait
+        let crc32c = ChecksumValidator::with_algorithm(ChecksumAlgorithm::Crc32c);
+        let mut plugged = CompositeValidator::builder().custom_checksum(crc32c).build();
+        let header = MessageHeader { checksum: ChecksumValidator::with_algorithm(ChecksumAlgorithm::Crc32c).calculate(&payload), ..header };
+        assert!(plugged.validate_message(&header, &payload).is_ok());
+    }
+
+    #[test]
+    fn test_relative_spread_limit() {
+        use crate::ingestion::zero_copy::{MessageHeader, Quote};
+
+        let mut validator = CompositeValidator::builder()
+            .max_relative_spread(0.05)
+            .max_relative_spread_for_symbol("ILLIQ", 0.5)
+            .build();
+        let ts = 1_700_000_000_000_000_000;
+        let mut check = |symbol: &[u8; 8], bid: i64, ask: i64| {
+            let frame = Quote::new(*symbol, bid * 100_000_000, 1, ask * 100_000_000, 1, ts).to_frame();
+            let header = MessageHeader::from_bytes(&frame).unwrap();
+            validator.validate_message(&header, &frame[MessageHeader::SIZE..])
+        };
+
+        assert!(check(b"BTCUSD\0\0", 50_000, 50_010).is_ok());
+        match check(b"BTCUSD\0\0", 1, 1_000_000) {
+            Err(ValidationError::OutOfBounds { field: BoundsField::Named("relative_spread"), value }) => assert_eq!(value, 999_999.0),
+            other => panic!("esperado spread fora dos limites, obtido {:?}", other),
+        }
+        // Limite próprio do símbolo
+        assert!(check(b"ILLIQ\0\0\0", 100, 140).is_ok());
+        assert!(check(b"ILLIQ\0\0\0", 100, 160).is_err());
+
+        // O override vale para qualquer grafia que normalize para o mesmo símbolo
+        let mut normalized = CompositeValidator::builder()
+            .max_relative_spread(0.05)
+            .max_relative_spread_for_symbol("BTC-USD", 0.5)
+            .symbol_validator(SymbolValidator::permissive().with_normalization(SymbolNormalization::new().uppercase().strip_separators(b"-")))
+            .build();
+        for symbol in [b"btcusd\0\0", b"BTC-USD\0", b"BTCUSD\0\0"] {
+            let frame = Quote::new(*symbol, 100 * 100_000_000, 1, 140 * 100_000_000, 1, ts).to_frame();
+            let header = MessageHeader::from_bytes(&frame).unwrap();
+            assert!(normalized.validate_message(&header, &frame[MessageHeader::SIZE..]).is_ok());
+        }
+
+        // Bid zero (com preços negativos liberados): spread enorme, não NaN/inf
+        let mut negative = CompositeValidator::builder()
+            .bounds(DataBounds::crypto().with_negative_prices(true))
+            .max_relative_spread(0.05)
+            .build();
+        let frame = Quote::new(*b"CALSPRD\0", 0, 1, 100_000_000, 1, ts).to_frame();
+        let header = MessageHeader::from_bytes(&frame).unwrap();
+        assert!(matches!(
+            negative.validate_message(&header, &frame[MessageHeader::SIZE..]),
+            Err(ValidationError::OutOfBounds { value, .. }) if value.is_finite()
+        ));
+    }
+
+    #[test]
+    fn test_update_bounds_at_runtime() {
+        use crate::ingestion::zero_copy::{MessageHeader, Trade};
+
+        let mut validator = CompositeValidator::builder().build();
+        let ts = 1_700_000_000_000_000_000;
+        let frame = Trade::new(*b"BTCUSD\0\0", 20_000_000 * 100_000_000, 100_000_000, ts, 1, 1).to_frame();
+        let header = MessageHeader::from_bytes(&frame).unwrap();
+        let payload = &frame[MessageHeader::SIZE..];
+        assert!(matches!(validator.validate_message(&header, payload), Err(ValidationError::OutOfBounds { .. })));
+
+        validator.update_bounds(DataBounds::crypto().with_max_price(50_000_000.0));
+        assert!(validator.validate_message(&header, payload).is_ok());
+        assert_eq!(validator.bounds().max_price, 50_000_000.0);
+
+        // De outra thread, pelo handle
+        let handle = validator.bounds_handle();
+        std::thread::spawn(move || handle.update(DataBounds::crypto())).join().unwrap();
+        assert!(validator.validate_message(&header, payload).is_err());
+    }
+
+    #[test]
+    fn test_checksum_validation() {
+        let validator = ChecksumValidator::new();
+        let data = b"Hello, world!";
+        let checksum = validator.calculate(data);
+        assert!(validator.validate(data, checksum).is_ok());
+    }
+
+    #[test]
+    fn test_incremental_checksum_matches_one_shot() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8).collect();
+
+        for algorithm in [ChecksumAlgorithm::Crc32, ChecksumAlgorithm::Crc32c] {
+            let validator = ChecksumValidator::with_algorithm(algorithm);
+            let expected = validator.calculate(&data);
+
+            for chunk_size in [1, 3, 7, 64, 1000, 4096] {
+                let mut state = validator.start();
+                for chunk in data.chunks(chunk_size) {
+                    validator.update(&mut state, chunk);
+                }
+                assert_eq!(validator.finalize(state), expected, "chunk_size={}", chunk_size);
+            }
+
+            // Pedaços irregulares, incluindo vazios
+            let mut state = validator.start();
+            for window in [0, 0, 5, 5, 300, 301, 2048, 4096].windows(2) {
+                validator.update(&mut state, &data[window[0]..window[1]]);
+            }
+            assert_eq!(validator.finalize(state), expected);
+        }
+
+        let validator = ChecksumValidator::new();
+        assert_eq!(validator.finalize(validator.start()), validator.calculate(&[]));
+    }
+
+    #[test]
+    fn test_bounds_validation() {
+        let bounds = DataBounds::crypto();
         assert!(bounds.validate_price(50000.0, "BTC").is_ok());
         assert!(bounds.validate_price(0.000000001, "BTC").is_err());
     }
+
+    #[test]
+    fn test_non_finite_values_rejected() {
+        let unbounded = DataBounds { max_price: f64::INFINITY, max_quantity: f64::INFINITY, ..DataBounds::crypto() };
+
+        for bounds in [DataBounds::crypto(), DataBounds::stocks(), unbounded] {
+            for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+                assert!(matches!(
+                    bounds.validate_price(value, "price"),
+                    Err(ValidationError::OutOfBounds { .. })
+                ));
+                assert!(matches!(
+                    bounds.validate_quantity(value, "quantity"),
+                    Err(ValidationError::OutOfBounds { .. })
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn test_bounds_field_override() {
+        let preset = DataBounds::crypto();
+        let bounds = DataBounds::crypto().with_max_price(50_000_000.0);
+
+        assert_eq!(bounds.max_price, 50_000_000.0);
+        assert_eq!(bounds.min_price, preset.min_price);
+        assert_eq!(bounds.min_quantity, preset.min_quantity);
+        assert_eq!(bounds.max_quantity, preset.max_quantity);
+        assert_eq!((bounds.min_timestamp, bounds.max_timestamp), (preset.min_timestamp, preset.max_timestamp));
+        assert!(!bounds.allow_negative_price);
+
+        assert!(preset.validate_price(20_000_000.0, "price").is_err());
+        assert!(bounds.validate_price(20_000_000.0, "price").is_ok());
+    }
+
+    #[test]
+    fn test_negative_prices() {
+        let bounds = DataBounds::stocks();
+        assert!(bounds.validate_price(-12.5, "spread").is_err());
+
+        let spreads = DataBounds { allow_negative_price: true, ..DataBounds::stocks() };
+        assert!(spreads.validate_price(-12.5, "spread").is_ok());
+        assert!(spreads.validate_price(0.0, "spread").is_ok());
+        assert!(spreads.validate_price(-20_000_000.0, "spread").is_err());
+        assert!(spreads.validate_price(f64::NAN, "spread").is_err());
+        assert!(spreads.validate_price(f64::NEG_INFINITY, "spread").is_err());
+    }
+
+    fn control_header(timestamp: u64, checksum: u32) -> crate::ingestion::zero_copy::MessageHeader {
+        crate::ingestion::zero_copy::MessageHeader {
+            magic: crate::ingestion::zero_copy::MessageHeader::MAGIC,
+            msg_type: 2,
+            version: 1,
+            priority: 0,
+            flags: 0,
+            timestamp,
+            payload_size: 16,
+            checksum,
+        }
+    }
+
+    #[test]
+    fn test_validation_order_fast_reject() {
+        let payload = [0xAAu8; 16];
+        let header = control_header(0, 0xDEADBEEF);
+
+        let mut checksum_first = CompositeValidator::new(DataBounds::crypto(), SymbolValidator::permissive());
+        assert!(matches!(
+            checksum_first.validate_message(&header, &payload),
+            Err(ValidationError::ChecksumMismatch { .. })
+        ));
+
+        let mut fast = CompositeValidator::new(DataBounds::crypto(), SymbolValidator::permissive())
+            .with_order(ValidationOrder::FastReject);
+        assert!(matches!(
+            fast.validate_message(&header, &payload),
+            Err(ValidationError::InvalidTimestamp(_))
+        ));
+    }
+
+    #[test]
+    fn test_fast_reject_still_requires_checksum() {
+        let payload = [0xAAu8; 16];
+        let ts = DataBounds::crypto().min_timestamp + 1;
+        let crc = ChecksumValidator::new().calculate(&payload);
+
+        let mut fast = CompositeValidator::new(DataBounds::crypto(), SymbolValidator::permissive())
+            .with_order(ValidationOrder::FastReject);
+        assert!(fast.validate_message(&control_header(ts, crc), &payload).is_ok());
+        assert!(matches!(
+            fast.validate_message(&control_header(ts, crc ^ 1), &payload),
+            Err(ValidationError::ChecksumMismatch { .. })
+        ));
+    }
+
+    fn snapshot_payload(bids: &[(i64, i64)], asks: &[(i64, i64)]) -> Vec<u8> {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(b"BTCUSD\0\0");
+        payload.extend_from_slice(&0u64.to_le_bytes());
+        payload.extend_from_slice(&(bids.len() as u32).to_le_bytes());
+        payload.extend_from_slice(&(asks.len() as u32).to_le_bytes());
+        for &(price, qty) in bids.iter().chain(asks) {
+            payload.extend_from_slice(&(price * 100_000_000).to_le_bytes());
+            payload.extend_from_slice(&(qty * 100_000_000).to_le_bytes());
+        }
+        payload
+    }
+
+    #[test]
+    fn test_book_snapshot_well_formed() {
+        let validator = CompositeValidator::new(DataBounds::crypto(), SymbolValidator::permissive());
+        let bids: Vec<_> = (0..500).map(|i| (50_000 - i, 1)).collect();
+        let asks: Vec<_> = (0..500).map(|i| (50_001 + i, 2)).collect();
+        let payload = snapshot_payload(&bids, &asks);
+
+        assert!(validator.validate_book_snapshot(&payload).is_ok());
+    }
+
+    #[test]
+    fn test_book_snapshot_mis_sorted() {
+        let validator = CompositeValidator::new(DataBounds::crypto(), SymbolValidator::permissive());
+        let payload = snapshot_payload(&[(100, 1), (99, 1)], &[(101, 1), (103, 1), (102, 1)]);
+
+        match validator.validate_book_snapshot(&payload) {
+            Err(ValidationError::InvalidBookLevel { index, .. }) => assert_eq!(index, 4),
+            other => panic!("esperado InvalidBookLevel, obtido {:?}", other),
+        }
+
+        let truncated = &payload[..payload.len() - 1];
+        assert!(matches!(validator.validate_book_snapshot(truncated), Err(ValidationError::CorruptedFormat)));
+    }
+
+    fn batch_trades(count: u64) -> Vec<crate::ingestion::zero_copy::Trade> {
+        use crate::ingestion::zero_copy::Trade;
+
+        let ts = 1_700_000_000_000_000_000;
+        (0..count)
+            .map(|i| Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts + i, 1, i + 1))
+            .collect()
+    }
+
+    fn validate_batch_frame(validator: &mut CompositeValidator, trades: &[crate::ingestion::zero_copy::Trade]) -> Result<(), ValidationError> {
+        validator.validate_frame(&TradeBatch::frame(trades))
+    }
+
+    #[test]
+    fn test_trade_batch_well_formed() {
+        use crate::ingestion::zero_copy::{MessageHeader, Trade};
+
+        let mut validator = CompositeValidator::builder().with_sequence_validator().build();
+        let trades = batch_trades(16);
+        assert_eq!(TradeBatch::parse(&TradeBatch::frame(&trades)[MessageHeader::SIZE..]).map(|b| b.len()), Some(16));
+
+        assert!(validate_batch_frame(&mut validator, &trades[..10]).is_ok());
+        // Estado segue entre lotes e mensagens avulsas
+        assert!(validate_batch_frame(&mut validator, &trades[10..]).is_ok());
+        assert!(validator.validate_frame(&trades[10].to_frame()).is_err());
+
+        let mut frame = TradeBatch::frame(&trades[..2]);
+        frame.truncate(frame.len() - Trade::SIZE);
+        let header = MessageHeader::builder().msg_type(TradeBatch::MSG_TYPE).timestamp(trades[0].timestamp()).payload(&frame[MessageHeader::SIZE..]).build();
+        assert!(matches!(
+            validator.validate_message(&header, &frame[MessageHeader::SIZE..]),
+            Err(ValidationError::CorruptedFormat)
+        ));
+    }
+
+    #[test]
+    fn test_trade_batch_reports_first_bad_index() {
+        use crate::ingestion::zero_copy::Trade;
+
+        let mut validator = CompositeValidator::builder().with_trade_id_validator(TradeIdStrictness::Strict).build();
+        let mut trades = batch_trades(5);
+        trades[3] = Trade::new(*b"BTCUSD\0\0", -1, 100_000_000, trades[3].timestamp(), 1, 4);
+        trades[4] = Trade::new(*b"BTCUSD\0\0", -1, 100_000_000, trades[4].timestamp(), 1, 5);
+        match validate_batch_frame(&mut validator, &trades) {
+            Err(ValidationError::BatchTrade { index: 3, error }) => assert!(matches!(error, TradeIssue::OutOfBounds { .. })),
+            other => panic!("esperado BatchTrade no índice 3, obtido {:?}", other),
+        }
+
+        let mut trades = batch_trades(4);
+        trades[2] = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, trades[2].timestamp(), 1, 1);
+        let err = validate_batch_frame(&mut validator, &trades).unwrap_err();
+        assert!(matches!(err, ValidationError::BatchTrade { index: 2, .. }));
+        assert_eq!(err.kind(), ValidationErrorKind::TradeIdRegression);
+    }
+
+    #[test]
+    fn test_trade_batch_ordering_is_all_or_nothing() {
+        use crate::ingestion::zero_copy::Trade;
+
+        let mut validator = CompositeValidator::builder().with_sequence_validator().build();
+        let trades = batch_trades(6);
+        assert!(validate_batch_frame(&mut validator, &trades[..2]).is_ok());
+
+        // 3, 4 e depois 4 de novo: o lote inteiro é rejeitado, sem registrar 3 e 4
+        let mut bad = trades[2..5].to_vec();
+        bad[2] = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, trades[4].timestamp(), 1, 4);
+        let err = validate_batch_frame(&mut validator, &bad).unwrap_err();
+        assert!(matches!(err, ValidationError::BatchTrade { index: 2, .. }));
+        assert_eq!(err.kind(), ValidationErrorKind::SequenceViolation);
+
+        // A retransmissão correta segue de onde o estado parou
+        assert!(validate_batch_frame(&mut validator, &trades[2..]).is_ok());
+        assert!(validator.validate_frame(&trades[5].to_frame()).is_err());
+
+        // Símbolos intercalados no mesmo lote seguem cada um a sua sequência
+        let ts = trades[5].timestamp() + 1;
+        let mixed = [
+            Trade::new(*b"ETHUSD\0\0", 1, 1, ts, 1, 100),
+            Trade::new(*b"BTCUSD\0\0", 1, 1, ts, 1, 7),
+            Trade::new(*b"ETHUSD\0\0", 1, 1, ts, 1, 101),
+        ];
+        assert!(validate_batch_frame(&mut validator, &mixed).is_ok());
+    }
+
+    #[test]
+    fn test_trade_batch_counts_against_rate_limit() {
+        let mut validator = CompositeValidator::builder().with_rate_limiter(3, Duration::from_secs(1)).build();
+        let trades = batch_trades(4);
+        assert!(validate_batch_frame(&mut validator, &trades[..2]).is_ok());
+
+        // O quarto trade da janela estoura o limite e o lote não conta nada
+        let err = validate_batch_frame(&mut validator, &trades[2..]).unwrap_err();
+        assert!(matches!(err, ValidationError::BatchTrade { index: 1, .. }));
+        assert_eq!(err.kind(), ValidationErrorKind::RateLimitExceeded);
+
+        assert!(validator.validate_frame(&trades[2].to_frame()).is_ok());
+        assert_eq!(validator.validate_frame(&trades[3].to_frame()).unwrap_err().kind(), ValidationErrorKind::RateLimitExceeded);
+    }
+
+    #[test]
+    fn test_book_depth_bounds() {
+        let bounds = DataBounds::crypto().with_max_book_levels(4).with_max_level_quantity(1_000.0);
+        let validator = CompositeValidator::new(bounds, SymbolValidator::permissive());
+
+        let deep = snapshot_payload(&[(100, 1), (99, 1), (98, 1)], &[(101, 1), (102, 1)]);
+        match validator.validate_book_snapshot(&deep) {
+            Err(ValidationError::OutOfBounds { field, value }) => {
+                assert_eq!(field, BoundsField::BookDepth(4));
+                assert_eq!(field.to_string(), "levels[4]");
+                assert_eq!(value, 5.0);
+            }
+            other => panic!("esperado OutOfBounds, obtido {:?}", other),
+        }
+
+        let heavy = snapshot_payload(&[(100, 1), (99, 1)], &[(101, 5_000)]);
+        match validator.validate_book_snapshot(&heavy) {
+            Err(ValidationError::OutOfBounds { field, value }) => {
+                assert_eq!(field, BoundsField::LevelQuantity(2));
+                assert_eq!(field.to_string(), "levels[2].quantity");
+                assert_eq!(value, 5_000.0);
+            }
+            other => panic!("esperado OutOfBounds, obtido {:?}", other),
+        }
+
+        assert!(validator.validate_book_snapshot(&snapshot_payload(&[(100, 1_000)], &[(101, 1)])).is_ok());
+    }
+
+    #[test]
+    fn test_per_type_stats() {
+        use crate::ingestion::zero_copy::{MessageHeader, Quote, Trade};
+
+        let mut validator = CompositeValidator::new(DataBounds::crypto(), SymbolValidator::permissive());
+        let ts = 1_700_000_000_000_000_000;
+
+        for i in 0..3 {
+            let frame = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts + i, 1, i).to_frame();
+            let header = MessageHeader::from_bytes(&frame).unwrap();
+            assert!(validator.validate_message(&header, &frame[MessageHeader::SIZE..]).is_ok());
+        }
+        for i in 0..2 {
+            let quote = Quote::new(*b"BTCUSD\0\0", 20_000_000 * 100_000_000, 1, 20_000_001 * 100_000_000, 1, ts + i);
+            let frame = quote.to_frame();
+            let header = MessageHeader::from_bytes(&frame).unwrap();
+            assert!(validator.validate_message(&header, &frame[MessageHeader::SIZE..]).is_err());
+        }
+
+        let stats = validator.type_stats();
+        assert_eq!(stats.len(), 2);
+
+        let trades = &stats[0];
+        assert_eq!((trades.msg_type, trades.received, trades.rejected), (Trade::MSG_TYPE, 3, 0));
+
+        let quotes = &stats[1];
+        assert_eq!((quotes.msg_type, quotes.received, quotes.rejected), (Quote::MSG_TYPE, 2, 2));
+        assert_eq!(quotes.rejections_for(ValidationErrorKind::OutOfBounds), 2);
+        assert_eq!(quotes.rejections_for(ValidationErrorKind::ChecksumMismatch), 0);
+    }
+
+    #[test]
+    fn test_builder_sequence_and_rate_limit() {
+        use crate::ingestion::zero_copy::{MessageHeader, Trade};
+
+        let mut validator = CompositeValidator::builder()
+            .bounds(DataBounds::crypto())
+            .symbol_validator(SymbolValidator::whitelist(vec!["BTCUSD".to_string()]))
+            .checksum_algorithm(ChecksumAlgorithm::Crc32)
+            .with_sequence_validator()
+            .with_rate_limiter(3, Duration::from_secs(1))
+            .build();
+
+        let ts = 1_700_000_000_000_000_000;
+        let mut validate = |trade_id: u64, timestamp: u64| {
+            let frame = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, timestamp, 1, trade_id).to_frame();
+            let header = MessageHeader::from_bytes(&frame).unwrap();
+            validator.validate_message(&header, &frame[MessageHeader::SIZE..])
+        };
+
+        assert!(validate(10, ts).is_ok());
+        assert!(validate(11, ts + 1).is_ok());
+        assert!(matches!(
+            validate(13, ts + 2),
+            Err(ValidationError::SequenceViolation { expected: 12, received: 13 })
+        ));
+        assert!(validate(12, ts + 3).is_ok());
+        assert!(matches!(validate(13, ts + 4), Err(ValidationError::RateLimitExceeded { limit: 3 })));
+
+        // Nova janela de 1s: o limite é renovado, e a rejeição pelo limite
+        // não avançou a sequência, então a retransmissão de 13 é aceita
+        assert!(validate(13, ts + 1_000_000_000).is_ok());
+        assert!(matches!(validate(13, ts + 1_000_000_001), Err(ValidationError::SequenceViolation { expected: 14, .. })));
+        assert!(validate(14, ts + 1_000_000_002).is_ok());
+    }
+
+    #[test]
+    fn test_dry_run_leaves_state_untouched() {
+        use crate::ingestion::zero_copy::{MessageHeader, Trade};
+
+        let mut validator = CompositeValidator::builder()
+            .with_sequence_validator()
+            .with_rate_limiter(2, Duration::from_secs(60))
+            .build();
+
+        let ts = 1_700_000_000_000_000_000;
+        let second = 1_000_000_000;
+        let frame = |trade_id: u64, timestamp: u64| {
+            Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, timestamp, 1, trade_id).to_frame()
+        };
+        let header = |frame: &[u8]| MessageHeader::from_bytes(frame).unwrap();
+
+        let first = frame(10, ts);
+        validator.validate_message(&header(&first), &first[MessageHeader::SIZE..]).unwrap();
+
+        // Rejeitado pela sequência; uma validação real já teria registrado ts + 10s
+        let gap = frame(12, ts + 10 * second);
+        assert!(matches!(
+            validator.validate_dry_run(0, &header(&gap), &gap[MessageHeader::SIZE..]),
+            Err(ValidationError::SequenceViolation { expected: 11, received: 12 })
+        ));
+
+        // Aceito em dry-run sem avançar sequência nem consumir o limite de taxa
+        let next = frame(11, ts + 5 * second);
+        for _ in 0..3 {
+            assert!(validator.validate_dry_run(0, &header(&next), &next[MessageHeader::SIZE..]).is_ok());
+        }
+        assert!(validator.validate_message(&header(&next), &next[MessageHeader::SIZE..]).is_ok());
+
+        let limited = frame(12, ts + 6 * second);
+        assert!(matches!(
+            validator.validate_dry_run(0, &header(&limited), &limited[MessageHeader::SIZE..]),
+            Err(ValidationError::RateLimitExceeded { limit: 2 })
+        ));
+        assert_eq!(validator.type_stats()[0].received, 2);
+    }
+
+    #[test]
+    fn test_gap_event_and_fill() {
+        use crate::ingestion::zero_copy::{MessageHeader, Trade};
+
+        let mut validator = CompositeValidator::builder().with_gap_recovery(8).build();
+        let gaps = validator.gap_events().unwrap();
+        let ts = 1_700_000_000_000_000_000;
+        let validate = |validator: &mut CompositeValidator, trade_id: u64, timestamp: u64| {
+            let frame = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, timestamp, 1, trade_id).to_frame();
+            let header = MessageHeader::from_bytes(&frame).unwrap();
+            validator.validate_message(&header, &frame[MessageHeader::SIZE..])
+        };
+
+        assert!(validate(&mut validator, 1, ts).is_ok());
+        assert!(validate(&mut validator, 5, ts + 4).is_ok());
+        assert_eq!(gaps.try_recv().unwrap(), GapEvent { symbol: *b"BTCUSD\0\0", source: 0, from_seq: 2, to_seq: 4 });
+        assert!(validate(&mut validator, 6, ts + 5).is_ok());
+
+        // Retransmissão fora de ordem, com timestamp anterior, fecha parte da lacuna
+        assert!(validate(&mut validator, 3, ts + 2).is_ok());
+        assert!(validate(&mut validator, 3, ts + 2).is_err());
+        validator.mark_filled(b"BTCUSD\0\0", 0, 2..=4);
+        assert!(matches!(
+            validate(&mut validator, 4, ts + 3),
+            Err(ValidationError::SequenceViolation { expected: 7, received: 4 })
+        ));
+
+        assert!(validate(&mut validator, 7, ts + 6).is_ok());
+        assert!(gaps.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_trade_id_monotonicity() {
+        let btc = *b"BTCUSD\0\0";
+        let eth = *b"ETHUSD\0\0";
+
+        let mut strict = TradeIdValidator::new(TradeIdStrictness::Strict);
+        assert!(strict.validate_trade_id(&btc, 0, 100).is_ok());
+        assert!(strict.validate_trade_id(&btc, 0, 105).is_ok());
+        assert!(matches!(
+            strict.validate_trade_id(&btc, 0, 105),
+            Err(ValidationError::TradeIdRegression { prev: 105, current: 105 })
+        ));
+        assert!(matches!(
+            strict.validate_trade_id(&btc, 0, 99),
+            Err(ValidationError::TradeIdRegression { prev: 105, current: 99 })
+        ));
+        // Primeiro trade_id de outro símbolo é aceito independentemente do valor
+        assert!(strict.validate_trade_id(&eth, 0, 1).is_ok());
+
+        let mut lenient = TradeIdValidator::new(TradeIdStrictness::AllowDuplicates);
+        assert!(lenient.validate_trade_id(&btc, 0, 7).is_ok());
+        assert!(lenient.validate_trade_id(&btc, 0, 7).is_ok());
+        assert!(lenient.validate_trade_id(&btc, 0, 6).is_err());
+    }
+
+    #[test]
+    fn test_reset_session_clears_state_but_keeps_config() {
+        use crate::ingestion::zero_copy::{MessageHeader, Trade};
+
+        let mut validator = CompositeValidator::builder()
+            .symbol_validator(SymbolValidator::whitelist(vec!["BTCUSD".to_string()]))
+            .with_sequence_validator()
+            .with_rate_limiter(2, Duration::from_secs(1))
+            .build();
+        let ts = 1_700_000_000_000_000_000;
+        let validate = |validator: &mut CompositeValidator, symbol: [u8; 8], trade_id: u64, timestamp: u64| {
+            let frame = Trade::new(symbol, 50_000 * 100_000_000, 100_000_000, timestamp, 1, trade_id).to_frame();
+            let header = MessageHeader::from_bytes(&frame).unwrap();
+            validator.validate_message(&header, &frame[MessageHeader::SIZE..])
+        };
+
+        assert!(validate(&mut validator, *b"BTCUSD\0\0", 10, ts).is_ok());
+        assert!(validate(&mut validator, *b"BTCUSD\0\0", 11, ts + 1).is_ok());
+        assert!(matches!(
+            validate(&mut validator, *b"BTCUSD\0\0", 12, ts - 1_000_000_000),
+            Err(ValidationError::TemporalOrderViolation { .. })
+        ));
+
+        validator.reset_session();
+        // Timestamp e sequência anteriores passam como primeira mensagem do símbolo
+        assert!(validate(&mut validator, *b"BTCUSD\0\0", 1, ts - 1_000_000_000).is_ok());
+        assert!(validate(&mut validator, *b"BTCUSD\0\0", 2, ts - 999_999_999).is_ok());
+        assert!(matches!(
+            validate(&mut validator, *b"BTCUSD\0\0", 3, ts - 999_999_998),
+            Err(ValidationError::RateLimitExceeded { limit: 2 })
+        ));
+        assert!(validate(&mut validator, *b"ETHUSD\0\0", 1, ts).is_err());
+    }
+
+    #[test]
+    fn test_compact_drops_stale_symbols() {
+        use crate::ingestion::clock::MockClock;
+
+        let clock = Arc::new(MockClock::new(1_700_000_000_000_000_000));
+        let mut temporal = TemporalValidator::new(Duration::ZERO).with_clock(clock.clone());
+        let mut sequence = SequenceValidator::new().with_clock(clock.clone());
+
+        temporal.validate_monotonic(b"BTCUSD\0\0", 0, 10).unwrap();
+        temporal.validate_monotonic(b"ETHUSD\0\0", 0, 10).unwrap();
+        sequence.validate_sequence(b"ETHUSD\0\0", 0, 1).unwrap();
+
+        clock.advance(Duration::from_secs(60));
+        temporal.validate_monotonic(b"BTCUSD\0\0", 0, 20).unwrap();
+
+        assert_eq!(temporal.compact(Duration::from_secs(30)), 1);
+        assert_eq!(sequence.compact(Duration::from_secs(30)), 1);
+        assert_eq!((temporal.tracked(), sequence.tracked()), (1, 0));
+
+        // BTC continua ativo; ETH volta a ser aceito como primeira mensagem
+        assert!(temporal.validate_monotonic(b"BTCUSD\0\0", 0, 5).is_err());
+        assert!(temporal.validate_monotonic(b"ETHUSD\0\0", 0, 1).is_ok());
+        assert!(sequence.validate_sequence(b"ETHUSD\0\0", 0, 42).is_ok());
+    }
+
+    #[test]
+    fn test_source_type_restrictions() {
+        use crate::ingestion::zero_copy::{MessageHeader, Quote, Trade};
+
+        const QUOTES_ONLY: u8 = 2;
+        let mut validator = CompositeValidator::builder().allowed_types_for_source(QUOTES_ONLY, [Quote::MSG_TYPE]).build();
+        let ts = 1_700_000_000_000_000_000;
+        let trade = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts, 1, 1).to_frame();
+        let quote = Quote::new(*b"BTCUSD\0\0", 100, 1, 101, 1, ts).to_frame();
+        let validate = |validator: &mut CompositeValidator, source: u8, frame: &[u8]| {
+            let header = MessageHeader::from_bytes(frame).unwrap();
+            validator.validate_message_from(source, &header, &frame[MessageHeader::SIZE..])
+        };
+
+        assert!(validate(&mut validator, QUOTES_ONLY, &quote).is_ok());
+        let err = validate(&mut validator, QUOTES_ONLY, &trade).unwrap_err();
+        assert!(matches!(err, ValidationError::TypeNotAllowedForSource { msg_type: Trade::MSG_TYPE, source_id: QUOTES_ONLY }));
+        assert_eq!(err.kind(), ValidationErrorKind::TypeNotAllowedForSource);
+        // Outras fontes seguem sem restrição
+        assert!(validate(&mut validator, 0, &trade).is_ok());
+    }
+
+    #[test]
+    fn test_checksum_exempt_types() {
+        use crate::ingestion::zero_copy::{MessageHeader, Trade};
+
+        const HEARTBEAT: u8 = 3;
+        let mut validator = CompositeValidator::builder()
+            .checksum_exempt_types([HEARTBEAT, Trade::MSG_TYPE])
+            .build();
+
+        let ts = 1_700_000_000_000_000_000;
+        let heartbeat = MessageHeader::builder().msg_type(HEARTBEAT).timestamp(ts).checksum(0).build();
+        assert!(validator.validate_message(&heartbeat, &[0u8; 8]).is_ok());
+
+        let mut frame = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts, 1, 1).to_frame();
+        frame[MessageHeader::SIZE] ^= 0xFF;
+        let header = MessageHeader::from_bytes(&frame).unwrap();
+        assert!(matches!(
+            validator.validate_message(&header, &frame[MessageHeader::SIZE..]),
+            Err(ValidationError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_future_timestamp_tolerance() {
+        use crate::ingestion::clock::MockClock;
+        use crate::ingestion::zero_copy::MessageHeader;
+
+        let now = 1_700_000_000_000_000_000;
+        let mut validator = CompositeValidator::builder()
+            .clock(Arc::new(MockClock::new(now)))
+            .future_tolerance(Duration::from_millis(100))
+            .future_tolerance_for_source(1, Duration::from_secs(5))
+            .build();
+
+        let payload = [0u8; 8];
+        let header = |timestamp: u64| {
+            MessageHeader::builder().msg_type(2).timestamp(timestamp).payload(&payload).build()
+        };
+        let slightly_ahead = now + 50_000_000;
+        let seconds_ahead = now + 2_000_000_000;
+        let year_ahead = now + 365 * 24 * 3600 * 1_000_000_000;
+
+        assert!(validator.validate_message(&header(slightly_ahead), &payload).is_ok());
+        assert!(matches!(
+            validator.validate_message(&header(seconds_ahead), &payload),
+            Err(ValidationError::InvalidTimestamp(_))
+        ));
+        assert!(validator.validate_message_from(1, &header(seconds_ahead), &payload).is_ok());
+        assert!(validator.validate_message_from(1, &header(year_ahead), &payload).is_err());
+    }
+
+    #[test]
+    fn test_strictness_profiles() {
+        use crate::ingestion::zero_copy::{MessageHeader, Trade};
+
+        let ts = 1_700_000_000_000_000_000;
+        let mut frame = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts, 1, 1).to_frame();
+        frame[MessageHeader::SIZE + 9] ^= 0x01;
+        let header = MessageHeader::from_bytes(&frame).unwrap();
+        let payload = &frame[MessageHeader::SIZE..];
+
+        let mut strict = CompositeValidator::builder().profile(StrictnessProfile::Strict).build();
+        assert!(matches!(
+            strict.validate_message(&header, payload),
+            Err(ValidationError::ChecksumMismatch { .. })
+        ));
+
+        let mut lenient = CompositeValidator::builder().profile(StrictnessProfile::Lenient).build();
+        assert!(lenient.validate_message(&header, payload).is_ok());
+        let trades = lenient.type_stats().into_iter().find(|c| c.msg_type == Trade::MSG_TYPE).unwrap();
+        assert_eq!(trades.rejections_for(ValidationErrorKind::ChecksumMismatch), 1);
+
+        // Development não calcula CRC e aceita tipos desconhecidos
+        let mut dev = CompositeValidator::builder().profile(StrictnessProfile::Development).build();
+        assert!(dev.validate_message(&header, payload).is_ok());
+        let unknown = MessageHeader::builder().msg_type(200).timestamp(ts).payload(&[0u8; 4]).checksum(0).build();
+        assert!(dev.validate_message(&unknown, &[0u8; 4]).is_ok());
+    }
+
+    #[test]
+    fn test_layered_header_and_payload_checksums() {
+        use crate::ingestion::zero_copy::{MessageHeader, Trade};
+
+        let ts = 1_700_000_000_000_000_000;
+        let trade = Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts, 1, 1);
+        let layered = MessageHeader::builder().msg_type(Trade::MSG_TYPE).version(2).timestamp(ts).frame(&trade.to_bytes());
+        assert_eq!(layered.len(), MessageHeader::SIZE + MessageHeader::HEADER_CHECKSUM_SIZE + Trade::SIZE);
+
+        let validate = |frame: &[u8]| CompositeValidator::builder().build().validate_frame(frame);
+        assert!(validate(&layered).is_ok());
+
+        // Header corrompido (timestamp), payload intacto
+        let mut header_hit = layered.clone();
+        header_hit[9] ^= 0x01;
+        assert!(matches!(validate(&header_hit), Err(ValidationError::HeaderChecksumMismatch { .. })));
+
+        // Payload corrompido, header intacto
+        let mut payload_hit = layered.clone();
+        payload_hit[MessageHeader::SIZE + MessageHeader::HEADER_CHECKSUM_SIZE + 12] ^= 0x01;
+        assert!(matches!(validate(&payload_hit), Err(ValidationError::ChecksumMismatch { .. })));
+
+        // v1 não tem checksum do header: a mesma corrupção passa despercebida
+        let mut legacy = trade.to_frame();
+        assert!(validate(&legacy).is_ok());
+        legacy[9] ^= 0x01;
+        assert!(validate(&legacy).is_ok());
+
+        let report = CompositeValidator::builder().build().validate_batch(&[layered, header_hit, payload_hit]);
+        assert_eq!(report.rejections_for(ValidationErrorKind::HeaderChecksumMismatch), 1);
+        assert_eq!(report.rejections_for(ValidationErrorKind::ChecksumMismatch), 1);
+    }
+
+    #[test]
+    fn test_unknown_type_policy_and_crc32c() {
+        use crate::ingestion::zero_copy::MessageHeader;
+
+        let payload = [1u8; 8];
+        let crc32c = ChecksumValidator::with_algorithm(ChecksumAlgorithm::Crc32c);
+        assert_eq!(crc32c.calculate(b"123456789"), 0xE3069283);
+        assert_eq!(ChecksumValidator::new().calculate(b"123456789"), 0xCBF43926);
+
+        let header = MessageHeader::builder()
+            .msg_type(200)
+            .timestamp(DataBounds::crypto().min_timestamp)
+            .checksum(crc32c.calculate(&payload))
+            .build();
+
+        let mut strict = CompositeValidator::builder().checksum_algorithm(ChecksumAlgorithm::Crc32c).build();
+        assert!(matches!(strict.validate_message(&header, &payload), Err(ValidationError::UnknownMessageType(200))));
+
+        let mut lenient = CompositeValidator::builder()
+            .checksum_algorithm(ChecksumAlgorithm::Crc32c)
+            .unknown_type_policy(UnknownTypePolicy::Accept)
+            .build();
+        assert!(lenient.validate_message(&header, &payload).is_ok());
+    }
+
+    #[cfg(feature = "stage-timing")]
+    #[test]
+    fn test_stage_timings_cover_every_stage() {
+        use crate::ingestion::clock::MockClock;
+        use crate::ingestion::synthetic::{SyntheticConfig, SyntheticFeed};
+
+        let mut feed = SyntheticFeed::new(SyntheticConfig::default(), Arc::new(MockClock::new(1_700_000_000_000_000_000)));
+        let frames: Vec<_> = (0..1_000).map(|_| feed.next_frame()).collect();
+
+        let mut validator = CompositeValidator::builder()
+            .symbol_validator(SymbolValidator::whitelist(vec!["BTCUSD".to_string()]))
+            .build();
+        assert_eq!(validator.stage_timings(), StageTimings::default());
+
+        let report = validator.validate_batch(&frames);
+        assert_eq!(report.accepted, 1_000);
+
+        let timings = validator.stage_timings();
+        assert!(timings.checksum_nanos > 0);
+        assert!(timings.bounds_nanos > 0);
+        assert!(timings.temporal_nanos > 0);
+        assert!(timings.symbol_nanos > 0);
+    }
+
+    #[test]
+    fn test_symbol_normalization() {
+        use crate::ingestion::zero_copy::{MessageHeader, Trade};
+
+        let normalization = SymbolNormalization::new().uppercase().strip_separators(b"-/");
+        let symbols = SymbolValidator::whitelist(vec!["BTCUSD".to_string(), "eth/usdt".to_string()])
+            .with_normalization(normalization.clone());
+        assert!(symbols.validate(b"btc-usd\0").is_ok());
+        assert!(symbols.validate(b"BTCUSD\0\0").is_ok());
+        assert!(symbols.validate(b"BTC/USD\0").is_ok());
+        assert!(symbols.validate(b"ETHUSDT\0").is_ok());
+        assert!(symbols.validate(b"SOLUSD\0\0").is_err());
+        assert_eq!(normalization.normalize(b"btc-usdt"), *b"BTCUSDT\0");
+        assert!(SymbolValidator::whitelist(vec!["BTCUSD".to_string()]).validate(b"btc-usd\0").is_err());
+
+        // Variantes compartilham o estado temporal do símbolo canônico
+        let mut validator = CompositeValidator::builder().symbol_validator(symbols).build();
+        let ts = 1_700_000_000_000_000_000;
+        let mut validate = |symbol: &[u8; 8], timestamp: u64| {
+            let frame = Trade::new(*symbol, 50_000 * 100_000_000, 100_000_000, timestamp, 1, 1).to_frame();
+            let header = MessageHeader::from_bytes(&frame).unwrap();
+            validator.validate_message(&header, &frame[MessageHeader::SIZE..])
+        };
+        assert!(validate(b"btc-usd\0", ts).is_ok());
+        assert!(matches!(validate(b"BTCUSD\0\0", ts - 10_000_000), Err(ValidationError::TemporalOrderViolation { .. })));
+    }
+
+    #[test]
+    fn test_symbol_str() {
+        assert_eq!(symbol_str(b"BTCUSDT1").unwrap(), "BTCUSDT1");
+        assert_eq!(symbol_str(b"BTC\0\0\0\0\0").unwrap(), "BTC");
+        assert!(matches!(symbol_str(b"BTC\0USD\0"), Err(ValidationError::InvalidSymbol(_))));
+        assert!(matches!(symbol_str(b"BTC\xFF\xFE\0\0\0"), Err(ValidationError::InvalidSymbol(_))));
+
+        let strict = SymbolValidator::permissive().strict_utf8();
+        assert!(strict.validate(b"BTC\0\0\0\0\0").is_ok());
+        assert!(strict.validate(b"BTC\0USD\0").is_err());
+        assert!(SymbolValidator::permissive().validate(b"BTC\0USD\0").is_ok());
+        let err = strict.validate(b"BTC\xFF\xFE\0\0\0").unwrap_err();
+        assert!(err.to_string().contains("UTF-8"));
+    }
+
+    #[test]
+    fn test_format_only_accepts_unknown_well_formed_symbols() {
+        let format_only = SymbolValidator::format_only();
+        for symbol in [b"NEWCOIN\0", b"BTC-USD\0", b"A1_B2\0\0\0"] {
+            assert!(format_only.validate(symbol).is_ok(), "{:?}", symbol);
+        }
+        assert!(format_only.contains("QUALQUER"));
+
+        let issue = |symbol: &[u8; 8]| match format_only.validate(symbol) {
+            Err(ValidationError::InvalidSymbol(issue)) => issue,
+            other => panic!("esperado InvalidSymbol, obtido {:?}", other),
+        };
+        assert_eq!(issue(b"BTC\0USD\0"), SymbolIssue::ByteAfterNul(*b"BTC\0USD\0"));
+        assert_eq!(issue(b"BTC\xFF\xFE\0\0\0"), SymbolIssue::InvalidUtf8(*b"BTC\xFF\xFE\0\0\0"));
+        assert_eq!(issue(b"BTC$USD\0"), SymbolIssue::InvalidChar(b'$'));
+        assert_eq!(issue(&[0; 8]), SymbolIssue::Empty);
+        // O permissivo deixa passar o mesmo lixo
+        assert!(SymbolValidator::permissive().validate(b"BTC\0USD\0").is_ok());
+        assert!(SymbolValidator::permissive().validate(&[0; 8]).is_ok());
+    }
+
+    #[test]
+    fn test_strict_padding_rejects_non_zero_padding() {
+        use crate::ingestion::zero_copy::{MessageHeader, Quote, Trade};
+
+        let ts = 1_700_000_000_000_000_000;
+        let mut trade = Trade::new(*b"BTCUSD\0\0", 100_000_000, 100_000_000, ts, 1, 1).to_bytes();
+        trade[Trade::SIZE - 1] = 0xAA;
+        let mut quote = Quote::new(*b"BTCUSD\0\0", 100_000_000, 1, 101_000_000, 1, ts).to_bytes();
+        quote[Quote::SIZE - 8] = 1;
+        let frames = [
+            MessageHeader::builder().msg_type(Trade::MSG_TYPE).timestamp(ts).frame(&trade),
+            MessageHeader::builder().msg_type(Quote::MSG_TYPE).timestamp(ts).frame(&quote),
+        ];
+
+        let mut strict = CompositeValidator::builder().strict_padding(true).build();
+        let mut lax = CompositeValidator::builder().build();
+        for frame in &frames {
+            let error = strict.validate_frame(frame).unwrap_err();
+            assert!(matches!(error, ValidationError::NonZeroPadding { .. }));
+            assert_eq!(error.kind(), ValidationErrorKind::CorruptedFormat);
+            // O checksum cobre o padding, então sem a opção a mensagem passa
+            assert!(lax.validate_frame(frame).is_ok());
+        }
+        let clean = Trade::new(*b"BTCUSD\0\0", 100_000_000, 100_000_000, ts + 1, 1, 2).to_frame();
+        assert!(strict.validate_frame(&clean).is_ok());
+    }
+
+    #[test]
+    fn test_known_symbols_round_trip() {
+        let input = ["BTCUSD", "ETHUSDT", "SOL-USD"];
+        let symbols = SymbolValidator::whitelist(input.iter().map(|s| s.to_string()).collect());
+        let mut known: Vec<String> = symbols.known_symbols().collect();
+        known.sort();
+        assert_eq!(known, ["BTCUSD", "ETHUSDT", "SOL-USD"]);
+        assert!(symbols.contains("ETHUSDT"));
+        assert!(!symbols.contains("ethusdt"));
+
+        let normalized = symbols.with_normalization(SymbolNormalization::new().uppercase().strip_separators(b"-"));
+        assert!(normalized.contains("sol-usd"));
+        assert!(normalized.known_symbols().any(|s| s == "SOLUSD"));
+
+        let permissive = SymbolValidator::permissive();
+        assert_eq!(permissive.known_symbols().count(), 0);
+        assert!(permissive.contains("ANYTHING"));
+    }
+
+    #[test]
+    fn test_symbol_prefix_whitelist() {
+        let symbols = SymbolValidator::whitelist(vec!["ETHBTC".to_string()])
+            .with_prefixes(vec!["BTC".to_string(), "SOL-".to_string(), String::new()]);
+        assert!(symbols.validate(b"BTCUSDT\0").is_ok());
+        assert!(symbols.validate(b"BTC\0\0\0\0\0").is_ok());
+        assert!(symbols.validate(b"ETHUSDT\0").is_err());
+        assert!(symbols.validate(b"BT\0\0\0\0\0\0").is_err());
+        assert!(symbols.validate(b"ETHBTC\0\0").is_ok());
+        assert!(symbols.contains("SOL-USDC"));
+        assert!(!symbols.contains("SOLUSDC"));
+
+        let normalized = symbols.with_normalization(SymbolNormalization::new().uppercase().strip_separators(b"-"));
+        assert!(normalized.validate(b"sol-usdc").is_ok());
+        assert!(normalized.known_symbols().eq(["ETHBTC".to_string()]));
+    }
 }
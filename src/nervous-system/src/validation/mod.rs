@@ -1 +1,9 @@
+pub mod audit;
+pub mod baseline;
+pub mod dedup;
+pub mod imbalance;
 pub mod integrity;
+pub mod parallel;
+pub mod registry;
+pub mod report;
+pub mod stats;
@@ -0,0 +1,157 @@
+//! Validação paralela em lote (backfill), particionada por símbolo
+
+use crate::ingestion::zero_copy::{MessageHeader, TradeBatch};
+use crate::validation::integrity::CompositeValidator;
+use crate::validation::report::ValidationReport;
+
+/// Distribui frames entre threads pelo hash do símbolo, com um
+/// `CompositeValidator` por thread. Todas as mensagens de um símbolo caem
+/// sempre no mesmo validador e na ordem original, então o estado por símbolo
+/// (temporal, sequência, trade_id) fica correto sem nenhum lock. Frames sem
+/// símbolo identificável (malformados, tipos sem símbolo) vão para o shard 0.
+///
+/// Os validadores persistem entre chamadas de `validate_batch`, como um
+/// validador único persistiria entre lotes.
+pub struct ParallelValidator {
+    shards: Vec<CompositeValidator>,
+}
+
+impl ParallelValidator {
+    pub fn new(workers: usize, factory: impl Fn() -> CompositeValidator) -> Self {
+        Self { shards: (0..workers.max(1)).map(|_| factory()).collect() }
+    }
+
+    pub fn workers(&self) -> usize { self.shards.len() }
+
+    pub fn validate_batch<F: AsRef<[u8]> + Sync>(&mut self, frames: &[F]) -> ValidationReport {
+        let workers = self.shards.len();
+        let mut assignments: Vec<Vec<(u64, &[u8])>> = vec![Vec::new(); workers];
+        for (position, frame) in frames.iter().enumerate() {
+            let frame = frame.as_ref();
+            assignments[shard_for(&self.shards[0], frame, workers)].push((position as u64, frame));
+        }
+
+        let partials: Vec<ValidationReport> = std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .shards
+                .iter_mut()
+                .zip(assignments)
+                .map(|(validator, frames)| {
+                    scope.spawn(move || {
+                        let mut report = ValidationReport::default();
+                        for (position, frame) in frames {
+                            validator.validate_frame_into(position, frame, &mut report);
+                        }
+                        report
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().expect("worker de validação falhou")).collect()
+        });
+
+        let mut report = ValidationReport::default();
+        for partial in &partials {
+            report.merge(partial);
+        }
+        report
+    }
+}
+
+/// Trades, quotes e snapshots começam o payload com o símbolo de 8 bytes; um
+/// lote de trades vai para o shard do símbolo do seu primeiro trade (lotes
+/// com um símbolo só mantêm o estado por símbolo inteiro num shard). O hash
+/// usa o símbolo normalizado, para que variantes caiam no mesmo shard.
+fn shard_for(validator: &CompositeValidator, frame: &[u8], workers: usize) -> usize {
+    let symbol = MessageHeader::from_bytes(frame)
+        .filter(MessageHeader::is_valid)
+        .and_then(|header| {
+            let offset = match header.msg_type {
+                0 | 1 | 4 => header.payload_offset(),
+                TradeBatch::MSG_TYPE => header.payload_offset() + TradeBatch::COUNT_SIZE,
+                _ => return None,
+            };
+            frame.get(offset..offset + 8)
+        });
+
+    match symbol {
+        Some(symbol) => {
+            let symbol = validator.normalize_symbol(symbol.try_into().expect("slice de 8 bytes"));
+            let key = u64::from_le_bytes(symbol);
+            (key.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 32) as usize % workers
+        }
+        None => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingestion::clock::MockClock;
+    use crate::ingestion::synthetic::{SyntheticConfig, SyntheticFeed};
+    use crate::validation::integrity::{SymbolValidator, TradeIdStrictness, ValidationErrorKind};
+    use std::sync::Arc;
+
+    fn validator() -> CompositeValidator {
+        CompositeValidator::builder()
+            .symbol_validator(SymbolValidator::whitelist(
+                ["BTCUSD", "ETHUSD", "SOLUSD", "ADAUSD"].iter().map(|s| s.to_string()).collect(),
+            ))
+            .with_trade_id_validator(TradeIdStrictness::Strict)
+            .build()
+    }
+
+    #[test]
+    fn test_parallel_matches_single_threaded() {
+        let config = SyntheticConfig {
+            symbols: vec![*b"BTCUSD\0\0", *b"ETHUSD\0\0", *b"SOLUSD\0\0", *b"ADAUSD\0\0", *b"XRPUSD\0\0"],
+            seed: 11,
+            ..SyntheticConfig::default()
+        };
+        let mut feed = SyntheticFeed::new(config, Arc::new(MockClock::new(1_700_000_000_000_000_000)));
+
+        let mut frames = Vec::new();
+        for i in 0..20_000 {
+            let mut frame = feed.next_frame();
+            match i % 97 {
+                0 => frame[MessageHeader::SIZE + 10] ^= 0xFF,
+                1 => frame.truncate(MessageHeader::SIZE + 4),
+                2 => frames.push(frame.clone()),
+                _ => {}
+            }
+            frames.push(frame);
+        }
+
+        let single = validator().validate_batch(&frames);
+        let parallel = ParallelValidator::new(4, validator).validate_batch(&frames);
+
+        assert_eq!(parallel, single);
+        assert_eq!(parallel.frames, frames.len() as u64);
+        assert!(parallel.malformed > 0);
+        assert!(parallel.rejections_for(ValidationErrorKind::ChecksumMismatch) > 0);
+        assert!(parallel.rejections_for(ValidationErrorKind::InvalidSymbol) > 0);
+        assert!(parallel.rejections_for(ValidationErrorKind::TradeIdRegression) > 0);
+    }
+
+    #[test]
+    fn test_trade_batches_share_the_symbol_shard() {
+        use crate::ingestion::zero_copy::Trade;
+
+        let reference = validator();
+        let ts = 1_700_000_000_000_000_000;
+        let mut shards = Vec::new();
+        for symbol in [*b"BTCUSD\0\0", *b"ETHUSD\0\0", *b"SOLUSD\0\0", *b"ADAUSD\0\0"] {
+            let trades = [Trade::new(symbol, 1, 1, ts, 1, 1), Trade::new(symbol, 1, 1, ts + 1, 1, 2)];
+            let single = shard_for(&reference, &trades[0].to_frame(), 8);
+            assert_eq!(shard_for(&reference, &TradeBatch::frame(&trades), 8), single);
+            shards.push(single);
+        }
+        assert!(shards.iter().any(|&shard| shard != 0));
+
+        // Lote intercalado com trades avulsos: mesma ordem por símbolo do validador único
+        let trades: Vec<Trade> = (0..6).map(|i| Trade::new(*b"ETHUSD\0\0", 1, 1, ts + i, 1, i + 1)).collect();
+        let frames = [trades[0].to_frame(), TradeBatch::frame(&trades[1..4]), trades[4].to_frame(), TradeBatch::frame(&trades[2..3])];
+        let parallel = ParallelValidator::new(4, validator).validate_batch(&frames);
+        assert_eq!(parallel, validator().validate_batch(&frames));
+        assert_eq!((parallel.accepted, parallel.rejections_for(ValidationErrorKind::TradeIdRegression)), (3, 1));
+    }
+}
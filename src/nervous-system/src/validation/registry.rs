@@ -0,0 +1,191 @@
+//! Registro de tipos de mensagem: tamanho mínimo, validação de payload e nome por `msg_type`
+
+use crate::ingestion::zero_copy::{BookSnapshot, EndOfSession, Quote, Trade, TradeBatch};
+use crate::validation::integrity::{CompositeValidator, ValidationError};
+
+/// Campos que os estágios com estado do `CompositeValidator` usam: o limitador
+/// de taxa recebe todo tipo que os informa; a ordem temporal, a sequência e o
+/// `trade_id` só os que têm `trade_id`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyedFields {
+    pub symbol: [u8; 8],
+    pub timestamp: u64,
+    pub trade_id: Option<u64>,
+}
+
+/// Valida o payload de um tipo (já com o tamanho mínimo garantido). Recebe o
+/// validador para reaproveitar seus limites e whitelist de símbolos.
+pub type PayloadValidatorFn = fn(&CompositeValidator, &[u8]) -> Result<Option<KeyedFields>, ValidationError>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct MessageTypeDescriptor {
+    /// Nome para logs.
+    pub name: &'static str,
+    /// Payloads menores são rejeitados com `CorruptedFormat` antes de `validate`.
+    pub min_size: usize,
+    pub validate: PayloadValidatorFn,
+}
+
+impl MessageTypeDescriptor {
+    /// Tipo sem validação de payload além do tamanho mínimo.
+    pub const fn opaque(name: &'static str, min_size: usize) -> Self {
+        Self { name, min_size, validate: |_, _| Ok(None) }
+    }
+}
+
+/// `msg_type` → descritor. `CompositeValidator` despacha a validação de
+/// payload por aqui; tipos sem registro seguem a `UnknownTypePolicy`.
+#[derive(Clone)]
+pub struct MessageTypeRegistry {
+    descriptors: Box<[Option<MessageTypeDescriptor>; 256]>,
+}
+
+impl MessageTypeRegistry {
+    pub fn empty() -> Self {
+        Self { descriptors: Box::new([None; 256]) }
+    }
+
+    /// Os tipos do protocolo: trade (0), quote (1), 2 e 3 sem validação de
+    /// payload, snapshot de book (4), fim de sessão (5) e lote de trades (6).
+    /// A ordem temporal e o `trade_id` dos trades de um lote são verificados
+    /// pelo `CompositeValidator` trade a trade, fora do descritor.
+    pub fn standard() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Trade::MSG_TYPE, MessageTypeDescriptor {
+            name: "trade",
+            min_size: Trade::SIZE,
+            validate: |validator, payload| {
+                let trade = validator.validate_trade(payload)?;
+                Ok(Some(KeyedFields { symbol: trade.symbol(), timestamp: trade.timestamp(), trade_id: Some(trade.trade_id()) }))
+            },
+        });
+        registry.register(Quote::MSG_TYPE, MessageTypeDescriptor {
+            name: "quote",
+            min_size: Quote::SIZE,
+            validate: |validator, payload| {
+                let quote = validator.validate_quote(payload)?;
+                Ok(Some(KeyedFields { symbol: quote.symbol(), timestamp: quote.timestamp(), trade_id: None }))
+            },
+        });
+        registry.register(2, MessageTypeDescriptor::opaque("type_2", 0));
+        registry.register(3, MessageTypeDescriptor::opaque("type_3", 0));
+        registry.register(BookSnapshot::MSG_TYPE, MessageTypeDescriptor {
+            name: "book_snapshot",
+            min_size: 0,
+            validate: |validator, payload| validator.validate_book_snapshot(payload).map(|()| None),
+        });
+        registry.register(EndOfSession::MSG_TYPE, MessageTypeDescriptor::opaque("end_of_session", 0));
+        registry.register(TradeBatch::MSG_TYPE, MessageTypeDescriptor {
+            name: "trade_batch",
+            min_size: TradeBatch::COUNT_SIZE,
+            validate: |validator, payload| validator.validate_trade_batch(payload).map(|()| None),
+        });
+        registry
+    }
+
+    /// Registra (ou substitui) o descritor de `msg_type`.
+    pub fn register(&mut self, msg_type: u8, descriptor: MessageTypeDescriptor) {
+        self.descriptors[msg_type as usize] = Some(descriptor);
+    }
+
+    pub fn unregister(&mut self, msg_type: u8) {
+        self.descriptors[msg_type as usize] = None;
+    }
+
+    #[inline]
+    pub fn get(&self, msg_type: u8) -> Option<&MessageTypeDescriptor> {
+        self.descriptors[msg_type as usize].as_ref()
+    }
+
+    pub fn name(&self, msg_type: u8) -> Option<&'static str> {
+        self.get(msg_type).map(|d| d.name)
+    }
+}
+
+impl Default for MessageTypeRegistry {
+    fn default() -> Self { Self::standard() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingestion::zero_copy::MessageHeader;
+    use crate::validation::integrity::{BoundsField, SymbolValidator, UnknownTypePolicy, ValidationErrorKind};
+    use crate::validation::report::ValidationReport;
+    use std::time::Duration;
+
+    const FUNDING_RATE: u8 = 9;
+
+    /// symbol (8) + taxa em basis points (i64) + timestamp (u64)
+    fn funding_frame(symbol: &[u8; 8], rate_bps: i64, timestamp: u64) -> bytes::BytesMut {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(symbol);
+        payload.extend_from_slice(&rate_bps.to_le_bytes());
+        payload.extend_from_slice(&timestamp.to_le_bytes());
+        MessageHeader::builder().msg_type(FUNDING_RATE).timestamp(timestamp).frame(&payload)
+    }
+
+    #[test]
+    fn test_custom_message_type() {
+        let descriptor = MessageTypeDescriptor {
+            name: "funding_rate",
+            min_size: 24,
+            validate: |validator, payload| {
+                let symbol: [u8; 8] = payload[..8].try_into().expect("tamanho mínimo garantido");
+                let rate_bps = i64::from_le_bytes(payload[8..16].try_into().expect("tamanho mínimo garantido"));
+                let timestamp = u64::from_le_bytes(payload[16..24].try_into().expect("tamanho mínimo garantido"));
+
+                validator.symbol_validator().validate(&symbol)?;
+                if rate_bps.abs() > 10_000 {
+                    return Err(ValidationError::OutOfBounds { field: BoundsField::Named("rate_bps"), value: rate_bps as f64 });
+                }
+                Ok(Some(KeyedFields { symbol, timestamp, trade_id: None }))
+            },
+        };
+
+        let mut validator = CompositeValidator::builder()
+            .symbol_validator(SymbolValidator::whitelist(vec!["BTCUSD".to_string()]))
+            .unknown_type_policy(UnknownTypePolicy::Reject)
+            .message_type(FUNDING_RATE, descriptor)
+            .with_rate_limiter(2, Duration::from_secs(1))
+            .build();
+        assert_eq!(validator.registry().name(FUNDING_RATE), Some("funding_rate"));
+        assert_eq!(validator.registry().name(Trade::MSG_TYPE), Some("trade"));
+
+        let ts = 1_700_000_000_000_000_000;
+        let frames = [
+            funding_frame(b"BTCUSD\0\0", 12, ts),
+            funding_frame(b"BTCUSD\0\0", 50_000, ts + 1),
+            funding_frame(b"DOGEUSD\0", 12, ts + 2),
+            funding_frame(b"BTCUSD\0\0", -3, ts + 3),
+            // Campos do tipo registrados valem para o limitador de taxa
+            funding_frame(b"BTCUSD\0\0", 7, ts + 4),
+        ];
+        let report = validator.validate_batch(&frames);
+
+        let mut expected = ValidationReport { frames: 5, accepted: 2, ..ValidationReport::default() };
+        expected.rejections[ValidationErrorKind::OutOfBounds.index()] = 1;
+        expected.rejections[ValidationErrorKind::InvalidSymbol.index()] = 1;
+        expected.rejections[ValidationErrorKind::RateLimitExceeded.index()] = 1;
+        expected.samples[ValidationErrorKind::OutOfBounds.index()] = vec![1];
+        expected.samples[ValidationErrorKind::InvalidSymbol.index()] = vec![2];
+        expected.samples[ValidationErrorKind::RateLimitExceeded.index()] = vec![4];
+        assert_eq!(report, expected);
+
+        let mut short = funding_frame(b"BTCUSD\0\0", 1, ts);
+        short.truncate(MessageHeader::SIZE + 16);
+        let header = MessageHeader::builder().msg_type(FUNDING_RATE).timestamp(ts).payload(&short[MessageHeader::SIZE..]).build();
+        assert!(matches!(
+            validator.validate_message(&header, &short[MessageHeader::SIZE..]),
+            Err(ValidationError::CorruptedFormat)
+        ));
+
+        let mut without = CompositeValidator::builder().unknown_type_policy(UnknownTypePolicy::Reject).build();
+        let frame = funding_frame(b"BTCUSD\0\0", 12, ts);
+        let header = MessageHeader::from_bytes(&frame).unwrap();
+        assert!(matches!(
+            without.validate_message(&header, &frame[MessageHeader::SIZE..]),
+            Err(ValidationError::UnknownMessageType(FUNDING_RATE))
+        ));
+    }
+}
@@ -0,0 +1,170 @@
+//! Relatório agregado de validação em lote
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::validation::integrity::{ValidationError, ValidationErrorKind};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub frames: u64,
+    pub accepted: u64,
+    /// Frames cujo header ou tamanho não permitem nem chegar à validação.
+    pub malformed: u64,
+    /// Rejeições por motivo, indexadas por `ValidationErrorKind::index`.
+    pub rejections: [u64; ValidationErrorKind::COUNT],
+    /// Posições (índice do frame no lote) das primeiras rejeições de cada
+    /// motivo, até `SAMPLES_PER_KIND`, em ordem crescente.
+    #[serde(default)]
+    pub samples: [Vec<u64>; ValidationErrorKind::COUNT],
+    #[serde(default)]
+    pub malformed_samples: Vec<u64>,
+}
+
+impl ValidationReport {
+    pub const SAMPLES_PER_KIND: usize = 5;
+
+    pub fn rejected(&self) -> u64 { self.rejections.iter().sum() }
+
+    pub fn rejections_for(&self, kind: ValidationErrorKind) -> u64 {
+        self.rejections[kind.index()]
+    }
+
+    pub fn samples_for(&self, kind: ValidationErrorKind) -> &[u64] {
+        &self.samples[kind.index()]
+    }
+
+    /// Registra o frame seguinte do lote (posição = frames já registrados).
+    pub fn record(&mut self, result: &Result<(), ValidationError>) {
+        self.record_at(self.frames, result);
+    }
+
+    /// Como `record`, para o frame na posição `position` do lote.
+    pub fn record_at(&mut self, position: u64, result: &Result<(), ValidationError>) {
+        self.frames += 1;
+        match result {
+            Ok(()) => self.accepted += 1,
+            Err(e) => {
+                let kind = e.kind().index();
+                self.rejections[kind] += 1;
+                push_sample(&mut self.samples[kind], position);
+            }
+        }
+    }
+
+    pub fn record_malformed(&mut self) {
+        self.record_malformed_at(self.frames);
+    }
+
+    pub fn record_malformed_at(&mut self, position: u64) {
+        self.frames += 1;
+        self.malformed += 1;
+        push_sample(&mut self.malformed_samples, position);
+    }
+
+    /// Soma os contadores de outro relatório (ex.: de outra thread).
+    pub fn merge(&mut self, other: &ValidationReport) {
+        self.frames += other.frames;
+        self.accepted += other.accepted;
+        self.malformed += other.malformed;
+        for (total, partial) in self.rejections.iter_mut().zip(other.rejections) {
+            *total += partial;
+        }
+        for (samples, partial) in self.samples.iter_mut().zip(&other.samples) {
+            merge_samples(samples, partial);
+        }
+        merge_samples(&mut self.malformed_samples, &other.malformed_samples);
+    }
+}
+
+fn push_sample(samples: &mut Vec<u64>, position: u64) {
+    if samples.len() < ValidationReport::SAMPLES_PER_KIND {
+        samples.push(position);
+    }
+}
+
+/// Mantém as menores posições das duas listas, como se os frames tivessem
+/// sido validados num único lote.
+fn merge_samples(samples: &mut Vec<u64>, other: &[u64]) {
+    samples.extend_from_slice(other);
+    samples.sort_unstable();
+    samples.truncate(ValidationReport::SAMPLES_PER_KIND);
+}
+
+/// Resumo para triagem: totais e, por motivo de rejeição, contagem e
+/// posições de exemplo.
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} frames: {} aceitos, {} rejeitados, {} malformados",
+            self.frames,
+            self.accepted,
+            self.rejected(),
+            self.malformed
+        )?;
+
+        let rows = ValidationErrorKind::ALL
+            .iter()
+            .map(|&kind| (format!("{:?}", kind), self.rejections_for(kind), self.samples_for(kind)))
+            .chain(std::iter::once(("Malformed".to_string(), self.malformed, self.malformed_samples.as_slice())));
+        for (name, count, samples) in rows.filter(|&(_, count, _)| count > 0) {
+            write!(f, "  {:<24} {:>10}", name, count)?;
+            if !samples.is_empty() {
+                let positions: Vec<String> = samples.iter().map(u64::to_string).collect();
+                let more = if count > samples.len() as u64 { ", ..." } else { "" };
+                write!(f, "  (frames {}{})", positions.join(", "), more)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingestion::zero_copy::{MessageHeader, Trade};
+    use crate::validation::integrity::{CompositeValidator, SymbolValidator, UnknownTypePolicy};
+
+    #[test]
+    fn test_mixed_batch_breakdown() {
+        let mut validator = CompositeValidator::builder()
+            .symbol_validator(SymbolValidator::whitelist(vec!["BTCUSD".to_string()]))
+            .unknown_type_policy(UnknownTypePolicy::Reject)
+            .build();
+
+        let ts = 1_700_000_000_000_000_000;
+        let trade = |price: i64, timestamp: u64, trade_id: u64| {
+            Trade::new(*b"BTCUSD\0\0", price * 100_000_000, 100_000_000, timestamp, 1, trade_id).to_frame()
+        };
+
+        let mut frames = vec![trade(50_000, ts + 10_000_000_000, 1)];
+        for i in 0..7 {
+            let mut corrupted = trade(50_000, ts + 20_000_000_000 + i, 10 + i);
+            corrupted[MessageHeader::SIZE + 12] ^= 0x01;
+            frames.push(corrupted);
+        }
+        frames.push(trade(-1, ts + 30_000_000_000, 20));
+        frames.push(trade(50_000, ts, 21));
+        frames.push(Trade::new(*b"XRPUSD\0\0", 1, 100_000_000, ts + 40_000_000_000, 1, 22).to_frame());
+        frames.push(MessageHeader::builder().msg_type(42).timestamp(ts).frame(&[0u8; 8]));
+        frames.push(trade(50_000, ts + 50_000_000_000, 23)[..MessageHeader::SIZE + 4].into());
+
+        let report = validator.validate_batch(&frames);
+        assert_eq!((report.frames, report.accepted, report.rejected(), report.malformed), (13, 1, 11, 1));
+        assert_eq!(report.rejections_for(ValidationErrorKind::ChecksumMismatch), 7);
+        assert_eq!(report.samples_for(ValidationErrorKind::ChecksumMismatch), [1, 2, 3, 4, 5]);
+        assert_eq!(report.samples_for(ValidationErrorKind::OutOfBounds), [8]);
+        assert_eq!(report.samples_for(ValidationErrorKind::TemporalOrderViolation), [9]);
+        assert_eq!(report.samples_for(ValidationErrorKind::InvalidSymbol), [10]);
+        assert_eq!(report.samples_for(ValidationErrorKind::UnknownMessageType), [11]);
+        assert_eq!(report.malformed_samples, [12]);
+
+        let summary = report.to_string();
+        assert!(summary.starts_with("13 frames: 1 aceitos, 11 rejeitados, 1 malformados"));
+        assert!(summary.contains("(frames 1, 2, 3, 4, 5, ...)"));
+        assert!(summary.contains("(frames 11)"));
+        assert!(!summary.contains("RateLimitExceeded"));
+    }
+}
@@ -0,0 +1,105 @@
+//! Estatísticas de validação por tipo de mensagem
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::validation::integrity::ValidationErrorKind;
+
+#[derive(Debug, Default)]
+struct TypeCounters {
+    received: AtomicU64,
+    rejected: [AtomicU64; ValidationErrorKind::COUNT],
+}
+
+/// Contadores indexados por `msg_type` (256 slots), atualizados com um
+/// `fetch_add` relaxado por mensagem — sem hash nem lock no caminho quente.
+pub struct MessageTypeStats {
+    counters: Box<[TypeCounters; 256]>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MessageTypeCounts {
+    pub msg_type: u8,
+    pub received: u64,
+    pub rejected: u64,
+    /// Rejeições por motivo, indexadas por `ValidationErrorKind::index`.
+    pub rejections: [u64; ValidationErrorKind::COUNT],
+}
+
+impl MessageTypeCounts {
+    pub fn rejections_for(&self, kind: ValidationErrorKind) -> u64 {
+        self.rejections[kind.index()]
+    }
+}
+
+impl MessageTypeStats {
+    pub fn new() -> Self {
+        Self { counters: Box::new(std::array::from_fn(|_| TypeCounters::default())) }
+    }
+
+    #[inline]
+    pub fn record(&self, msg_type: u8, rejection: Option<ValidationErrorKind>) {
+        let counters = &self.counters[msg_type as usize];
+        counters.received.fetch_add(1, Ordering::Relaxed);
+        if let Some(kind) = rejection {
+            counters.rejected[kind.index()].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Retorna apenas os tipos que já receberam mensagens, em ordem de `msg_type`.
+    pub fn snapshot(&self) -> Vec<MessageTypeCounts> {
+        self.counters
+            .iter()
+            .enumerate()
+            .filter_map(|(msg_type, counters)| {
+                let received = counters.received.load(Ordering::Relaxed);
+                if received == 0 {
+                    return None;
+                }
+                let rejections: [u64; ValidationErrorKind::COUNT] =
+                    std::array::from_fn(|i| counters.rejected[i].load(Ordering::Relaxed));
+                Some(MessageTypeCounts {
+                    msg_type: msg_type as u8,
+                    received,
+                    rejected: rejections.iter().sum(),
+                    rejections,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Default for MessageTypeStats {
+    fn default() -> Self { Self::new() }
+}
+
+/// Tempo acumulado por estágio da validação, em nanossegundos (feature `stage-timing`).
+#[cfg(feature = "stage-timing")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct StageTimings {
+    pub checksum_nanos: u64,
+    pub bounds_nanos: u64,
+    pub temporal_nanos: u64,
+    pub symbol_nanos: u64,
+}
+
+#[cfg(feature = "stage-timing")]
+#[derive(Debug, Default)]
+pub(crate) struct StageClock {
+    pub(crate) checksum: AtomicU64,
+    pub(crate) bounds: AtomicU64,
+    pub(crate) temporal: AtomicU64,
+    pub(crate) symbol: AtomicU64,
+}
+
+#[cfg(feature = "stage-timing")]
+impl StageClock {
+    pub(crate) fn snapshot(&self) -> StageTimings {
+        StageTimings {
+            checksum_nanos: self.checksum.load(Ordering::Relaxed),
+            bounds_nanos: self.bounds.load(Ordering::Relaxed),
+            temporal_nanos: self.temporal.load(Ordering::Relaxed),
+            symbol_nanos: self.symbol.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Compila e linka um consumidor C++ contra o wrapper gerado e a staticlib
+//! (pulado se não houver compilador C++ disponível)
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use tensorwerk_nervous::bridge::cpp::{ingestor_header, HEADER_NAME};
+use tensorwerk_nervous::ingestion::zero_copy::{MessageHeader, Trade};
+
+const CONSUMER: &str = r#"
+#include "tensorwerk_ingestor.hpp"
+
+#include <fstream>
+#include <iostream>
+#include <iterator>
+#include <vector>
+
+int main(int argc, char** argv) {
+    std::ifstream input(argv[1], std::ios::binary);
+    std::vector<uint8_t> feed((std::istreambuf_iterator<char>(input)), std::istreambuf_iterator<char>());
+
+    tensorwerk::Ingestor ingestor(1, 16);
+    if (int code = ingestor.process(feed); code != 0) {
+        std::cerr << "process: " << code << "\n";
+        return 1;
+    }
+
+    size_t messages = 0, bytes = 0;
+    for (auto& message : ingestor) {
+        messages += 1;
+        bytes += message.size();
+    }
+    auto stats = ingestor.stats();
+    if (stats.version != tensorwerk::STATS_BINARY_VERSION || stats.messages_received != messages) {
+        std::cerr << "stats: v" << stats.version << " " << stats.messages_received << "\n";
+        return 1;
+    }
+    static_assert(tensorwerk::FFI_PAUSED != tensorwerk::FFI_STALE_BUFFER);
+    const uint8_t* data = nullptr;
+    size_t len = 0;
+    if (int code = rust_buffer_data(RustBuffer{}, &data, &len); code != tensorwerk::FFI_STALE_BUFFER) {
+        std::cerr << "rust_buffer_data: " << code << "\n";
+        return 1;
+    }
+    std::cout << messages << " " << bytes << " " << ingestor.next().has_value() << "\n";
+    return 0;
+}
+"#;
+
+/// `target/<perfil>/deps`: a staticlib recém-compilada fica ao lado do
+/// executável do teste (a cópia em `target/<perfil>` só é atualizada num build
+/// da lib em si).
+fn artifact_dir() -> PathBuf {
+    std::env::current_exe().unwrap().parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn test_cpp_wrapper_compiles_links_and_iterates() {
+    let compiler = std::env::var("CXX").unwrap_or_else(|_| "c++".to_string());
+    if Command::new(&compiler).arg("--version").output().is_err() {
+        eprintln!("Compilador C++ ({}) indisponível; teste pulado", compiler);
+        return;
+    }
+    let staticlib = artifact_dir().join("libtensorwerk_nervous.a");
+    assert!(staticlib.exists(), "staticlib não encontrada em {}", staticlib.display());
+
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("cpp_wrapper");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join(HEADER_NAME), ingestor_header()).unwrap();
+    std::fs::write(dir.join("consumer.cpp"), CONSUMER).unwrap();
+
+    let ts = 1_700_000_000_000_000_000;
+    let feed: Vec<u8> = (0..3)
+        .flat_map(|i| Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, ts + i, 1, i).to_frame())
+        .collect();
+    std::fs::write(dir.join("feed.bin"), &feed).unwrap();
+
+    let binary = dir.join("consumer");
+    let build = Command::new(&compiler)
+        .args(["-std=c++20", "-Wall", "-Werror", "-o"])
+        .arg(&binary)
+        .arg(dir.join("consumer.cpp"))
+        .arg("-I")
+        .arg(&dir)
+        .arg(&staticlib)
+        .args(["-lpthread", "-ldl", "-lm"])
+        .output()
+        .unwrap();
+    assert!(build.status.success(), "{}", String::from_utf8_lossy(&build.stderr));
+
+    let run = Command::new(&binary).arg(dir.join("feed.bin")).output().unwrap();
+    assert!(run.status.success(), "{}", String::from_utf8_lossy(&run.stderr));
+    let frame_size = MessageHeader::SIZE + Trade::SIZE;
+    assert_eq!(String::from_utf8_lossy(&run.stdout).trim(), format!("3 {} 0", 3 * frame_size));
+}
@@ -0,0 +1,88 @@
+//! Rejeitar mensagens inválidas não pode alocar: sob uma enxurrada de
+//! mensagens ruins o caminho de rejeição vira vetor de DoS
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use tensorwerk_nervous::ingestion::zero_copy::{MessageHeader, Quote, Trade, TradeBatch};
+use tensorwerk_nervous::validation::integrity::{CompositeValidator, SymbolValidator, ValidationErrorKind};
+
+/// Conta alocações só da thread que ligou a contagem (o harness de testes
+/// aloca em outras threads).
+struct CountingAllocator;
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if COUNTING.with(Cell::get) {
+            ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        }
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations_during(f: impl FnOnce()) -> usize {
+    ALLOCATIONS.with(|count| count.set(0));
+    COUNTING.with(|counting| counting.set(true));
+    f();
+    COUNTING.with(|counting| counting.set(false));
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[test]
+fn test_rejections_do_not_allocate() {
+    let mut validator = CompositeValidator::builder()
+        .symbol_validator(SymbolValidator::whitelist(vec!["BTCUSD".to_string()]))
+        .build();
+    let ts = 1_700_000_000_000_000_000;
+    let frames = [
+        (Trade::new(*b"BTCUSD\0\0", 50_000 * 100_000_000, 100_000_000, 1, 1, 1).to_frame(), ValidationErrorKind::InvalidTimestamp),
+        (Trade::new(*b"BTCUSD\0\0", -1, 100_000_000, ts, 1, 2).to_frame(), ValidationErrorKind::OutOfBounds),
+        (Trade::new(*b"DOGEUSD\0", 100_000_000, 100_000_000, ts, 1, 3).to_frame(), ValidationErrorKind::InvalidSymbol),
+        (Trade::new(*b"BTC$USD\0", 100_000_000, 100_000_000, ts, 1, 4).to_frame(), ValidationErrorKind::InvalidSymbol),
+        (Quote::new(*b"BTCUSD\0\0", 101, 1, 100, 1, ts).to_frame(), ValidationErrorKind::InvalidSymbol),
+        (
+            TradeBatch::frame(&[
+                Trade::new(*b"BTCUSD\0\0", 100_000_000, 100_000_000, ts, 1, 5),
+                Trade::new(*b"BTCUSD\0\0", -1, 100_000_000, ts, 1, 6),
+            ]),
+            ValidationErrorKind::OutOfBounds,
+        ),
+        (
+            TradeBatch::frame(&[
+                Trade::new(*b"BTCUSD\0\0", 100_000_000, 100_000_000, ts + 1, 1, 7),
+                Trade::new(*b"BTCUSD\0\0", 100_000_000, 100_000_000, ts - 1_000_000_000, 1, 8),
+            ]),
+            ValidationErrorKind::TemporalOrderViolation,
+        ),
+    ];
+
+    // A primeira leitura dos limites (`ArcSwap`) numa thread registra o nó
+    // local dela e o mapa de trabalho dos lotes ganha capacidade, uma única
+    // vez; não conta como custo por rejeição
+    for (frame, _) in &frames {
+        let _ = validator.validate_message(&MessageHeader::from_bytes(frame).unwrap(), &frame[MessageHeader::SIZE..]);
+    }
+
+    let allocations = allocations_during(|| {
+        for _ in 0..1_000 {
+            for (frame, kind) in &frames {
+                let header = MessageHeader::from_bytes(frame).unwrap();
+                let error = validator.validate_message(&header, &frame[MessageHeader::SIZE..]).unwrap_err();
+                assert_eq!(error.kind(), *kind);
+            }
+        }
+    });
+    assert_eq!(allocations, 0);
+}